@@ -8,7 +8,4 @@ pub enum ContractError {
 
     #[error(transparent)]
     Base(#[from] cw721_base::error::ContractError),
-
-    #[error("Royalty percentage must be between 0 and 100")]
-    InvalidRoyaltyPercentage,
 }
@@ -5,10 +5,10 @@ pub mod query;
 pub use query::{check_royalties, query_royalties_info};
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{to_json_binary, Empty};
-pub use cw721_base::{
-    execute::Cw721Execute, msg::InstantiateMsg, query::Cw721Query, Cw721Contract,
-};
+use cosmwasm_std::{to_json_binary, Decimal, Empty};
+use cw721_base::error::ContractError as Cw721ContractError;
+use cw721_base::state::{DefaultOptionCollectionInfoExtension, ValidateMetadata};
+pub use cw721_base::{execute::Cw721Execute, query::Cw721Query, Cw721Contract};
 
 use crate::error::ContractError;
 
@@ -45,12 +45,48 @@ pub struct Metadata {
     pub royalty_payment_address: Option<String>,
 }
 
+impl ValidateMetadata for Metadata {
+    /// Validates all fields of the default cw721 metadata and the royalty percentage.
+    fn validate_metadata(&self) -> Result<(), Cw721ContractError> {
+        // no need to check < 0 because royalty_percentage is u64
+        if self.royalty_percentage.unwrap_or_default() > 100 {
+            return Err(Cw721ContractError::InvalidRoyaltyShare {
+                max_share: Decimal::percent(100),
+            });
+        }
+        cw721_base::state::Metadata {
+            image: self.image.clone(),
+            image_data: self.image_data.clone(),
+            external_url: self.external_url.clone(),
+            description: self.description.clone(),
+            name: self.name.clone(),
+            attributes: self.attributes.as_ref().map(|attributes| {
+                attributes
+                    .iter()
+                    .map(|t| cw721_base::state::Trait {
+                        display_type: t.display_type.clone(),
+                        trait_type: t.trait_type.clone(),
+                        value: t.value.clone(),
+                    })
+                    .collect()
+            }),
+            background_color: self.background_color.clone(),
+            animation_url: self.animation_url.clone(),
+            youtube_url: self.youtube_url.clone(),
+        }
+        .validate_metadata()
+    }
+}
+
 pub type Extension = Option<Metadata>;
 
 pub type MintExtension = Option<Extension>;
 
-pub type Cw2981Contract<'a> = Cw721Contract<'a, Extension, Empty, Empty>;
-pub type ExecuteMsg = cw721_base::msg::ExecuteMsg<Extension, Empty>;
+pub type Cw2981Contract<'a> =
+    Cw721Contract<'a, Extension, Empty, Empty, DefaultOptionCollectionInfoExtension>;
+pub type InstantiateMsg = cw721_base::msg::InstantiateMsg<DefaultOptionCollectionInfoExtension>;
+pub type ExecuteMsg =
+    cw721_base::msg::ExecuteMsg<Extension, Empty, DefaultOptionCollectionInfoExtension>;
 
 #[cfg(not(feature = "library"))]
 pub mod entry {
@@ -59,7 +95,7 @@ pub mod entry {
     use super::*;
 
     use cosmwasm_std::entry_point;
-    use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+    use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult};
 
     #[entry_point]
     pub fn instantiate(
@@ -85,27 +121,18 @@ pub mod entry {
         info: MessageInfo,
         msg: ExecuteMsg,
     ) -> Result<Response, ContractError> {
-        if let ExecuteMsg::Mint {
-            extension:
-                Some(Metadata {
-                    royalty_percentage: Some(royalty_percentage),
-                    ..
-                }),
-            ..
-        } = &msg
-        {
-            // validate royalty_percentage to be between 0 and 100
-            // no need to check < 0 because royalty_percentage is u64
-            if *royalty_percentage > 100 {
-                return Err(ContractError::InvalidRoyaltyPercentage);
-            }
-        }
-
         Cw2981Contract::default()
             .execute(deps, env, info, msg)
             .map_err(Into::into)
     }
 
+    #[entry_point]
+    pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+        Cw2981Contract::default()
+            .reply(deps, env, msg)
+            .map_err(Into::into)
+    }
+
     #[entry_point]
     pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         match msg {
@@ -123,8 +150,10 @@ pub mod entry {
 mod tests {
     use super::*;
     use crate::msg::{CheckRoyaltiesResponse, QueryMsg, RoyaltiesInfoResponse};
+    use cw721_base::msg::MintMsg;
 
-    use cosmwasm_std::{from_json, Uint128};
+    use cosmwasm_std::{from_json, Addr, Uint128};
+    use cw_ownable::Ownership;
 
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
 
@@ -139,8 +168,16 @@ mod tests {
         let init_msg = InstantiateMsg {
             name: "SpaceShips".to_string(),
             symbol: "SPACE".to_string(),
+            collection_info_extension: None,
+            creator: None,
             minter: None,
             withdraw_address: None,
+            max_batch_size: None,
+            max_supply: None,
+            token_id_policy: None,
+            allow_remint: None,
+            base_uri: None,
+            enable_snapshots: None,
         };
         entry::instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
 
@@ -176,8 +213,16 @@ mod tests {
         let init_msg = InstantiateMsg {
             name: "SpaceShips".to_string(),
             symbol: "SPACE".to_string(),
+            collection_info_extension: None,
+            creator: None,
             minter: None,
             withdraw_address: None,
+            max_batch_size: None,
+            max_supply: None,
+            token_id_policy: None,
+            allow_remint: None,
+            base_uri: None,
+            enable_snapshots: None,
         };
         entry::instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
 
@@ -194,8 +239,57 @@ mod tests {
             }),
         };
         // mint will return StdError
+        let err = entry::execute(deps.as_mut(), mock_env(), info.clone(), exec_msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Base(Cw721ContractError::InvalidRoyaltyShare {
+                max_share: Decimal::percent(100)
+            })
+        );
+
+        // same applies for batch minting
+        let exec_msg = ExecuteMsg::BatchMint {
+            mints: vec![MintMsg {
+                token_id: token_id.to_string(),
+                owner: "john".to_string(),
+                token_uri: None,
+                extension: Some(Metadata {
+                    royalty_percentage: Some(101),
+                    ..Metadata::default()
+                }),
+            }],
+        };
+        let err = entry::execute(deps.as_mut(), mock_env(), info.clone(), exec_msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Base(Cw721ContractError::InvalidRoyaltyShare {
+                max_share: Decimal::percent(100)
+            })
+        );
+
+        // ... and for updating nft info
+        let exec_msg = ExecuteMsg::Mint {
+            token_id: token_id.to_string(),
+            owner: "john".to_string(),
+            token_uri: Some("https://starships.example.com/Starship/Enterprise.json".into()),
+            extension: None,
+        };
+        entry::execute(deps.as_mut(), mock_env(), info.clone(), exec_msg).unwrap();
+        let exec_msg = ExecuteMsg::UpdateNftInfo {
+            token_id: token_id.to_string(),
+            token_uri: None,
+            extension: Some(Some(Metadata {
+                royalty_percentage: Some(101),
+                ..Metadata::default()
+            })),
+        };
         let err = entry::execute(deps.as_mut(), mock_env(), info, exec_msg).unwrap_err();
-        assert_eq!(err, ContractError::InvalidRoyaltyPercentage);
+        assert_eq!(
+            err,
+            ContractError::Base(Cw721ContractError::InvalidRoyaltyShare {
+                max_share: Decimal::percent(100)
+            })
+        );
     }
 
     #[test]
@@ -207,8 +301,16 @@ mod tests {
         let init_msg = InstantiateMsg {
             name: "SpaceShips".to_string(),
             symbol: "SPACE".to_string(),
+            collection_info_extension: None,
+            creator: None,
             minter: None,
             withdraw_address: None,
+            max_batch_size: None,
+            max_supply: None,
+            token_id_policy: None,
+            allow_remint: None,
+            base_uri: None,
+            enable_snapshots: None,
         };
         entry::instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
 
@@ -246,8 +348,16 @@ mod tests {
         let init_msg = InstantiateMsg {
             name: "SpaceShips".to_string(),
             symbol: "SPACE".to_string(),
+            collection_info_extension: None,
+            creator: None,
             minter: None,
             withdraw_address: None,
+            max_batch_size: None,
+            max_supply: None,
+            token_id_policy: None,
+            allow_remint: None,
+            base_uri: None,
+            enable_snapshots: None,
         };
         let env = mock_env();
         entry::instantiate(deps.as_mut(), env.clone(), info.clone(), init_msg).unwrap();
@@ -324,4 +434,44 @@ mod tests {
         .unwrap();
         assert_eq!(res, voyager_expected);
     }
+
+    #[test]
+    fn query_minter_and_creator_ownership() {
+        let mut deps = mock_dependencies();
+
+        let info = mock_info(CREATOR, &[]);
+        let init_msg = InstantiateMsg {
+            name: "SpaceShips".to_string(),
+            symbol: "SPACE".to_string(),
+            collection_info_extension: None,
+            creator: None,
+            minter: Some("minter".to_string()),
+            withdraw_address: None,
+            max_batch_size: None,
+            max_supply: None,
+            token_id_policy: None,
+            allow_remint: None,
+            base_uri: None,
+            enable_snapshots: None,
+        };
+        entry::instantiate(deps.as_mut(), mock_env(), info, init_msg).unwrap();
+
+        let query_owner = |msg: QueryMsg| -> Option<Addr> {
+            let ownership: Ownership<Addr> =
+                from_json(entry::query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+            ownership.owner
+        };
+        assert_eq!(
+            query_owner(QueryMsg::GetMinterOwnership {}),
+            Some(Addr::unchecked("minter"))
+        );
+        assert_eq!(
+            query_owner(QueryMsg::Ownership {}),
+            Some(Addr::unchecked("minter"))
+        );
+        assert_eq!(
+            query_owner(QueryMsg::GetCreatorOwnership {}),
+            Some(Addr::unchecked(CREATOR))
+        );
+    }
 }
@@ -4,10 +4,14 @@ use cosmwasm_std::{Addr, Uint128};
 use cw721::msg::Cw721QueryMsg;
 use cw721_base::{
     msg::{
-        AllNftInfoResponse, ApprovalResponse, ApprovalsResponse, MinterResponse, NftInfoResponse,
-        NumTokensResponse, OperatorResponse, OperatorsResponse, OwnerOfResponse, TokensResponse,
+        AllNftInfoResponse, ApprovalResponse, ApprovalsResponse, ApprovedTokensResponse,
+        BalanceOfResponse, CollectionInfoAndExtensionResponse, HooksResponse, MinterResponse,
+        NftInfoResponse, NftInfosResponse, NftLockResponse, NftLocksResponse, NumTokensResponse,
+        OperatorGrantsResponse, OperatorResponse, OperatorsResponse, OrderBy,
+        OwnerOfAtHeightResponse, OwnerOfResponse, PauseStatusResponse, SupplyResponse,
+        TokenHistoryResponse, TokensResponse, TotalMintedResponse, UserOfResponse,
     },
-    state::CollectionInfo,
+    state::{BaseUri, CollectionInfo, DefaultOptionCollectionInfoExtension, Placeholder},
 };
 use cw_ownable::Ownership;
 
@@ -72,21 +76,71 @@ pub enum QueryMsg {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    /// List all owners that granted the operator access to all of their tokens
+    #[returns(OperatorGrantsResponse)]
+    OperatorGrants {
+        operator: String,
+        /// unset or false will filter out expired items, you must set to true to see them
+        include_expired: Option<bool>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// List all tokens the spender is approved for
+    #[returns(ApprovedTokensResponse)]
+    ApprovedTokens {
+        spender: String,
+        /// unset or false will filter out expired approvals, you must set to true to see them
+        include_expired: Option<bool>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     /// Total number of tokens issued
     #[returns(NumTokensResponse)]
     NumTokens {},
+    /// Number of tokens owned by the given address
+    #[returns(BalanceOfResponse)]
+    BalanceOf { owner: String },
+    /// Return the owner of the given token at the beginning of the given block
+    #[returns(OwnerOfAtHeightResponse)]
+    OwnerOfAtHeight { token_id: String, height: u64 },
+    /// Number of tokens owned by the given address at the beginning of the given block
+    #[returns(BalanceOfResponse)]
+    BalanceOfAtHeight { owner: String, height: u64 },
+    /// Number of tokens, max supply and the token id used by the next `MintNext`
+    #[returns(SupplyResponse)]
+    Supply {},
+    /// Number of tokens ever minted and burned, `NumTokens` is the difference of both
+    #[returns(TotalMintedResponse)]
+    TotalMinted {},
+    /// Returns true in case the token has been burned
+    #[returns(bool)]
+    IsBurned { token_id: String },
+    /// List all ids of burned tokens
+    #[returns(TokensResponse)]
+    BurnedTokens {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 
     #[returns(CollectionInfo)]
     ContractInfo {},
 
-    /// With MetaData Extension.
-    /// Returns top-level metadata about the contract
-    #[returns(CollectionInfo)]
+    /// Returns collection info including its extension
+    #[returns(CollectionInfoAndExtensionResponse<DefaultOptionCollectionInfoExtension>)]
     GetCollectionInfo {},
 
+    /// Deprecated: use GetMinterOwnership instead!
     #[returns(Ownership<Addr>)]
     Ownership {},
 
+    /// Return the minter ownership, minter is allowed to mint new NFTs
+    #[returns(Ownership<Addr>)]
+    GetMinterOwnership {},
+
+    /// Return the creator ownership, creator owns the collection
+    #[returns(Ownership<Addr>)]
+    GetCreatorOwnership {},
+
     /// With MetaData Extension.
     /// Returns metadata about one particular token, based on *ERC721 Metadata JSON Schema*
     /// but directly from the contract
@@ -101,6 +155,25 @@ pub enum QueryMsg {
         /// unset or false will filter out expired approvals, you must set to true to see them
         include_expired: Option<bool>,
     },
+    /// With MetaData Extension.
+    /// Requires pagination. Returns the result of `AllNftInfo` for all tokens of the contract.
+    #[returns(NftInfosResponse<Extension>)]
+    AllNftInfos {
+        start_after: Option<String>,
+        limit: Option<u32>,
+        /// unset or false will filter out expired approvals, you must set to true to see them
+        include_expired: Option<bool>,
+    },
+    /// With MetaData Extension.
+    /// Requires pagination. Returns the result of `AllNftInfo` for all tokens of the given owner.
+    #[returns(NftInfosResponse<Extension>)]
+    NftInfosByOwner {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        /// unset or false will filter out expired approvals, you must set to true to see them
+        include_expired: Option<bool>,
+    },
 
     /// With Enumerable extension.
     /// Returns all tokens owned by the given address, [] if unset.
@@ -108,14 +181,22 @@ pub enum QueryMsg {
     Tokens {
         owner: String,
         start_after: Option<String>,
+        /// Lists tokens up to, but excluding, this token id
+        end_before: Option<String>,
         limit: Option<u32>,
+        /// Defaults to ascending order
+        order: Option<OrderBy>,
     },
     /// With Enumerable extension.
     /// Requires pagination. Lists all token_ids controlled by the contract.
     #[returns(TokensResponse)]
     AllTokens {
         start_after: Option<String>,
+        /// Lists tokens up to, but excluding, this token id
+        end_before: Option<String>,
         limit: Option<u32>,
+        /// Defaults to ascending order
+        order: Option<OrderBy>,
     },
 
     /// Return the minter
@@ -125,6 +206,65 @@ pub enum QueryMsg {
     #[returns(Option<String>)]
     GetWithdrawAddress {},
 
+    /// Returns the base uri used for deriving token uris, `None` if not set.
+    #[returns(Option<BaseUri>)]
+    BaseUri {},
+
+    /// Returns the metadata returned for all tokens until revealed, `None` if revealed.
+    #[returns(Option<Placeholder<Extension>>)]
+    Placeholder {},
+
+    /// Returns until when transfers and minting are paused, `None` if not paused.
+    #[returns(PauseStatusResponse)]
+    PauseStatus {},
+
+    /// Returns the lock of a token, `None` if not locked
+    #[returns(NftLockResponse)]
+    NftLock {
+        token_id: String,
+        /// unset or false will filter out expired locks, you must set to true to see them
+        include_expired: Option<bool>,
+    },
+    /// List all locked tokens of the given owner
+    #[returns(NftLocksResponse)]
+    NftLocks {
+        owner: String,
+        /// unset or false will filter out expired locks, you must set to true to see them
+        include_expired: Option<bool>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Returns the nonce expected by the next `Permit` of the given owner
+    #[returns(u64)]
+    PermitNonce { owner: String },
+
+    /// Returns the user of a token, `None` if not set or expired
+    #[returns(UserOfResponse)]
+    UserOf { token_id: String },
+    /// List all tokens the given address is a non-expired user of
+    #[returns(TokensResponse)]
+    TokensOfUser {
+        user: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Returns the recorded mints, transfers, sends and burns of a token, oldest first
+    #[returns(TokenHistoryResponse)]
+    TokenHistory {
+        token_id: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    /// Returns all registered hook contracts
+    #[returns(HooksResponse)]
+    Hooks {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
     // -- below queries, Extension and GetCollectionInfoExtension, are just dummies, since type annotations are required for
     // -- TMetadataExtension and TCollectionInfoExtension, Error:
     // -- "type annotations needed: cannot infer type for type parameter `TMetadataExtension` declared on the enum `Cw721QueryMsg`"
@@ -132,8 +272,8 @@ pub enum QueryMsg {
     Extension { msg: Extension },
 }
 
-impl From<QueryMsg> for Cw721QueryMsg<Extension> {
-    fn from(msg: QueryMsg) -> Cw721QueryMsg<Extension> {
+impl From<QueryMsg> for Cw721QueryMsg<Extension, DefaultOptionCollectionInfoExtension> {
+    fn from(msg: QueryMsg) -> Cw721QueryMsg<Extension, DefaultOptionCollectionInfoExtension> {
         match msg {
             QueryMsg::OwnerOf {
                 token_id,
@@ -143,7 +283,21 @@ impl From<QueryMsg> for Cw721QueryMsg<Extension> {
                 include_expired,
             },
             QueryMsg::NumTokens {} => Cw721QueryMsg::NumTokens {},
+            QueryMsg::BalanceOf { owner } => Cw721QueryMsg::BalanceOf { owner },
+            QueryMsg::OwnerOfAtHeight { token_id, height } => {
+                Cw721QueryMsg::OwnerOfAtHeight { token_id, height }
+            }
+            QueryMsg::BalanceOfAtHeight { owner, height } => {
+                Cw721QueryMsg::BalanceOfAtHeight { owner, height }
+            }
+            QueryMsg::Supply {} => Cw721QueryMsg::Supply {},
+            QueryMsg::TotalMinted {} => Cw721QueryMsg::TotalMinted {},
+            QueryMsg::IsBurned { token_id } => Cw721QueryMsg::IsBurned { token_id },
+            QueryMsg::BurnedTokens { start_after, limit } => {
+                Cw721QueryMsg::BurnedTokens { start_after, limit }
+            }
             QueryMsg::ContractInfo {} => Cw721QueryMsg::ContractInfo {},
+            QueryMsg::GetCollectionInfo {} => Cw721QueryMsg::GetCollectionInfo {},
             QueryMsg::NftInfo { token_id } => Cw721QueryMsg::NftInfo { token_id },
             QueryMsg::AllNftInfo {
                 token_id,
@@ -152,21 +306,98 @@ impl From<QueryMsg> for Cw721QueryMsg<Extension> {
                 token_id,
                 include_expired,
             },
+            QueryMsg::AllNftInfos {
+                start_after,
+                limit,
+                include_expired,
+            } => Cw721QueryMsg::AllNftInfos {
+                start_after,
+                limit,
+                include_expired,
+            },
+            QueryMsg::NftInfosByOwner {
+                owner,
+                start_after,
+                limit,
+                include_expired,
+            } => Cw721QueryMsg::NftInfosByOwner {
+                owner,
+                start_after,
+                limit,
+                include_expired,
+            },
             QueryMsg::Tokens {
                 owner,
                 start_after,
+                end_before,
                 limit,
+                order,
             } => Cw721QueryMsg::Tokens {
                 owner,
                 start_after,
+                end_before,
                 limit,
+                order,
+            },
+            QueryMsg::AllTokens {
+                start_after,
+                end_before,
+                limit,
+                order,
+            } => Cw721QueryMsg::AllTokens {
+                start_after,
+                end_before,
+                limit,
+                order,
             },
-            QueryMsg::AllTokens { start_after, limit } => {
-                Cw721QueryMsg::AllTokens { start_after, limit }
-            }
             #[allow(deprecated)]
             QueryMsg::Minter {} => Cw721QueryMsg::Minter {},
+            QueryMsg::Ownership {} => Cw721QueryMsg::Ownership {},
+            QueryMsg::GetMinterOwnership {} => Cw721QueryMsg::GetMinterOwnership {},
+            QueryMsg::GetCreatorOwnership {} => Cw721QueryMsg::GetCreatorOwnership {},
             QueryMsg::GetWithdrawAddress {} => Cw721QueryMsg::GetWithdrawAddress {},
+            QueryMsg::BaseUri {} => Cw721QueryMsg::BaseUri {},
+            QueryMsg::Placeholder {} => Cw721QueryMsg::Placeholder {},
+            QueryMsg::PauseStatus {} => Cw721QueryMsg::PauseStatus {},
+            QueryMsg::NftLock {
+                token_id,
+                include_expired,
+            } => Cw721QueryMsg::NftLock {
+                token_id,
+                include_expired,
+            },
+            QueryMsg::NftLocks {
+                owner,
+                include_expired,
+                start_after,
+                limit,
+            } => Cw721QueryMsg::NftLocks {
+                owner,
+                include_expired,
+                start_after,
+                limit,
+            },
+            QueryMsg::PermitNonce { owner } => Cw721QueryMsg::PermitNonce { owner },
+            QueryMsg::UserOf { token_id } => Cw721QueryMsg::UserOf { token_id },
+            QueryMsg::TokensOfUser {
+                user,
+                start_after,
+                limit,
+            } => Cw721QueryMsg::TokensOfUser {
+                user,
+                start_after,
+                limit,
+            },
+            QueryMsg::TokenHistory {
+                token_id,
+                start_after,
+                limit,
+            } => Cw721QueryMsg::TokenHistory {
+                token_id,
+                start_after,
+                limit,
+            },
+            QueryMsg::Hooks { start_after, limit } => Cw721QueryMsg::Hooks { start_after, limit },
             QueryMsg::AllOperators {
                 owner,
                 include_expired,
@@ -178,6 +409,28 @@ impl From<QueryMsg> for Cw721QueryMsg<Extension> {
                 start_after,
                 limit,
             },
+            QueryMsg::OperatorGrants {
+                operator,
+                include_expired,
+                start_after,
+                limit,
+            } => Cw721QueryMsg::OperatorGrants {
+                operator,
+                include_expired,
+                start_after,
+                limit,
+            },
+            QueryMsg::ApprovedTokens {
+                spender,
+                include_expired,
+                start_after,
+                limit,
+            } => Cw721QueryMsg::ApprovedTokens {
+                spender,
+                include_expired,
+                start_after,
+                limit,
+            },
             QueryMsg::Approval {
                 token_id,
                 spender,
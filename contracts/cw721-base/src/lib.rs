@@ -26,13 +26,13 @@ pub mod entry {
 
     #[cfg(not(feature = "library"))]
     use cosmwasm_std::entry_point;
-    use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+    use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult};
     use cw721::{
         error::Cw721ContractError,
         execute::Cw721Execute,
         msg::{Cw721ExecuteMsg, Cw721InstantiateMsg, Cw721MigrateMsg, Cw721QueryMsg},
         query::Cw721Query,
-        state::DefaultOptionMetadataExtension,
+        state::{DefaultOptionCollectionInfoExtension, DefaultOptionMetadataExtension},
     };
 
     // This makes a conscious choice on the various generics used by the contract
@@ -41,9 +41,14 @@ pub mod entry {
         deps: DepsMut,
         env: Env,
         info: MessageInfo,
-        msg: Cw721InstantiateMsg,
+        msg: Cw721InstantiateMsg<DefaultOptionCollectionInfoExtension>,
     ) -> Result<Response, Cw721ContractError> {
-        let contract = Cw721Contract::<DefaultOptionMetadataExtension, Empty, Empty>::default();
+        let contract = Cw721Contract::<
+            DefaultOptionMetadataExtension,
+            Empty,
+            Empty,
+            DefaultOptionCollectionInfoExtension,
+        >::default();
         contract.instantiate(deps, env, info, msg, CONTRACT_NAME, CONTRACT_VERSION)
     }
 
@@ -52,19 +57,44 @@ pub mod entry {
         deps: DepsMut,
         env: Env,
         info: MessageInfo,
-        msg: Cw721ExecuteMsg<DefaultOptionMetadataExtension, Empty>,
+        msg: Cw721ExecuteMsg<
+            DefaultOptionMetadataExtension,
+            Empty,
+            DefaultOptionCollectionInfoExtension,
+        >,
     ) -> Result<Response, Cw721ContractError> {
-        let contract = Cw721Contract::<DefaultOptionMetadataExtension, Empty, Empty>::default();
+        let contract = Cw721Contract::<
+            DefaultOptionMetadataExtension,
+            Empty,
+            Empty,
+            DefaultOptionCollectionInfoExtension,
+        >::default();
         contract.execute(deps, env, info, msg)
     }
 
+    #[cfg_attr(not(feature = "library"), entry_point)]
+    pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, Cw721ContractError> {
+        let contract = Cw721Contract::<
+            DefaultOptionMetadataExtension,
+            Empty,
+            Empty,
+            DefaultOptionCollectionInfoExtension,
+        >::default();
+        contract.reply(deps, env, msg)
+    }
+
     #[cfg_attr(not(feature = "library"), entry_point)]
     pub fn query(
         deps: Deps,
         env: Env,
-        msg: Cw721QueryMsg<DefaultOptionMetadataExtension>,
+        msg: Cw721QueryMsg<DefaultOptionMetadataExtension, DefaultOptionCollectionInfoExtension>,
     ) -> StdResult<Binary> {
-        let contract = Cw721Contract::<DefaultOptionMetadataExtension, Empty, Empty>::default();
+        let contract = Cw721Contract::<
+            DefaultOptionMetadataExtension,
+            Empty,
+            Empty,
+            DefaultOptionCollectionInfoExtension,
+        >::default();
         contract.query(deps, env, msg)
     }
 
@@ -74,7 +104,12 @@ pub mod entry {
         env: Env,
         msg: Cw721MigrateMsg,
     ) -> Result<Response, Cw721ContractError> {
-        let contract = Cw721Contract::<DefaultOptionMetadataExtension, Empty, Empty>::default();
+        let contract = Cw721Contract::<
+            DefaultOptionMetadataExtension,
+            Empty,
+            Empty,
+            DefaultOptionCollectionInfoExtension,
+        >::default();
         contract.migrate(deps, env, msg, CONTRACT_NAME, CONTRACT_VERSION)
     }
 }
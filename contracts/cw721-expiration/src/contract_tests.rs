@@ -3,7 +3,7 @@
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
 
 use cosmwasm_std::{
-    from_json, to_json_binary, Addr, CosmosMsg, DepsMut, Empty, Response, StdError, WasmMsg,
+    from_json, to_json_binary, Addr, CosmosMsg, DepsMut, Empty, Event, Response, StdError, WasmMsg,
 };
 
 use cw721::error::Cw721ContractError;
@@ -12,7 +12,7 @@ use cw721::msg::{
     OwnerOfResponse, TokensResponse,
 };
 use cw721::receiver::Cw721ReceiveMsg;
-use cw721::state::{CollectionInfo, MINTER};
+use cw721::state::{CollectionInfo, DefaultOptionCollectionInfoExtension};
 use cw721::{query::Cw721Query, Approval, Expiration};
 use cw_ownable::{Action, Ownership, OwnershipError};
 
@@ -29,15 +29,33 @@ const SYMBOL: &str = "MGK";
 fn setup_contract(
     deps: DepsMut<'_>,
     expiration_days: u16,
-) -> Cw721ExpirationContract<'static, DefaultOptionMetadataExtension, Empty, Empty> {
-    let contract =
-        Cw721ExpirationContract::<DefaultOptionMetadataExtension, Empty, Empty>::default();
+) -> Cw721ExpirationContract<
+    'static,
+    DefaultOptionMetadataExtension,
+    Empty,
+    Empty,
+    DefaultOptionCollectionInfoExtension,
+> {
+    let contract = Cw721ExpirationContract::<
+        DefaultOptionMetadataExtension,
+        Empty,
+        Empty,
+        DefaultOptionCollectionInfoExtension,
+    >::default();
     let msg = InstantiateMsg {
         expiration_days,
         name: CONTRACT_NAME.to_string(),
         symbol: SYMBOL.to_string(),
+        collection_info_extension: None,
+        creator: None,
         minter: Some(String::from(MINTER_ADDR)),
         withdraw_address: None,
+        max_batch_size: None,
+        max_supply: None,
+        token_id_policy: None,
+        allow_remint: None,
+        base_uri: None,
+        enable_snapshots: None,
     };
     let info = mock_info("creator", &[]);
     let res = contract.instantiate(deps, mock_env(), info, msg).unwrap();
@@ -48,15 +66,27 @@ fn setup_contract(
 #[test]
 fn proper_instantiation() {
     let mut deps = mock_dependencies();
-    let contract =
-        Cw721ExpirationContract::<DefaultOptionMetadataExtension, Empty, Empty>::default();
+    let contract = Cw721ExpirationContract::<
+        DefaultOptionMetadataExtension,
+        Empty,
+        Empty,
+        DefaultOptionCollectionInfoExtension,
+    >::default();
 
     let msg = InstantiateMsg {
         expiration_days: 1,
         name: CONTRACT_NAME.to_string(),
         symbol: SYMBOL.to_string(),
+        collection_info_extension: None,
+        creator: None,
         minter: Some(String::from(MINTER_ADDR)),
         withdraw_address: Some(String::from(CREATOR_ADDR)),
+        max_batch_size: None,
+        max_supply: None,
+        token_id_policy: None,
+        allow_remint: None,
+        base_uri: None,
+        enable_snapshots: None,
     };
     let info = mock_info("creator", &[]);
     let env = mock_env();
@@ -68,7 +98,12 @@ fn proper_instantiation() {
     assert_eq!(0, res.messages.len());
 
     // it worked, let's query the state
-    let minter_ownership = MINTER.get_ownership(deps.as_ref().storage).unwrap();
+    let minter_ownership = contract
+        .base_contract
+        .config
+        .minter
+        .get_ownership(deps.as_ref().storage)
+        .unwrap();
     assert_eq!(Some(Addr::unchecked(MINTER_ADDR)), minter_ownership.owner);
     let collection_info = contract
         .base_contract
@@ -98,7 +133,15 @@ fn proper_instantiation() {
 
     // list the token_ids
     let tokens = contract
-        .query_all_tokens_include_expired_nft(deps.as_ref(), mock_env(), None, None, false)
+        .query_all_tokens_include_expired_nft(
+            deps.as_ref(),
+            mock_env(),
+            None,
+            None,
+            None,
+            None,
+            false,
+        )
         .unwrap();
     assert_eq!(0, tokens.tokens.len());
 }
@@ -106,15 +149,27 @@ fn proper_instantiation() {
 #[test]
 fn proper_instantiation_with_collection_info() {
     let mut deps = mock_dependencies();
-    let contract =
-        Cw721ExpirationContract::<DefaultOptionMetadataExtension, Empty, Empty>::default();
+    let contract = Cw721ExpirationContract::<
+        DefaultOptionMetadataExtension,
+        Empty,
+        Empty,
+        DefaultOptionCollectionInfoExtension,
+    >::default();
 
     let msg = InstantiateMsg {
         expiration_days: 1,
         name: CONTRACT_NAME.to_string(),
         symbol: SYMBOL.to_string(),
+        collection_info_extension: None,
+        creator: None,
         minter: Some(String::from(MINTER_ADDR)),
         withdraw_address: Some(String::from(CREATOR_ADDR)),
+        max_batch_size: None,
+        max_supply: None,
+        token_id_policy: None,
+        allow_remint: None,
+        base_uri: None,
+        enable_snapshots: None,
     };
     let info = mock_info("creator", &[]);
     let env = mock_env();
@@ -126,7 +181,12 @@ fn proper_instantiation_with_collection_info() {
     assert_eq!(0, res.messages.len());
 
     // it worked, let's query the state
-    let minter_ownership = MINTER.get_ownership(deps.as_ref().storage).unwrap();
+    let minter_ownership = contract
+        .base_contract
+        .config
+        .minter
+        .get_ownership(deps.as_ref().storage)
+        .unwrap();
     assert_eq!(Some(Addr::unchecked(MINTER_ADDR)), minter_ownership.owner);
     let collection_info = contract
         .base_contract
@@ -156,7 +216,15 @@ fn proper_instantiation_with_collection_info() {
 
     // list the token_ids
     let tokens = contract
-        .query_all_tokens_include_expired_nft(deps.as_ref(), mock_env(), None, None, false)
+        .query_all_tokens_include_expired_nft(
+            deps.as_ref(),
+            mock_env(),
+            None,
+            None,
+            None,
+            None,
+            false,
+        )
         .unwrap();
     assert_eq!(0, tokens.tokens.len());
 }
@@ -258,7 +326,15 @@ fn test_mint() {
 
     // list the token_ids
     let tokens = contract
-        .query_all_tokens_include_expired_nft(deps.as_ref(), mock_env(), None, None, false)
+        .query_all_tokens_include_expired_nft(
+            deps.as_ref(),
+            mock_env(),
+            None,
+            None,
+            None,
+            None,
+            false,
+        )
         .unwrap();
     assert_eq!(1, tokens.tokens.len());
     assert_eq!(vec![token_id], tokens.tokens);
@@ -419,7 +495,15 @@ fn test_burn() {
 
     // list the token_ids
     let tokens = contract
-        .query_all_tokens_include_expired_nft(deps.as_ref(), env.clone(), None, None, false)
+        .query_all_tokens_include_expired_nft(
+            deps.as_ref(),
+            env.clone(),
+            None,
+            None,
+            None,
+            None,
+            false,
+        )
         .unwrap();
     assert!(tokens.tokens.is_empty());
 
@@ -504,9 +588,12 @@ fn test_transfer_nft() {
         res,
         Response::new()
             .add_attribute("action", "transfer_nft")
-            .add_attribute("sender", owner)
-            .add_attribute("recipient", new_owner)
-            .add_attribute("token_id", token_id.clone())
+            .add_event(
+                Event::new("transfer_nft")
+                    .add_attribute("sender", owner)
+                    .add_attribute("recipient", new_owner)
+                    .add_attribute("token_id", token_id.clone())
+            )
     );
 
     // assert invalid nft throws error
@@ -590,9 +677,12 @@ fn test_send_nft() {
         Response::new()
             .add_message(expected)
             .add_attribute("action", "send_nft")
-            .add_attribute("sender", "venus")
-            .add_attribute("recipient", "another_contract")
-            .add_attribute("token_id", token_id.clone())
+            .add_event(
+                Event::new("send_nft")
+                    .add_attribute("sender", "venus")
+                    .add_attribute("contract", "another_contract")
+                    .add_attribute("token_id", token_id.clone())
+            )
     );
 
     // assert invalid nft throws error
@@ -669,9 +759,12 @@ fn test_approve_revoke() {
         res,
         Response::new()
             .add_attribute("action", "approve")
-            .add_attribute("sender", "demeter")
-            .add_attribute("spender", "random")
-            .add_attribute("token_id", token_id.clone())
+            .add_event(
+                Event::new("approve")
+                    .add_attribute("sender", "demeter")
+                    .add_attribute("spender", "random")
+                    .add_attribute("token_id", token_id.clone())
+            )
     );
 
     // test approval query
@@ -836,7 +929,15 @@ fn test_approve_all_revoke_all() {
 
     // paginate the token_ids
     let tokens = contract
-        .query_all_tokens_include_expired_nft(deps.as_ref(), mock_env(), None, Some(1), false)
+        .query_all_tokens_include_expired_nft(
+            deps.as_ref(),
+            mock_env(),
+            None,
+            None,
+            Some(1),
+            None,
+            false,
+        )
         .unwrap();
     assert_eq!(1, tokens.tokens.len());
     assert_eq!(vec![token_id1.clone()], tokens.tokens);
@@ -845,7 +946,9 @@ fn test_approve_all_revoke_all() {
             deps.as_ref(),
             mock_env(),
             Some(token_id1.clone()),
+            None,
             Some(3),
+            None,
             false,
         )
         .unwrap();
@@ -865,8 +968,11 @@ fn test_approve_all_revoke_all() {
         res,
         Response::new()
             .add_attribute("action", "approve_all")
-            .add_attribute("sender", "demeter")
-            .add_attribute("operator", "random")
+            .add_event(
+                Event::new("approve_all")
+                    .add_attribute("sender", "demeter")
+                    .add_attribute("operator", "random")
+            )
     );
 
     // random can now transfer
@@ -1134,12 +1240,28 @@ fn test_tokens_by_owner() {
     // get all tokens in order:
     let expected = vec![token_id1.clone(), token_id2.clone(), token_id3.clone()];
     let tokens = contract
-        .query_all_tokens_include_expired_nft(deps.as_ref(), mock_env(), None, None, false)
+        .query_all_tokens_include_expired_nft(
+            deps.as_ref(),
+            mock_env(),
+            None,
+            None,
+            None,
+            None,
+            false,
+        )
         .unwrap();
     assert_eq!(&expected, &tokens.tokens);
     // paginate
     let tokens = contract
-        .query_all_tokens_include_expired_nft(deps.as_ref(), mock_env(), None, Some(2), false)
+        .query_all_tokens_include_expired_nft(
+            deps.as_ref(),
+            mock_env(),
+            None,
+            None,
+            Some(2),
+            None,
+            false,
+        )
         .unwrap();
     assert_eq!(&expected[..2], &tokens.tokens[..]);
     let tokens = contract
@@ -1148,6 +1270,8 @@ fn test_tokens_by_owner() {
             mock_env(),
             Some(expected[1].clone()),
             None,
+            None,
+            None,
             false,
         )
         .unwrap();
@@ -1164,12 +1288,23 @@ fn test_tokens_by_owner() {
             demeter.clone(),
             None,
             None,
+            None,
+            None,
             false,
         )
         .unwrap();
     assert_eq!(&by_demeter, &tokens.tokens);
     let tokens = contract
-        .query_tokens_include_expired_nft(deps.as_ref(), mock_env(), ceres, None, None, false)
+        .query_tokens_include_expired_nft(
+            deps.as_ref(),
+            mock_env(),
+            ceres,
+            None,
+            None,
+            None,
+            None,
+            false,
+        )
         .unwrap();
     assert_eq!(&by_ceres, &tokens.tokens);
 
@@ -1180,7 +1315,9 @@ fn test_tokens_by_owner() {
             mock_env(),
             demeter.clone(),
             None,
+            None,
             Some(1),
+            None,
             false,
         )
         .unwrap();
@@ -1191,7 +1328,9 @@ fn test_tokens_by_owner() {
             mock_env(),
             demeter,
             Some(by_demeter[0].clone()),
+            None,
             Some(3),
+            None,
             false,
         )
         .unwrap();
@@ -1336,6 +1475,328 @@ fn test_owner_of() {
     );
 }
 
+#[test]
+fn test_owner_of_at_height() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), 1);
+    let minter = mock_info(MINTER_ADDR, &[]);
+
+    let token_id = "grow1".to_string();
+    let mut env = mock_env();
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: token_id.clone(),
+        owner: String::from("ark"),
+        token_uri: None,
+        extension: None,
+    };
+    contract
+        .execute(deps.as_mut(), env.clone(), minter, mint_msg)
+        .unwrap();
+
+    // assert invalid nft throws error, before snapshots are checked
+    let mint_date = env.block.time;
+    let expiration = env.block.time.plus_days(1);
+    env.block.time = expiration;
+    let height = env.block.height;
+    let error = contract
+        .query_owner_of_at_height_include_expired_nft(
+            deps.as_ref(),
+            env.clone(),
+            token_id.clone(),
+            height,
+            false,
+        )
+        .unwrap_err();
+    assert_eq!(
+        error,
+        ContractError::NftExpired {
+            token_id: token_id.clone(),
+            mint_date,
+            expiration
+        }
+    );
+
+    // assert invalid nft is passed to base contract, where snapshots are not enabled
+    let error = contract
+        .query_owner_of_at_height_include_expired_nft(deps.as_ref(), env, token_id, height, true)
+        .unwrap_err();
+    assert_eq!(
+        error,
+        ContractError::Std(StdError::generic_err("Snapshots are not enabled"))
+    );
+}
+
+#[test]
+fn test_nft_lock() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), 1);
+    let minter = mock_info(MINTER_ADDR, &[]);
+
+    let token_id = "grow1".to_string();
+    let owner = String::from("ark");
+    let mut env = mock_env();
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: token_id.clone(),
+        owner: owner.clone(),
+        token_uri: None,
+        extension: None,
+    };
+    contract
+        .execute(deps.as_mut(), env.clone(), minter, mint_msg)
+        .unwrap();
+    let lock_msg = Cw721ExecuteMsg::LockNft {
+        token_id: token_id.clone(),
+        until: Expiration::Never {},
+    };
+    contract
+        .execute(deps.as_mut(), env.clone(), mock_info(&owner, &[]), lock_msg)
+        .unwrap();
+
+    // assert valid nft is returned
+    let res = contract
+        .query_nft_lock_include_expired_nft(
+            deps.as_ref(),
+            env.clone(),
+            token_id.clone(),
+            false,
+            false,
+        )
+        .unwrap();
+    assert!(res.lock.is_some());
+    let res = contract
+        .query_nft_locks_include_expired_nft(
+            deps.as_ref(),
+            env.clone(),
+            owner.clone(),
+            false,
+            None,
+            None,
+            false,
+        )
+        .unwrap();
+    assert_eq!(res.locks.len(), 1);
+    assert_eq!(res.locks[0].token_id, token_id);
+
+    // assert invalid nft throws error or is not returned
+    let mint_date = env.block.time;
+    let expiration = env.block.time.plus_days(1);
+    env.block.time = expiration;
+    let error = contract
+        .query_nft_lock_include_expired_nft(
+            deps.as_ref(),
+            env.clone(),
+            token_id.clone(),
+            false,
+            false,
+        )
+        .unwrap_err();
+    assert_eq!(
+        error,
+        ContractError::NftExpired {
+            token_id: token_id.clone(),
+            mint_date,
+            expiration
+        }
+    );
+    let res = contract
+        .query_nft_locks_include_expired_nft(
+            deps.as_ref(),
+            env.clone(),
+            owner.clone(),
+            false,
+            None,
+            None,
+            false,
+        )
+        .unwrap();
+    assert_eq!(res.locks, vec![]);
+
+    // assert invalid nft is returned
+    let res = contract
+        .query_nft_lock_include_expired_nft(
+            deps.as_ref(),
+            env.clone(),
+            token_id.clone(),
+            false,
+            true,
+        )
+        .unwrap();
+    assert!(res.lock.is_some());
+    let res = contract
+        .query_nft_locks_include_expired_nft(deps.as_ref(), env, owner, false, None, None, true)
+        .unwrap();
+    assert_eq!(res.locks.len(), 1);
+}
+
+#[test]
+fn test_user_of() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), 1);
+    let minter = mock_info(MINTER_ADDR, &[]);
+
+    let token_id = "grow1".to_string();
+    let user = String::from("bob");
+    let mut env = mock_env();
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: token_id.clone(),
+        owner: String::from("ark"),
+        token_uri: None,
+        extension: None,
+    };
+    contract
+        .execute(deps.as_mut(), env.clone(), minter, mint_msg)
+        .unwrap();
+    let set_user_msg = Cw721ExecuteMsg::SetUser {
+        token_id: token_id.clone(),
+        user: Some(user.clone()),
+        expires: None,
+    };
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("ark", &[]),
+            set_user_msg,
+        )
+        .unwrap();
+
+    // assert valid nft is returned
+    let res = contract
+        .query_user_of_include_expired_nft(deps.as_ref(), env.clone(), token_id.clone(), false)
+        .unwrap();
+    assert_eq!(res.user.unwrap().user, Addr::unchecked(&user));
+    let tokens = contract
+        .query_tokens_of_user_include_expired_nft(
+            deps.as_ref(),
+            env.clone(),
+            user.clone(),
+            None,
+            None,
+            false,
+        )
+        .unwrap();
+    assert_eq!(tokens.tokens, vec![token_id.clone()]);
+
+    // assert invalid nft throws error or is not returned
+    let mint_date = env.block.time;
+    let expiration = env.block.time.plus_days(1);
+    env.block.time = expiration;
+    let error = contract
+        .query_user_of_include_expired_nft(deps.as_ref(), env.clone(), token_id.clone(), false)
+        .unwrap_err();
+    assert_eq!(
+        error,
+        ContractError::NftExpired {
+            token_id: token_id.clone(),
+            mint_date,
+            expiration
+        }
+    );
+    let tokens = contract
+        .query_tokens_of_user_include_expired_nft(
+            deps.as_ref(),
+            env.clone(),
+            user.clone(),
+            None,
+            None,
+            false,
+        )
+        .unwrap();
+    assert_eq!(
+        tokens,
+        TokensResponse {
+            tokens: vec![],
+            next: None,
+        }
+    );
+
+    // assert invalid nft is returned
+    contract
+        .query_user_of_include_expired_nft(deps.as_ref(), env.clone(), token_id.clone(), true)
+        .unwrap();
+    let tokens = contract
+        .query_tokens_of_user_include_expired_nft(deps.as_ref(), env, user, None, None, true)
+        .unwrap();
+    assert_eq!(tokens.tokens, vec![token_id]);
+}
+
+#[test]
+fn test_approved_tokens() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut(), 1);
+    let minter = mock_info(MINTER_ADDR, &[]);
+
+    let token_id = "grow1".to_string();
+    let spender = String::from("bob");
+    let mut env = mock_env();
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: token_id.clone(),
+        owner: String::from("ark"),
+        token_uri: None,
+        extension: None,
+    };
+    contract
+        .execute(deps.as_mut(), env.clone(), minter, mint_msg)
+        .unwrap();
+    let approve_msg = Cw721ExecuteMsg::Approve {
+        spender: spender.clone(),
+        token_id: token_id.clone(),
+        expires: None,
+    };
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("ark", &[]),
+            approve_msg,
+        )
+        .unwrap();
+
+    // assert valid nft is returned
+    let res = contract
+        .query_approved_tokens_include_expired_nft(
+            deps.as_ref(),
+            env.clone(),
+            spender.clone(),
+            false,
+            None,
+            None,
+            false,
+        )
+        .unwrap();
+    assert_eq!(res.tokens.len(), 1);
+    assert_eq!(res.tokens[0].token_id, token_id);
+
+    // assert invalid nft is not returned
+    env.block.time = env.block.time.plus_days(1);
+    let res = contract
+        .query_approved_tokens_include_expired_nft(
+            deps.as_ref(),
+            env.clone(),
+            spender.clone(),
+            false,
+            None,
+            None,
+            false,
+        )
+        .unwrap();
+    assert_eq!(res.tokens, vec![]);
+
+    // assert invalid nft is returned
+    let res = contract
+        .query_approved_tokens_include_expired_nft(
+            deps.as_ref(),
+            env,
+            spender,
+            false,
+            None,
+            None,
+            true,
+        )
+        .unwrap();
+    assert_eq!(res.tokens.len(), 1);
+}
+
 #[test]
 fn test_approval() {
     let mut deps = mock_dependencies();
@@ -1468,6 +1929,8 @@ fn test_tokens() {
             owner.clone(),
             None,
             None,
+            None,
+            None,
             false,
         )
         .unwrap();
@@ -1482,19 +1945,28 @@ fn test_tokens() {
             owner.clone(),
             None,
             None,
+            None,
+            None,
             false,
         )
         .unwrap();
-    assert_eq!(tokens, TokensResponse { tokens: vec![] });
+    assert_eq!(
+        tokens,
+        TokensResponse {
+            tokens: vec![],
+            next: None,
+        }
+    );
 
     // assert invalid nft is returned
     let tokens = contract
-        .query_tokens_include_expired_nft(deps.as_ref(), env, owner, None, None, true)
+        .query_tokens_include_expired_nft(deps.as_ref(), env, owner, None, None, None, None, true)
         .unwrap();
     assert_eq!(
         tokens,
         TokensResponse {
-            tokens: [token_id].to_vec()
+            tokens: [token_id].to_vec(),
+            next: None,
         }
     );
 }
@@ -1521,25 +1993,49 @@ fn test_all_tokens() {
 
     // assert valid nft is returned
     contract
-        .query_all_tokens_include_expired_nft(deps.as_ref(), env.clone(), None, None, false)
+        .query_all_tokens_include_expired_nft(
+            deps.as_ref(),
+            env.clone(),
+            None,
+            None,
+            None,
+            None,
+            false,
+        )
         .unwrap();
 
     // assert invalid nft is not returned
     let expiration = env.block.time.plus_days(1);
     env.block.time = expiration;
     let tokens = contract
-        .query_tokens_include_expired_nft(deps.as_ref(), env.clone(), owner, None, None, false)
+        .query_tokens_include_expired_nft(
+            deps.as_ref(),
+            env.clone(),
+            owner,
+            None,
+            None,
+            None,
+            None,
+            false,
+        )
         .unwrap();
-    assert_eq!(tokens, TokensResponse { tokens: vec![] });
+    assert_eq!(
+        tokens,
+        TokensResponse {
+            tokens: vec![],
+            next: None,
+        }
+    );
 
     // assert invalid nft is returned
     let tokens = contract
-        .query_all_tokens_include_expired_nft(deps.as_ref(), env, None, None, true)
+        .query_all_tokens_include_expired_nft(deps.as_ref(), env, None, None, None, None, true)
         .unwrap();
     assert_eq!(
         tokens,
         TokensResponse {
-            tokens: [token_id].to_vec()
+            tokens: [token_id].to_vec(),
+            next: None,
         }
     );
 }
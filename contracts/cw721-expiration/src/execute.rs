@@ -1,7 +1,8 @@
 use cosmwasm_std::{Binary, CustomMsg, DepsMut, Env, MessageInfo, Response};
 use cw721::{
     execute::Cw721Execute,
-    msg::{Cw721ExecuteMsg, Cw721InstantiateMsg},
+    msg::{Cw721ExecuteMsg, Cw721InstantiateMsg, MintMsg},
+    state::{ValidateCollectionInfo, ValidateMetadata},
     Expiration,
 };
 use serde::de::DeserializeOwned;
@@ -12,12 +13,25 @@ use crate::{
     CONTRACT_VERSION,
 };
 
-impl<'a, TMetadataExtension, TCustomResponseMessage, TMetadataExtensionMsg>
-    Cw721ExpirationContract<'a, TMetadataExtension, TCustomResponseMessage, TMetadataExtensionMsg>
+impl<
+        'a,
+        TMetadataExtension,
+        TCustomResponseMessage,
+        TMetadataExtensionMsg,
+        TCollectionInfoExtension,
+    >
+    Cw721ExpirationContract<
+        'a,
+        TMetadataExtension,
+        TCustomResponseMessage,
+        TMetadataExtensionMsg,
+        TCollectionInfoExtension,
+    >
 where
-    TMetadataExtension: Serialize + DeserializeOwned + Clone,
+    TMetadataExtension: Serialize + DeserializeOwned + Clone + ValidateMetadata,
     TCustomResponseMessage: CustomMsg,
     TMetadataExtensionMsg: CustomMsg,
+    TCollectionInfoExtension: Serialize + DeserializeOwned + Clone + ValidateCollectionInfo,
 {
     // -- instantiate --
     pub fn instantiate(
@@ -25,7 +39,7 @@ where
         deps: DepsMut,
         env: Env,
         info: MessageInfo,
-        msg: InstantiateMsg,
+        msg: InstantiateMsg<TCollectionInfoExtension>,
     ) -> Result<Response<TCustomResponseMessage>, ContractError> {
         if msg.expiration_days == 0 {
             return Err(ContractError::MinExpiration {});
@@ -34,6 +48,7 @@ where
             TMetadataExtension,
             TCustomResponseMessage,
             TMetadataExtensionMsg,
+            TCollectionInfoExtension,
         >::default();
         contract
             .expiration_days
@@ -45,8 +60,16 @@ where
             Cw721InstantiateMsg {
                 name: msg.name,
                 symbol: msg.symbol,
+                collection_info_extension: msg.collection_info_extension,
+                creator: msg.creator,
                 minter: msg.minter,
                 withdraw_address: msg.withdraw_address,
+                max_batch_size: msg.max_batch_size,
+                max_supply: msg.max_supply,
+                token_id_policy: msg.token_id_policy,
+                allow_remint: msg.allow_remint,
+                base_uri: msg.base_uri,
+                enable_snapshots: msg.enable_snapshots,
             },
             CONTRACT_NAME,
             CONTRACT_VERSION,
@@ -59,12 +82,13 @@ where
         deps: DepsMut,
         env: Env,
         info: MessageInfo,
-        msg: Cw721ExecuteMsg<TMetadataExtension, TMetadataExtensionMsg>,
+        msg: Cw721ExecuteMsg<TMetadataExtension, TMetadataExtensionMsg, TCollectionInfoExtension>,
     ) -> Result<Response<TCustomResponseMessage>, ContractError> {
         let contract = Cw721ExpirationContract::<
             TMetadataExtension,
             TCustomResponseMessage,
             TMetadataExtensionMsg,
+            TCollectionInfoExtension,
         >::default();
         match msg {
             Cw721ExecuteMsg::Mint {
@@ -75,6 +99,11 @@ where
             } => {
                 contract.mint_with_timestamp(deps, env, info, token_id, owner, token_uri, extension)
             }
+            Cw721ExecuteMsg::MintNext {
+                owner,
+                token_uri,
+                extension,
+            } => contract.mint_next_with_timestamp(deps, env, info, owner, token_uri, extension),
             Cw721ExecuteMsg::Approve {
                 spender,
                 token_id,
@@ -95,6 +124,52 @@ where
             Cw721ExecuteMsg::Burn { token_id } => {
                 contract.burn_nft_include_nft_expired(deps, env, info, token_id)
             }
+            Cw721ExecuteMsg::BatchMint { mints } => {
+                contract.batch_mint_with_timestamp(deps, env, info, mints)
+            }
+            Cw721ExecuteMsg::BatchApprove {
+                spender,
+                token_ids,
+                expires,
+            } => contract
+                .batch_approve_include_nft_expired(deps, env, info, spender, token_ids, expires),
+            Cw721ExecuteMsg::BatchRevoke { spender, token_ids } => {
+                contract.batch_revoke_include_nft_expired(deps, env, info, spender, token_ids)
+            }
+            Cw721ExecuteMsg::BatchTransferNft {
+                recipient,
+                token_ids,
+            } => contract
+                .batch_transfer_nft_include_nft_expired(deps, env, info, recipient, token_ids),
+            Cw721ExecuteMsg::BatchBurn { token_ids } => {
+                contract.batch_burn_include_nft_expired(deps, env, info, token_ids)
+            }
+            Cw721ExecuteMsg::LockNft { token_id, until } => {
+                contract.lock_nft_include_nft_expired(deps, env, info, token_id, until)
+            }
+            Cw721ExecuteMsg::SetUser {
+                token_id,
+                user,
+                expires,
+            } => contract.set_user_include_nft_expired(deps, env, info, token_id, user, expires),
+            Cw721ExecuteMsg::Permit {
+                owner_pubkey,
+                token_id,
+                spender,
+                expires,
+                nonce,
+                signature,
+            } => contract.permit_include_nft_expired(
+                deps,
+                env,
+                info,
+                owner_pubkey,
+                token_id,
+                spender,
+                expires,
+                nonce,
+                signature,
+            ),
             _ => {
                 let response = contract.base_contract.execute(deps, env, info, msg)?;
                 Ok(response)
@@ -118,7 +193,27 @@ where
             .save(deps.storage, &token_id, &mint_timstamp)?;
         let res = self
             .base_contract
-            .mint(deps, info, token_id, owner, token_uri, extension)?
+            .mint(deps, env, info, token_id, owner, token_uri, extension)?
+            .add_attribute("mint_timestamp", mint_timstamp.to_string());
+        Ok(res)
+    }
+
+    pub fn mint_next_with_timestamp(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        owner: String,
+        token_uri: Option<String>,
+        extension: TMetadataExtension,
+    ) -> Result<Response<TCustomResponseMessage>, ContractError> {
+        let token_id = self.base_contract.config.next_token_id(deps.storage)?;
+        let mint_timstamp = env.block.time;
+        self.mint_timestamps
+            .save(deps.storage, &token_id.to_string(), &mint_timstamp)?;
+        let res = self
+            .base_contract
+            .mint_next(deps, env, info, owner, token_uri, extension)?
             .add_attribute("mint_timestamp", mint_timstamp.to_string());
         Ok(res)
     }
@@ -138,6 +233,35 @@ where
             .approve(deps, env, info, spender, token_id, expires)?)
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn permit_include_nft_expired(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        owner_pubkey: Binary,
+        token_id: Option<String>,
+        spender: String,
+        expires: Option<Expiration>,
+        nonce: u64,
+        signature: Binary,
+    ) -> Result<Response<TCustomResponseMessage>, ContractError> {
+        if let Some(token_id) = &token_id {
+            self.assert_nft_expired(deps.as_ref(), &env, token_id)?;
+        }
+        Ok(self.base_contract.permit(
+            deps,
+            env,
+            info,
+            owner_pubkey,
+            token_id,
+            spender,
+            expires,
+            nonce,
+            signature,
+        )?)
+    }
+
     pub fn revoke_include_nft_expired(
         &self,
         deps: DepsMut,
@@ -152,6 +276,35 @@ where
             .revoke(deps, env, info, spender, token_id)?)
     }
 
+    pub fn lock_nft_include_nft_expired(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        until: Expiration,
+    ) -> Result<Response<TCustomResponseMessage>, ContractError> {
+        self.assert_nft_expired(deps.as_ref(), &env, token_id.as_str())?;
+        Ok(self
+            .base_contract
+            .lock_nft(deps, env, info, token_id, until)?)
+    }
+
+    pub fn set_user_include_nft_expired(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        user: Option<String>,
+        expires: Option<Expiration>,
+    ) -> Result<Response<TCustomResponseMessage>, ContractError> {
+        self.assert_nft_expired(deps.as_ref(), &env, token_id.as_str())?;
+        Ok(self
+            .base_contract
+            .set_user(deps, env, info, token_id, user, expires)?)
+    }
+
     pub fn transfer_nft_include_nft_expired(
         &self,
         deps: DepsMut,
@@ -191,4 +344,85 @@ where
         self.assert_nft_expired(deps.as_ref(), &env, token_id.as_str())?;
         Ok(self.base_contract.burn_nft(deps, env, info, token_id)?)
     }
+
+    pub fn batch_mint_with_timestamp(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        mints: Vec<MintMsg<TMetadataExtension>>,
+    ) -> Result<Response<TCustomResponseMessage>, ContractError> {
+        let mint_timstamp = env.block.time;
+        for mint in &mints {
+            self.mint_timestamps
+                .save(deps.storage, &mint.token_id, &mint_timstamp)?;
+        }
+        let res = self
+            .base_contract
+            .batch_mint(deps, env, info, mints)?
+            .add_attribute("mint_timestamp", mint_timstamp.to_string());
+        Ok(res)
+    }
+
+    pub fn batch_approve_include_nft_expired(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        spender: String,
+        token_ids: Vec<String>,
+        expires: Option<Expiration>,
+    ) -> Result<Response<TCustomResponseMessage>, ContractError> {
+        for token_id in &token_ids {
+            self.assert_nft_expired(deps.as_ref(), &env, token_id)?;
+        }
+        Ok(self
+            .base_contract
+            .batch_approve(deps, env, info, spender, token_ids, expires)?)
+    }
+
+    pub fn batch_revoke_include_nft_expired(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        spender: String,
+        token_ids: Vec<String>,
+    ) -> Result<Response<TCustomResponseMessage>, ContractError> {
+        for token_id in &token_ids {
+            self.assert_nft_expired(deps.as_ref(), &env, token_id)?;
+        }
+        Ok(self
+            .base_contract
+            .batch_revoke(deps, env, info, spender, token_ids)?)
+    }
+
+    pub fn batch_transfer_nft_include_nft_expired(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        recipient: String,
+        token_ids: Vec<String>,
+    ) -> Result<Response<TCustomResponseMessage>, ContractError> {
+        for token_id in &token_ids {
+            self.assert_nft_expired(deps.as_ref(), &env, token_id)?;
+        }
+        Ok(self
+            .base_contract
+            .batch_transfer_nft(deps, env, info, recipient, token_ids)?)
+    }
+
+    pub fn batch_burn_include_nft_expired(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_ids: Vec<String>,
+    ) -> Result<Response<TCustomResponseMessage>, ContractError> {
+        for token_id in &token_ids {
+            self.assert_nft_expired(deps.as_ref(), &env, token_id)?;
+        }
+        Ok(self.base_contract.batch_burn(deps, env, info, token_ids)?)
+    }
 }
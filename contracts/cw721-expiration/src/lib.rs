@@ -8,7 +8,7 @@ pub mod state;
 mod contract_tests;
 
 use cosmwasm_std::Empty;
-use cw721::state::DefaultOptionMetadataExtension;
+use cw721::state::{DefaultOptionCollectionInfoExtension, DefaultOptionMetadataExtension};
 
 // Version info for migration
 const CONTRACT_NAME: &str = "crates.io:cw721-expiration";
@@ -29,8 +29,10 @@ pub mod entry {
 
     #[cfg(not(feature = "library"))]
     use cosmwasm_std::entry_point;
-    use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response};
-    use cw721::{msg::Cw721ExecuteMsg, state::DefaultOptionMetadataExtension};
+    use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response};
+    use cw721::{
+        execute::Cw721Execute, msg::Cw721ExecuteMsg, state::DefaultOptionMetadataExtension,
+    };
 
     // This makes a conscious choice on the various generics used by the contract
     #[cfg_attr(not(feature = "library"), entry_point)]
@@ -38,10 +40,14 @@ pub mod entry {
         deps: DepsMut,
         env: Env,
         info: MessageInfo,
-        msg: InstantiateMsg,
+        msg: InstantiateMsg<DefaultOptionCollectionInfoExtension>,
     ) -> Result<Response, ContractError> {
-        let contract =
-            Cw721ExpirationContract::<DefaultOptionMetadataExtension, Empty, Empty>::default();
+        let contract = Cw721ExpirationContract::<
+            DefaultOptionMetadataExtension,
+            Empty,
+            Empty,
+            DefaultOptionCollectionInfoExtension,
+        >::default();
         contract.instantiate(deps, env, info, msg)
     }
 
@@ -50,21 +56,44 @@ pub mod entry {
         deps: DepsMut,
         env: Env,
         info: MessageInfo,
-        msg: Cw721ExecuteMsg<DefaultOptionMetadataExtension, Empty>,
+        msg: Cw721ExecuteMsg<
+            DefaultOptionMetadataExtension,
+            Empty,
+            DefaultOptionCollectionInfoExtension,
+        >,
     ) -> Result<Response, ContractError> {
-        let contract =
-            Cw721ExpirationContract::<DefaultOptionMetadataExtension, Empty, Empty>::default();
+        let contract = Cw721ExpirationContract::<
+            DefaultOptionMetadataExtension,
+            Empty,
+            Empty,
+            DefaultOptionCollectionInfoExtension,
+        >::default();
         contract.execute(deps, env, info, msg)
     }
 
+    #[entry_point]
+    pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+        let contract = Cw721ExpirationContract::<
+            DefaultOptionMetadataExtension,
+            Empty,
+            Empty,
+            DefaultOptionCollectionInfoExtension,
+        >::default();
+        Ok(contract.base_contract.reply(deps, env, msg)?)
+    }
+
     #[entry_point]
     pub fn query(
         deps: Deps,
         env: Env,
-        msg: QueryMsg<DefaultOptionMetadataExtension>,
+        msg: QueryMsg<DefaultOptionMetadataExtension, DefaultOptionCollectionInfoExtension>,
     ) -> Result<Binary, ContractError> {
-        let contract =
-            Cw721ExpirationContract::<DefaultOptionMetadataExtension, Empty, Empty>::default();
+        let contract = Cw721ExpirationContract::<
+            DefaultOptionMetadataExtension,
+            Empty,
+            Empty,
+            DefaultOptionCollectionInfoExtension,
+        >::default();
         contract.query(deps, env, msg)
     }
 
@@ -97,8 +126,16 @@ mod tests {
                 expiration_days: 0,
                 name: "collection_name".into(),
                 symbol: "collection_symbol".into(),
+                collection_info_extension: None,
+                creator: None,
                 minter: Some("minter".into()),
                 withdraw_address: None,
+                max_batch_size: None,
+                max_supply: None,
+                token_id_policy: None,
+                allow_remint: None,
+                base_uri: None,
+                enable_snapshots: None,
             },
         )
         .unwrap_err();
@@ -113,8 +150,16 @@ mod tests {
                 expiration_days: 1,
                 name: "".into(),
                 symbol: "".into(),
+                collection_info_extension: None,
+                creator: None,
                 minter: Some("minter".into()),
                 withdraw_address: None,
+                max_batch_size: None,
+                max_supply: None,
+                token_id_policy: None,
+                allow_remint: None,
+                base_uri: None,
+                enable_snapshots: None,
             },
         )
         .unwrap();
@@ -130,10 +175,15 @@ mod tests {
 
         assert_eq!(
             1,
-            Cw721ExpirationContract::<DefaultOptionMetadataExtension, Empty, Empty>::default()
-                .expiration_days
-                .load(deps.as_ref().storage)
-                .unwrap()
+            Cw721ExpirationContract::<
+                DefaultOptionMetadataExtension,
+                Empty,
+                Empty,
+                DefaultOptionCollectionInfoExtension,
+            >::default()
+            .expiration_days
+            .load(deps.as_ref().storage)
+            .unwrap()
         );
     }
 }
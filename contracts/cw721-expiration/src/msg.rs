@@ -1,14 +1,13 @@
 use crate::DefaultOptionMetadataExtension;
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::Addr;
-use cw721::state::CollectionInfo;
 use cw_ownable::Ownership;
 
 // expose to all others using contract, so others dont need to import cw721
 pub use cw721::msg::{Cw721ExecuteMsg as ExecuteMsg, Cw721MigrateMsg as MigrateMsg, *};
 
 #[cw_serde]
-pub struct InstantiateMsg {
+pub struct InstantiateMsg<TCollectionInfoExtension> {
     /// max 65535 days
     pub expiration_days: u16,
 
@@ -17,6 +16,12 @@ pub struct InstantiateMsg {
     pub name: String,
     /// Symbol of the NFT contract
     pub symbol: String,
+    /// Optional extension of the collection metadata
+    pub collection_info_extension: TCollectionInfoExtension,
+
+    /// The creator owns the collection and is allowed to e.g. set the withdraw address.
+    /// If not set, the sender of the instantiate message is the creator.
+    pub creator: Option<String>,
 
     /// The minter is the only one who can create new NFTs.
     /// This is designed for a base NFT that is controlled by an external program
@@ -24,11 +29,29 @@ pub struct InstantiateMsg {
     pub minter: Option<String>,
 
     pub withdraw_address: Option<String>,
+
+    /// Maximum number of items in batch messages, defaults to `DEFAULT_MAX_BATCH_SIZE`.
+    pub max_batch_size: Option<u32>,
+
+    /// Maximum number of tokens ever minted, including burned ones, unlimited if not set.
+    pub max_supply: Option<u64>,
+
+    /// Validation of token ids on mint, defaults to `TokenIdPolicy::default()`.
+    pub token_id_policy: Option<cw721::state::TokenIdPolicy>,
+
+    /// Allows minting ids of burned tokens again, defaults to false.
+    pub allow_remint: Option<bool>,
+
+    /// Used for deriving the token uri of tokens minted without one.
+    pub base_uri: Option<cw721::state::BaseUri>,
+
+    /// Records ownership changes for `OwnerOfAtHeight` and `BalanceOfAtHeight`, defaults to false.
+    pub enable_snapshots: Option<bool>,
 }
 
 #[cw_serde]
 #[derive(QueryResponses)]
-pub enum QueryMsg<TMetadataExtension> {
+pub enum QueryMsg<TMetadataExtension, TCollectionInfoExtension> {
     // -------- below adds `include_expired_nft` prop to cw721/src/msg.rs --------
     /// Return the owner of the given token, error if token does not exist
     #[returns(cw721::msg::OwnerOfResponse)]
@@ -79,13 +102,42 @@ pub enum QueryMsg<TMetadataExtension> {
         include_expired_nft: Option<bool>,
     },
 
+    /// With MetaData Extension.
+    /// Requires pagination. Returns the result of `AllNftInfo` for all tokens of the contract.
+    #[returns(cw721::msg::NftInfosResponse<DefaultOptionMetadataExtension>)]
+    AllNftInfos {
+        start_after: Option<String>,
+        limit: Option<u32>,
+        /// unset or false will filter out expired approvals, you must set to true to see them
+        include_expired: Option<bool>,
+        /// unset or false will filter out expired nfts, you must set to true to see them
+        include_expired_nft: Option<bool>,
+    },
+
+    /// With MetaData Extension.
+    /// Requires pagination. Returns the result of `AllNftInfo` for all tokens of the given owner.
+    #[returns(cw721::msg::NftInfosResponse<DefaultOptionMetadataExtension>)]
+    NftInfosByOwner {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        /// unset or false will filter out expired approvals, you must set to true to see them
+        include_expired: Option<bool>,
+        /// unset or false will filter out expired nfts, you must set to true to see them
+        include_expired_nft: Option<bool>,
+    },
+
     /// With Enumerable extension.
     /// Returns all tokens owned by the given address, [] if unset.
     #[returns(cw721::msg::TokensResponse)]
     Tokens {
         owner: String,
         start_after: Option<String>,
+        /// Lists tokens up to, but excluding, this token id
+        end_before: Option<String>,
         limit: Option<u32>,
+        /// Defaults to ascending order
+        order: Option<OrderBy>,
         /// unset or false will filter out expired nfts, you must set to true to see them
         include_expired_nft: Option<bool>,
     },
@@ -95,7 +147,11 @@ pub enum QueryMsg<TMetadataExtension> {
     #[returns(cw721::msg::TokensResponse)]
     AllTokens {
         start_after: Option<String>,
+        /// Lists tokens up to, but excluding, this token id
+        end_before: Option<String>,
         limit: Option<u32>,
+        /// Defaults to ascending order
+        order: Option<OrderBy>,
         /// unset or false will filter out expired nfts, you must set to true to see them
         include_expired_nft: Option<bool>,
     },
@@ -117,16 +173,64 @@ pub enum QueryMsg<TMetadataExtension> {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    /// List all owners that granted the operator access to all of their tokens
+    #[returns(cw721::msg::OperatorGrantsResponse)]
+    OperatorGrants {
+        operator: String,
+        /// unset or false will filter out expired items, you must set to true to see them
+        include_expired: Option<bool>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// List all tokens the spender is approved for
+    #[returns(cw721::msg::ApprovedTokensResponse)]
+    ApprovedTokens {
+        spender: String,
+        /// unset or false will filter out expired approvals, you must set to true to see them
+        include_expired: Option<bool>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        /// unset or false will filter out expired nfts, you must set to true to see them
+        include_expired_nft: Option<bool>,
+    },
     /// Total number of tokens issued, including all expired NFTs
     #[returns(cw721::msg::NumTokensResponse)]
     NumTokens {},
+    /// Number of tokens owned by the given address, including all expired NFTs
+    #[returns(cw721::msg::BalanceOfResponse)]
+    BalanceOf { owner: String },
+    /// Return the owner of the given token at the beginning of the given block
+    #[returns(cw721::msg::OwnerOfAtHeightResponse)]
+    OwnerOfAtHeight {
+        token_id: String,
+        height: u64,
+        /// unset or false will filter out expired nfts, you must set to true to see them
+        include_expired_nft: Option<bool>,
+    },
+    /// Number of tokens owned by the given address at the beginning of the given block, including all expired NFTs
+    #[returns(cw721::msg::BalanceOfResponse)]
+    BalanceOfAtHeight { owner: String, height: u64 },
+    /// Number of tokens, including all expired NFTs, max supply and the token id used by the next `MintNext`
+    #[returns(cw721::msg::SupplyResponse)]
+    Supply {},
+    /// Number of tokens ever minted and burned, including all expired NFTs. `NumTokens` is the difference of both
+    #[returns(cw721::msg::TotalMintedResponse)]
+    TotalMinted {},
+    /// Returns true in case the token has been burned
+    #[returns(bool)]
+    IsBurned { token_id: String },
+    /// List all ids of burned tokens
+    #[returns(cw721::msg::TokensResponse)]
+    BurnedTokens {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 
     #[returns(cw721::state::CollectionInfo)]
     ContractInfo {},
 
-    /// With MetaData Extension.
-    /// Returns top-level metadata about the contract
-    #[returns(CollectionInfo)]
+    /// Returns collection info including its extension
+    #[returns(cw721::msg::CollectionInfoAndExtensionResponse<TCollectionInfoExtension>)]
     GetCollectionInfo {},
 
     #[returns(Ownership<Addr>)]
@@ -135,6 +239,83 @@ pub enum QueryMsg<TMetadataExtension> {
     #[returns(Ownership<Addr>)]
     GetMinterOwnership {},
 
+    #[returns(Ownership<Addr>)]
+    GetCreatorOwnership {},
+
+    /// Returns true in case metadata is frozen for the given token or, if no `token_id` is set,
+    /// for the whole collection.
+    #[returns(bool)]
+    IsNftInfoFrozen { token_id: Option<String> },
+
+    /// Returns the base uri used for deriving token uris, `None` if not set.
+    #[returns(Option<cw721::state::BaseUri>)]
+    BaseUri {},
+
+    /// Returns the metadata returned for all tokens until revealed, `None` if revealed.
+    #[returns(Option<cw721::state::Placeholder<DefaultOptionMetadataExtension>>)]
+    Placeholder {},
+
+    /// Returns until when transfers and minting are paused, `None` if not paused.
+    #[returns(cw721::msg::PauseStatusResponse)]
+    PauseStatus {},
+
+    /// Returns the lock of a token, `None` if not locked
+    #[returns(cw721::msg::NftLockResponse)]
+    NftLock {
+        token_id: String,
+        /// unset or false will filter out expired locks, you must set to true to see them
+        include_expired: Option<bool>,
+        /// unset or false will filter out expired nfts, you must set to true to see them
+        include_expired_nft: Option<bool>,
+    },
+    /// List all locked tokens of the given owner
+    #[returns(cw721::msg::NftLocksResponse)]
+    NftLocks {
+        owner: String,
+        /// unset or false will filter out expired locks, you must set to true to see them
+        include_expired: Option<bool>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        /// unset or false will filter out expired nfts, you must set to true to see them
+        include_expired_nft: Option<bool>,
+    },
+
+    /// Returns the nonce expected by the next `Permit` of the given owner
+    #[returns(u64)]
+    PermitNonce { owner: String },
+
+    /// Returns the user of a token, `None` if not set or expired
+    #[returns(cw721::msg::UserOfResponse)]
+    UserOf {
+        token_id: String,
+        /// unset or false will filter out expired nfts, you must set to true to see them
+        include_expired_nft: Option<bool>,
+    },
+    /// List all tokens the given address is a non-expired user of
+    #[returns(cw721::msg::TokensResponse)]
+    TokensOfUser {
+        user: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        /// unset or false will filter out expired nfts, you must set to true to see them
+        include_expired_nft: Option<bool>,
+    },
+
+    /// Returns the recorded mints, transfers, sends and burns of a token, oldest first
+    #[returns(cw721::msg::TokenHistoryResponse)]
+    TokenHistory {
+        token_id: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    /// Returns all registered hook contracts
+    #[returns(cw721::msg::HooksResponse)]
+    Hooks {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
     /// Return the minter
     #[returns(MinterResponse)]
     Minter {},
@@ -143,6 +324,10 @@ pub enum QueryMsg<TMetadataExtension> {
     #[returns(())]
     Extension { msg: TMetadataExtension },
 
+    /// Do not use - dummy collection info extension query, needed for inferring type parameter during compile
+    #[returns(())]
+    GetCollectionInfoExtension { msg: TCollectionInfoExtension },
+
     #[returns(Option<String>)]
     GetWithdrawAddress {},
 }
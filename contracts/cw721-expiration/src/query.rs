@@ -1,7 +1,8 @@
 use cosmwasm_std::{to_json_binary, Binary, CustomMsg, Deps, Env, StdResult};
 use cw721::msg::{
-    AllNftInfoResponse, ApprovalResponse, ApprovalsResponse, NftInfoResponse, OwnerOfResponse,
-    TokensResponse,
+    AllNftInfoResponse, ApprovalResponse, ApprovalsResponse, ApprovedTokensResponse,
+    NftInfoResponse, NftInfosResponse, NftLockResponse, NftLocksResponse, OrderBy,
+    OwnerOfAtHeightResponse, OwnerOfResponse, TokensResponse, UserOfResponse,
 };
 use cw721::query::Cw721Query;
 use serde::de::DeserializeOwned;
@@ -9,23 +10,37 @@ use serde::Serialize;
 
 use crate::{error::ContractError, msg::QueryMsg, state::Cw721ExpirationContract};
 
-impl<'a, TMetadataExtension, TCustomResponseMessage, TMetadataExtensionMsg>
-    Cw721ExpirationContract<'a, TMetadataExtension, TCustomResponseMessage, TMetadataExtensionMsg>
+impl<
+        'a,
+        TMetadataExtension,
+        TCustomResponseMessage,
+        TMetadataExtensionMsg,
+        TCollectionInfoExtension,
+    >
+    Cw721ExpirationContract<
+        'a,
+        TMetadataExtension,
+        TCustomResponseMessage,
+        TMetadataExtensionMsg,
+        TCollectionInfoExtension,
+    >
 where
     TMetadataExtension: Serialize + DeserializeOwned + Clone,
     TCustomResponseMessage: CustomMsg,
     TMetadataExtensionMsg: CustomMsg,
+    TCollectionInfoExtension: Serialize + DeserializeOwned + Clone + Default,
 {
     pub fn query(
         &self,
         deps: Deps,
         env: Env,
-        msg: QueryMsg<TMetadataExtension>,
+        msg: QueryMsg<TMetadataExtension, TCollectionInfoExtension>,
     ) -> Result<Binary, ContractError> {
         let contract = Cw721ExpirationContract::<
             TMetadataExtension,
             TCustomResponseMessage,
             TMetadataExtensionMsg,
+            TCollectionInfoExtension,
         >::default();
         match msg {
             // -------- msgs with `include_expired_nft` prop --------
@@ -94,10 +109,44 @@ where
                     include_expired_nft.unwrap_or(false),
                 )?,
             )?),
+            QueryMsg::AllNftInfos {
+                start_after,
+                limit,
+                include_expired: include_expired_approval,
+                include_expired_nft,
+            } => Ok(to_json_binary(
+                &contract.query_all_nft_infos_include_expired_nft(
+                    deps,
+                    env,
+                    start_after,
+                    limit,
+                    include_expired_approval.unwrap_or(false),
+                    include_expired_nft.unwrap_or(false),
+                )?,
+            )?),
+            QueryMsg::NftInfosByOwner {
+                owner,
+                start_after,
+                limit,
+                include_expired: include_expired_approval,
+                include_expired_nft,
+            } => Ok(to_json_binary(
+                &contract.query_nft_infos_by_owner_include_expired_nft(
+                    deps,
+                    env,
+                    owner,
+                    start_after,
+                    limit,
+                    include_expired_approval.unwrap_or(false),
+                    include_expired_nft.unwrap_or(false),
+                )?,
+            )?),
             QueryMsg::Tokens {
                 owner,
                 start_after,
+                end_before,
                 limit,
+                order,
                 include_expired_nft: include_invalid,
             } => Ok(to_json_binary(
                 &contract.query_tokens_include_expired_nft(
@@ -105,20 +154,26 @@ where
                     env,
                     owner,
                     start_after,
+                    end_before,
                     limit,
+                    order,
                     include_invalid.unwrap_or(false),
                 )?,
             )?),
             QueryMsg::AllTokens {
                 start_after,
+                end_before,
                 limit,
+                order,
                 include_expired_nft: include_invalid,
             } => Ok(to_json_binary(
                 &contract.query_all_tokens_include_expired_nft(
                     deps,
                     env,
                     start_after,
+                    end_before,
                     limit,
+                    order,
                     include_invalid.unwrap_or(false),
                 )?,
             )?),
@@ -147,14 +202,85 @@ where
                 start_after,
                 limit,
             )?)?),
+            QueryMsg::OperatorGrants {
+                operator,
+                include_expired,
+                start_after,
+                limit,
+            } => Ok(to_json_binary(
+                &contract.base_contract.query_operator_grants(
+                    deps,
+                    env,
+                    operator,
+                    include_expired.unwrap_or(false),
+                    start_after,
+                    limit,
+                )?,
+            )?),
+            QueryMsg::ApprovedTokens {
+                spender,
+                include_expired,
+                start_after,
+                limit,
+                include_expired_nft,
+            } => Ok(to_json_binary(
+                &contract.query_approved_tokens_include_expired_nft(
+                    deps,
+                    env,
+                    spender,
+                    include_expired.unwrap_or(false),
+                    start_after,
+                    limit,
+                    include_expired_nft.unwrap_or(false),
+                )?,
+            )?),
             QueryMsg::NumTokens {} => Ok(to_json_binary(
                 &contract.base_contract.query_num_tokens(deps, env)?,
             )?),
+            QueryMsg::BalanceOf { owner } => Ok(to_json_binary(
+                &contract.base_contract.query_balance_of(deps, env, owner)?,
+            )?),
+            QueryMsg::OwnerOfAtHeight {
+                token_id,
+                height,
+                include_expired_nft,
+            } => Ok(to_json_binary(
+                &contract.query_owner_of_at_height_include_expired_nft(
+                    deps,
+                    env,
+                    token_id,
+                    height,
+                    include_expired_nft.unwrap_or(false),
+                )?,
+            )?),
+            QueryMsg::BalanceOfAtHeight { owner, height } => Ok(to_json_binary(
+                &contract
+                    .base_contract
+                    .query_balance_of_at_height(deps, env, owner, height)?,
+            )?),
+            QueryMsg::Supply {} => Ok(to_json_binary(
+                &contract.base_contract.query_supply(deps, env)?,
+            )?),
+            QueryMsg::TotalMinted {} => Ok(to_json_binary(
+                &contract.base_contract.query_total_minted(deps, env)?,
+            )?),
+            QueryMsg::IsBurned { token_id } => Ok(to_json_binary(
+                &contract
+                    .base_contract
+                    .query_is_burned(deps, env, token_id)?,
+            )?),
+            QueryMsg::BurnedTokens { start_after, limit } => Ok(to_json_binary(
+                &contract
+                    .base_contract
+                    .query_burned_tokens(deps, env, start_after, limit)?,
+            )?),
             QueryMsg::ContractInfo {} => Ok(to_json_binary(
                 &contract.base_contract.query_collection_info(deps, env)?,
             )?),
             QueryMsg::GetCollectionInfo {} => Ok(to_json_binary(
-                &contract.base_contract.query_collection_info(deps, env)?,
+                &contract
+                    .base_contract
+                    .query_collection_info_and_extension(deps)?,
             )?),
             QueryMsg::Ownership {} => Ok(to_json_binary(
                 &contract
@@ -166,12 +292,115 @@ where
                     .base_contract
                     .query_minter_ownership(deps.storage)?,
             )?),
+            QueryMsg::GetCreatorOwnership {} => Ok(to_json_binary(
+                &contract
+                    .base_contract
+                    .query_creator_ownership(deps.storage)?,
+            )?),
+            QueryMsg::BaseUri {} => Ok(to_json_binary(
+                &contract.base_contract.query_base_uri(deps)?,
+            )?),
+            QueryMsg::Placeholder {} => Ok(to_json_binary(
+                &contract.base_contract.query_placeholder(deps, env)?,
+            )?),
+            QueryMsg::IsNftInfoFrozen { token_id } => Ok(to_json_binary(
+                &contract
+                    .base_contract
+                    .query_is_nft_info_frozen(deps, token_id)?,
+            )?),
+            QueryMsg::PauseStatus {} => Ok(to_json_binary(
+                &contract.base_contract.query_pause_status(deps, env)?,
+            )?),
+            QueryMsg::NftLock {
+                token_id,
+                include_expired,
+                include_expired_nft,
+            } => Ok(to_json_binary(
+                &contract.query_nft_lock_include_expired_nft(
+                    deps,
+                    env,
+                    token_id,
+                    include_expired.unwrap_or(false),
+                    include_expired_nft.unwrap_or(false),
+                )?,
+            )?),
+            QueryMsg::NftLocks {
+                owner,
+                include_expired,
+                start_after,
+                limit,
+                include_expired_nft,
+            } => Ok(to_json_binary(
+                &contract.query_nft_locks_include_expired_nft(
+                    deps,
+                    env,
+                    owner,
+                    include_expired.unwrap_or(false),
+                    start_after,
+                    limit,
+                    include_expired_nft.unwrap_or(false),
+                )?,
+            )?),
+            QueryMsg::PermitNonce { owner } => Ok(to_json_binary(
+                &contract
+                    .base_contract
+                    .query_permit_nonce(deps, env, owner)?,
+            )?),
+            QueryMsg::UserOf {
+                token_id,
+                include_expired_nft,
+            } => Ok(to_json_binary(
+                &contract.query_user_of_include_expired_nft(
+                    deps,
+                    env,
+                    token_id,
+                    include_expired_nft.unwrap_or(false),
+                )?,
+            )?),
+            QueryMsg::TokensOfUser {
+                user,
+                start_after,
+                limit,
+                include_expired_nft,
+            } => Ok(to_json_binary(
+                &contract.query_tokens_of_user_include_expired_nft(
+                    deps,
+                    env,
+                    user,
+                    start_after,
+                    limit,
+                    include_expired_nft.unwrap_or(false),
+                )?,
+            )?),
+            QueryMsg::TokenHistory {
+                token_id,
+                start_after,
+                limit,
+            } => Ok(to_json_binary(
+                &contract.base_contract.query_token_history(
+                    deps,
+                    env,
+                    token_id,
+                    start_after,
+                    limit,
+                )?,
+            )?),
+            QueryMsg::Hooks { start_after, limit } => Ok(to_json_binary(
+                &contract
+                    .base_contract
+                    .query_hooks(deps, env, start_after, limit)?,
+            )?),
             QueryMsg::Minter {} => Ok(to_json_binary(
                 &contract.base_contract.query_minter(deps.storage)?,
             )?),
             QueryMsg::Extension { msg } => Ok(to_json_binary(
                 &contract.base_contract.query_extension(deps, env, msg)?,
             )?),
+            QueryMsg::GetCollectionInfoExtension { msg } => Ok(to_json_binary(
+                &contract
+                    .base_contract
+                    .query_collection_info_extension(deps, env, msg)?,
+            )?),
             QueryMsg::GetWithdrawAddress {} => Ok(to_json_binary(
                 &contract.base_contract.query_withdraw_address(deps)?,
             )?),
@@ -207,6 +436,22 @@ where
             .query_owner_of(deps, env, token_id, include_expired_approval)?)
     }
 
+    pub fn query_owner_of_at_height_include_expired_nft(
+        &self,
+        deps: Deps,
+        env: Env,
+        token_id: String,
+        height: u64,
+        include_expired_nft: bool,
+    ) -> Result<OwnerOfAtHeightResponse, ContractError> {
+        if !include_expired_nft {
+            self.assert_nft_expired(deps, &env, token_id.as_str())?;
+        }
+        Ok(self
+            .base_contract
+            .query_owner_of_at_height(deps, env, token_id, height)?)
+    }
+
     pub fn query_approval_include_expired_nft(
         &self,
         deps: Deps,
@@ -245,30 +490,58 @@ where
             .query_approvals(deps, env, token_id, include_expired_approval)?)
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn query_approved_tokens_include_expired_nft(
+        &self,
+        deps: Deps,
+        env: Env,
+        spender: String,
+        include_expired_approval: bool,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        include_expired_nft: bool,
+    ) -> StdResult<ApprovedTokensResponse> {
+        let tokens = self.base_contract.query_approved_tokens(
+            deps,
+            env.clone(),
+            spender,
+            include_expired_approval,
+            start_after,
+            limit,
+        )?;
+        if include_expired_nft {
+            return Ok(tokens);
+        }
+        Ok(ApprovedTokensResponse {
+            tokens: self.filter_expired(deps, &env, tokens.tokens, |token| token.token_id.as_str()),
+            next: tokens.next,
+        })
+    }
+
     pub fn query_tokens_include_expired_nft(
         &self,
         deps: Deps,
         env: Env,
         owner: String,
         start_after: Option<String>,
+        end_before: Option<String>,
         limit: Option<u32>,
+        order: Option<OrderBy>,
         include_expired_nft: bool,
     ) -> StdResult<TokensResponse> {
-        let tokens =
-            self.base_contract
-                .query_tokens(deps, env.clone(), owner, start_after, limit)?;
+        let tokens = self.base_contract.query_tokens(
+            deps,
+            env.clone(),
+            owner,
+            start_after,
+            end_before,
+            limit,
+            order,
+        )?;
         if include_expired_nft {
             return Ok(tokens);
         }
-        let filtered: Vec<_> = tokens
-            .tokens
-            .iter()
-            .filter(|token_id| {
-                self.is_nft_expired(deps, &env, token_id).unwrap_or(false) // Convert Option<bool> to bool
-            })
-            .map(|token_id| token_id.to_string())
-            .collect();
-        Ok(TokensResponse { tokens: filtered })
+        Ok(self.filter_expired_tokens(deps, &env, tokens))
     }
 
     pub fn query_all_tokens_include_expired_nft(
@@ -276,24 +549,98 @@ where
         deps: Deps,
         env: Env,
         start_after: Option<String>,
+        end_before: Option<String>,
         limit: Option<u32>,
+        order: Option<OrderBy>,
         include_expired_nft: bool,
     ) -> Result<TokensResponse, ContractError> {
-        let tokens = self
+        let tokens = self.base_contract.query_all_tokens(
+            deps,
+            env.clone(),
+            start_after,
+            end_before,
+            limit,
+            order,
+        )?;
+        if include_expired_nft {
+            return Ok(tokens);
+        }
+        Ok(self.filter_expired_tokens(deps, &env, tokens))
+    }
+
+    pub fn query_nft_lock_include_expired_nft(
+        &self,
+        deps: Deps,
+        env: Env,
+        token_id: String,
+        include_expired_lock: bool,
+        include_expired_nft: bool,
+    ) -> Result<NftLockResponse, ContractError> {
+        if !include_expired_nft {
+            self.assert_nft_expired(deps, &env, token_id.as_str())?;
+        }
+        Ok(self
             .base_contract
-            .query_all_tokens(deps, env.clone(), start_after, limit)?;
+            .query_nft_lock(deps, env, token_id, include_expired_lock)?)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn query_nft_locks_include_expired_nft(
+        &self,
+        deps: Deps,
+        env: Env,
+        owner: String,
+        include_expired_lock: bool,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        include_expired_nft: bool,
+    ) -> StdResult<NftLocksResponse> {
+        let locks = self.base_contract.query_nft_locks(
+            deps,
+            env.clone(),
+            owner,
+            include_expired_lock,
+            start_after,
+            limit,
+        )?;
+        if include_expired_nft {
+            return Ok(locks);
+        }
+        Ok(NftLocksResponse {
+            locks: self.filter_expired(deps, &env, locks.locks, |lock| lock.token_id.as_str()),
+            next: locks.next,
+        })
+    }
+
+    pub fn query_user_of_include_expired_nft(
+        &self,
+        deps: Deps,
+        env: Env,
+        token_id: String,
+        include_expired_nft: bool,
+    ) -> Result<UserOfResponse, ContractError> {
+        if !include_expired_nft {
+            self.assert_nft_expired(deps, &env, token_id.as_str())?;
+        }
+        Ok(self.base_contract.query_user_of(deps, env, token_id)?)
+    }
+
+    pub fn query_tokens_of_user_include_expired_nft(
+        &self,
+        deps: Deps,
+        env: Env,
+        user: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        include_expired_nft: bool,
+    ) -> StdResult<TokensResponse> {
+        let tokens =
+            self.base_contract
+                .query_tokens_of_user(deps, env.clone(), user, start_after, limit)?;
         if include_expired_nft {
             return Ok(tokens);
         }
-        let filtered: Vec<_> = tokens
-            .tokens
-            .iter()
-            .filter(|token_id| {
-                self.is_nft_expired(deps, &env, token_id).unwrap_or(false) // Convert Option<bool> to bool
-            })
-            .map(|token_id| token_id.to_string())
-            .collect();
-        Ok(TokensResponse { tokens: filtered })
+        Ok(self.filter_expired_tokens(deps, &env, tokens))
     }
 
     pub fn query_all_nft_info_include_expired_nft(
@@ -312,7 +659,98 @@ where
             .query_all_nft_info(deps, env, token_id, include_expired_approval)?)
     }
 
+    pub fn query_all_nft_infos_include_expired_nft(
+        &self,
+        deps: Deps,
+        env: Env,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        include_expired_approval: bool,
+        include_expired_nft: bool,
+    ) -> StdResult<NftInfosResponse<TMetadataExtension>> {
+        let nft_infos = self.base_contract.query_all_nft_infos(
+            deps,
+            env.clone(),
+            start_after,
+            limit,
+            include_expired_approval,
+        )?;
+        if include_expired_nft {
+            return Ok(nft_infos);
+        }
+        Ok(self.filter_expired_nft_infos(deps, &env, nft_infos))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn query_nft_infos_by_owner_include_expired_nft(
+        &self,
+        deps: Deps,
+        env: Env,
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        include_expired_approval: bool,
+        include_expired_nft: bool,
+    ) -> StdResult<NftInfosResponse<TMetadataExtension>> {
+        let nft_infos = self.base_contract.query_nft_infos_by_owner(
+            deps,
+            env.clone(),
+            owner,
+            start_after,
+            limit,
+            include_expired_approval,
+        )?;
+        if include_expired_nft {
+            return Ok(nft_infos);
+        }
+        Ok(self.filter_expired_nft_infos(deps, &env, nft_infos))
+    }
+
     // --- helpers ---
+    /// Removes items of expired NFTs from a page returned by the base contract. Pages may therefore
+    /// contain less than `limit` items, while `next` still continues after the unfiltered page.
+    fn filter_expired<T>(
+        &self,
+        deps: Deps,
+        env: &Env,
+        items: Vec<T>,
+        token_id: impl Fn(&T) -> &str,
+    ) -> Vec<T> {
+        items
+            .into_iter()
+            .filter(|item| {
+                self.is_nft_expired(deps, env, token_id(item))
+                    .unwrap_or(false)
+            })
+            .collect()
+    }
+
+    fn filter_expired_tokens(
+        &self,
+        deps: Deps,
+        env: &Env,
+        tokens: TokensResponse,
+    ) -> TokensResponse {
+        TokensResponse {
+            tokens: self.filter_expired(deps, env, tokens.tokens, String::as_str),
+            next: tokens.next,
+        }
+    }
+
+    fn filter_expired_nft_infos(
+        &self,
+        deps: Deps,
+        env: &Env,
+        nft_infos: NftInfosResponse<TMetadataExtension>,
+    ) -> NftInfosResponse<TMetadataExtension> {
+        NftInfosResponse {
+            nft_infos: self.filter_expired(deps, env, nft_infos.nft_infos, |nft_info| {
+                nft_info.token_id.as_str()
+            }),
+            next: nft_infos.next,
+        }
+    }
+
     pub fn is_nft_expired(&self, deps: Deps, env: &Env, token_id: &str) -> StdResult<bool> {
         // any non-expired token approval can send
         let mint_date = self.mint_timestamps.load(deps.storage, token_id)?;
@@ -13,7 +13,7 @@ use cw2::set_contract_version;
 use cw20::Cw20ReceiveMsg;
 use cw721::helpers::Cw721Contract;
 use cw721::msg::{Cw721ExecuteMsg, Cw721InstantiateMsg};
-use cw721::state::DefaultOptionMetadataExtension;
+use cw721::state::{DefaultOptionCollectionInfoExtension, DefaultOptionMetadataExtension};
 use cw_utils::parse_reply_instantiate_data;
 
 // version info for migration info
@@ -57,12 +57,22 @@ pub fn instantiate(
     let sub_msg: Vec<SubMsg> = vec![SubMsg {
         msg: WasmMsg::Instantiate {
             code_id: msg.token_code_id,
-            msg: to_json_binary(&Cw721InstantiateMsg {
-                name: msg.name.clone(),
-                symbol: msg.symbol,
-                minter: None,
-                withdraw_address: msg.withdraw_address,
-            })?,
+            msg: to_json_binary(
+                &Cw721InstantiateMsg::<DefaultOptionCollectionInfoExtension> {
+                    name: msg.name.clone(),
+                    symbol: msg.symbol,
+                    collection_info_extension: None,
+                    creator: None,
+                    minter: None,
+                    withdraw_address: msg.withdraw_address,
+                    max_batch_size: None,
+                    max_supply: None,
+                    token_id_policy: None,
+                    allow_remint: None,
+                    base_uri: None,
+                    enable_snapshots: None,
+                },
+            )?,
             funds: vec![],
             admin: None,
             label: String::from("Instantiate fixed price NFT contract"),
@@ -159,7 +169,11 @@ pub fn execute_receive(
         return Err(ContractError::WrongPaymentAmount {});
     }
 
-    let mint_msg = Cw721ExecuteMsg::<DefaultOptionMetadataExtension, Empty>::Mint {
+    let mint_msg = Cw721ExecuteMsg::<
+        DefaultOptionMetadataExtension,
+        Empty,
+        DefaultOptionCollectionInfoExtension,
+    >::Mint {
         token_id: config.unused_token_id.to_string(),
         owner: sender,
         token_uri: config.token_uri.clone().into(),
@@ -168,11 +182,11 @@ pub fn execute_receive(
 
     match config.cw721_address.clone() {
         Some(cw721) => {
-            let callback = Cw721Contract::<DefaultOptionMetadataExtension, Empty>(
-                cw721,
-                PhantomData,
-                PhantomData,
-            )
+            let callback = Cw721Contract::<
+                DefaultOptionMetadataExtension,
+                Empty,
+                DefaultOptionCollectionInfoExtension,
+            >(cw721, PhantomData, PhantomData, PhantomData)
             .call(mint_msg)?;
             config.unused_token_id += 1;
             CONFIG.save(deps.storage, &config)?;
@@ -228,11 +242,21 @@ mod tests {
             vec![SubMsg {
                 msg: WasmMsg::Instantiate {
                     code_id: msg.token_code_id,
-                    msg: to_json_binary(&Cw721InstantiateMsg {
+                    msg: to_json_binary(&Cw721InstantiateMsg::<
+                        DefaultOptionCollectionInfoExtension,
+                    > {
                         name: msg.name.clone(),
                         symbol: msg.symbol.clone(),
+                        collection_info_extension: None,
+                        creator: None,
                         minter: None,
                         withdraw_address: None,
+                        max_batch_size: None,
+                        max_supply: None,
+                        token_id_policy: None,
+                        allow_remint: None,
+                        base_uri: None,
+                        enable_snapshots: None,
                     })
                     .unwrap(),
                     funds: vec![],
@@ -381,7 +405,11 @@ mod tests {
         let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
         let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
 
-        let mint_msg = Cw721ExecuteMsg::<DefaultOptionMetadataExtension, Empty>::Mint {
+        let mint_msg = Cw721ExecuteMsg::<
+            DefaultOptionMetadataExtension,
+            Empty,
+            DefaultOptionCollectionInfoExtension,
+        >::Mint {
             token_id: String::from("0"),
             owner: String::from("minter"),
             token_uri: Some(String::from("https://ipfs.io/ipfs/Q")),
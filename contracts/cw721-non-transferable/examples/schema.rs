@@ -4,7 +4,7 @@ use std::fs::create_dir_all;
 use cosmwasm_schema::{export_schema_with_title, remove_schemas, schema_for};
 
 use cosmwasm_std::Empty;
-use cw721::state::DefaultOptionMetadataExtension;
+use cw721::state::{DefaultOptionCollectionInfoExtension, DefaultOptionMetadataExtension};
 use cw721_non_transferable::{
     msg::{ExecuteMsg, MigrateMsg},
     InstantiateMsg, QueryMsg,
@@ -19,7 +19,9 @@ fn main() {
     // entry points - generate always with title for avoiding name suffixes like "..._empty_for_..." due to generics
     export_schema_with_title(&schema_for!(InstantiateMsg), &out_dir, "InstantiateMsg");
     export_schema_with_title(
-        &schema_for!(ExecuteMsg::<DefaultOptionMetadataExtension, Empty>),
+        &schema_for!(
+            ExecuteMsg::<DefaultOptionMetadataExtension, Empty, DefaultOptionCollectionInfoExtension>
+        ),
         &out_dir,
         "ExecuteMsg",
     );
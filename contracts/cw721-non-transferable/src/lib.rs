@@ -1,8 +1,8 @@
 pub use crate::msg::{InstantiateMsg, QueryMsg};
 use cosmwasm_std::Empty;
-use cw721::state::DefaultOptionMetadataExtension;
+use cw721::state::{DefaultOptionCollectionInfoExtension, DefaultOptionMetadataExtension};
 pub use cw721_base::{
-    entry::{execute as _execute, query as _query},
+    entry::{execute as _execute, query as _query, reply as _reply},
     Cw721Contract,
 };
 
@@ -14,8 +14,13 @@ pub mod state;
 const CONTRACT_NAME: &str = "crates.io:cw721-non-transferable";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-pub type Cw721NonTransferableContract<'a> =
-    Cw721Contract<'a, DefaultOptionMetadataExtension, Empty, Empty>;
+pub type Cw721NonTransferableContract<'a> = Cw721Contract<
+    'a,
+    DefaultOptionMetadataExtension,
+    Empty,
+    Empty,
+    DefaultOptionCollectionInfoExtension,
+>;
 
 #[cfg(not(feature = "library"))]
 pub mod entry {
@@ -23,8 +28,8 @@ pub mod entry {
     use crate::query::admin;
     use crate::state::{Config, CONFIG};
     use cosmwasm_std::{
-        entry_point, to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response,
-        StdResult,
+        entry_point, to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Reply,
+        Response, StdResult,
     };
     use cw721::error::Cw721ContractError;
     use cw721::execute::Cw721Execute;
@@ -50,8 +55,16 @@ pub mod entry {
         let cw721_base_instantiate_msg = Cw721InstantiateMsg {
             name: msg.name,
             symbol: msg.symbol,
+            collection_info_extension: msg.collection_info_extension,
+            creator: msg.creator,
             minter: msg.minter,
             withdraw_address: msg.withdraw_address,
+            max_batch_size: msg.max_batch_size,
+            max_supply: msg.max_supply,
+            token_id_policy: msg.token_id_policy,
+            allow_remint: msg.allow_remint,
+            base_uri: msg.base_uri,
+            enable_snapshots: msg.enable_snapshots,
         };
 
         Cw721NonTransferableContract::default().instantiate(
@@ -75,7 +88,11 @@ pub mod entry {
         deps: DepsMut,
         env: Env,
         info: MessageInfo,
-        msg: Cw721ExecuteMsg<DefaultOptionMetadataExtension, Empty>,
+        msg: Cw721ExecuteMsg<
+            DefaultOptionMetadataExtension,
+            Empty,
+            DefaultOptionCollectionInfoExtension,
+        >,
     ) -> Result<Response, Cw721ContractError> {
         let config = CONFIG.load(deps.storage)?;
         match config.admin {
@@ -95,7 +112,7 @@ pub mod entry {
                     token_uri,
                     extension,
                 } => Cw721NonTransferableContract::default()
-                    .mint(deps, info, token_id, owner, token_uri, extension),
+                    .mint(deps, env, info, token_id, owner, token_uri, extension),
                 _ => Err(Cw721ContractError::Ownership(
                     cw721_base::OwnershipError::NotOwner,
                 )),
@@ -103,6 +120,11 @@ pub mod entry {
         }
     }
 
+    #[entry_point]
+    pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, Cw721ContractError> {
+        _reply(deps, env, msg)
+    }
+
     #[entry_point]
     pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         match msg {
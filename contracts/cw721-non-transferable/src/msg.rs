@@ -1,15 +1,35 @@
 use cosmwasm_schema::cw_serde;
 // expose to all others using contract, so others dont need to import cw721
 pub use cw721::msg::{Cw721ExecuteMsg as ExecuteMsg, Cw721MigrateMsg as MigrateMsg, *};
-use cw721::state::DefaultOptionMetadataExtension;
+use cw721::state::{
+    BaseUri, DefaultOptionCollectionInfoExtension, DefaultOptionMetadataExtension, TokenIdPolicy,
+};
 
 #[cw_serde]
 pub struct InstantiateMsg {
     pub admin: Option<String>,
     pub name: String,
     pub symbol: String,
+    pub collection_info_extension: DefaultOptionCollectionInfoExtension,
+    pub creator: Option<String>,
     pub minter: Option<String>,
     pub withdraw_address: Option<String>,
+    pub max_batch_size: Option<u32>,
+
+    /// Maximum number of tokens ever minted, including burned ones, unlimited if not set.
+    pub max_supply: Option<u64>,
+
+    /// Validation of token ids on mint, defaults to `TokenIdPolicy::default()`.
+    pub token_id_policy: Option<TokenIdPolicy>,
+
+    /// Allows minting ids of burned tokens again, defaults to false.
+    pub allow_remint: Option<bool>,
+
+    /// Used for deriving the token uri of tokens minted without one.
+    pub base_uri: Option<BaseUri>,
+
+    /// Records ownership changes for `OwnerOfAtHeight` and `BalanceOfAtHeight`, defaults to false.
+    pub enable_snapshots: Option<bool>,
 }
 
 #[cw_serde]
@@ -37,7 +57,28 @@ pub enum QueryMsg {
         limit: Option<u32>,
     },
     NumTokens {},
+    BalanceOf {
+        owner: String,
+    },
+    OwnerOfAtHeight {
+        token_id: String,
+        height: u64,
+    },
+    BalanceOfAtHeight {
+        owner: String,
+        height: u64,
+    },
+    Supply {},
+    TotalMinted {},
+    IsBurned {
+        token_id: String,
+    },
+    BurnedTokens {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     ContractInfo {},
+    GetCollectionInfo {},
 
     NftInfo {
         token_id: String,
@@ -46,22 +87,59 @@ pub enum QueryMsg {
         token_id: String,
         include_expired: Option<bool>,
     },
+    AllNftInfos {
+        start_after: Option<String>,
+        limit: Option<u32>,
+        include_expired: Option<bool>,
+    },
+    NftInfosByOwner {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        include_expired: Option<bool>,
+    },
     Tokens {
         owner: String,
         start_after: Option<String>,
+        end_before: Option<String>,
         limit: Option<u32>,
+        order: Option<OrderBy>,
     },
     AllTokens {
         start_after: Option<String>,
+        end_before: Option<String>,
         limit: Option<u32>,
+        order: Option<OrderBy>,
     },
     Minter {},
 
     GetWithdrawAddress {},
+    BaseUri {},
+    Placeholder {},
+    PermitNonce {
+        owner: String,
+    },
+    UserOf {
+        token_id: String,
+    },
+    TokensOfUser {
+        user: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    TokenHistory {
+        token_id: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
 }
 
-impl From<QueryMsg> for Cw721QueryMsg<DefaultOptionMetadataExtension> {
-    fn from(msg: QueryMsg) -> Cw721QueryMsg<DefaultOptionMetadataExtension> {
+impl From<QueryMsg>
+    for Cw721QueryMsg<DefaultOptionMetadataExtension, DefaultOptionCollectionInfoExtension>
+{
+    fn from(
+        msg: QueryMsg,
+    ) -> Cw721QueryMsg<DefaultOptionMetadataExtension, DefaultOptionCollectionInfoExtension> {
         match msg {
             QueryMsg::OwnerOf {
                 token_id,
@@ -71,7 +149,21 @@ impl From<QueryMsg> for Cw721QueryMsg<DefaultOptionMetadataExtension> {
                 include_expired,
             },
             QueryMsg::NumTokens {} => Cw721QueryMsg::NumTokens {},
+            QueryMsg::BalanceOf { owner } => Cw721QueryMsg::BalanceOf { owner },
+            QueryMsg::OwnerOfAtHeight { token_id, height } => {
+                Cw721QueryMsg::OwnerOfAtHeight { token_id, height }
+            }
+            QueryMsg::BalanceOfAtHeight { owner, height } => {
+                Cw721QueryMsg::BalanceOfAtHeight { owner, height }
+            }
+            QueryMsg::Supply {} => Cw721QueryMsg::Supply {},
+            QueryMsg::TotalMinted {} => Cw721QueryMsg::TotalMinted {},
+            QueryMsg::IsBurned { token_id } => Cw721QueryMsg::IsBurned { token_id },
+            QueryMsg::BurnedTokens { start_after, limit } => {
+                Cw721QueryMsg::BurnedTokens { start_after, limit }
+            }
             QueryMsg::ContractInfo {} => Cw721QueryMsg::ContractInfo {},
+            QueryMsg::GetCollectionInfo {} => Cw721QueryMsg::GetCollectionInfo {},
             QueryMsg::NftInfo { token_id } => Cw721QueryMsg::NftInfo { token_id },
             QueryMsg::AllNftInfo {
                 token_id,
@@ -80,20 +172,74 @@ impl From<QueryMsg> for Cw721QueryMsg<DefaultOptionMetadataExtension> {
                 token_id,
                 include_expired,
             },
+            QueryMsg::AllNftInfos {
+                start_after,
+                limit,
+                include_expired,
+            } => Cw721QueryMsg::AllNftInfos {
+                start_after,
+                limit,
+                include_expired,
+            },
+            QueryMsg::NftInfosByOwner {
+                owner,
+                start_after,
+                limit,
+                include_expired,
+            } => Cw721QueryMsg::NftInfosByOwner {
+                owner,
+                start_after,
+                limit,
+                include_expired,
+            },
             QueryMsg::Tokens {
                 owner,
                 start_after,
+                end_before,
                 limit,
+                order,
             } => Cw721QueryMsg::Tokens {
                 owner,
                 start_after,
+                end_before,
                 limit,
+                order,
+            },
+            QueryMsg::AllTokens {
+                start_after,
+                end_before,
+                limit,
+                order,
+            } => Cw721QueryMsg::AllTokens {
+                start_after,
+                end_before,
+                limit,
+                order,
             },
-            QueryMsg::AllTokens { start_after, limit } => {
-                Cw721QueryMsg::AllTokens { start_after, limit }
-            }
             QueryMsg::Minter {} => Cw721QueryMsg::Minter {},
             QueryMsg::GetWithdrawAddress {} => Cw721QueryMsg::GetWithdrawAddress {},
+            QueryMsg::BaseUri {} => Cw721QueryMsg::BaseUri {},
+            QueryMsg::Placeholder {} => Cw721QueryMsg::Placeholder {},
+            QueryMsg::PermitNonce { owner } => Cw721QueryMsg::PermitNonce { owner },
+            QueryMsg::UserOf { token_id } => Cw721QueryMsg::UserOf { token_id },
+            QueryMsg::TokensOfUser {
+                user,
+                start_after,
+                limit,
+            } => Cw721QueryMsg::TokensOfUser {
+                user,
+                start_after,
+                limit,
+            },
+            QueryMsg::TokenHistory {
+                token_id,
+                start_after,
+                limit,
+            } => Cw721QueryMsg::TokenHistory {
+                token_id,
+                start_after,
+                limit,
+            },
             QueryMsg::AllOperators { .. } => unreachable!("AllOperators is not supported!"),
             QueryMsg::Approval { .. } => unreachable!("Approval is not supported!"),
             QueryMsg::Approvals { .. } => unreachable!("Approvals is not supported!"),
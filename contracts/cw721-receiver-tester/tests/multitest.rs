@@ -1,4 +1,5 @@
 use cosmwasm_std::{to_json_binary, Addr, Attribute, Binary};
+use cw721::event::Cw721Event;
 use cw_multi_test::{App, ContractWrapper, Executor};
 
 #[test]
@@ -27,27 +28,21 @@ fn test_cw721_base_receive_succeed() {
             &[],
         )
         .unwrap();
-    let mut wasm_events = response.events.iter().filter(|e| e.ty == "wasm");
-
-    let send_event = wasm_events.next().unwrap();
-    assert_eq!(
-        get_attribute(&send_event.attributes, "action"),
-        Some("send_nft")
-    );
-    assert_eq!(
-        get_attribute(&send_event.attributes, "token_id"),
-        Some("test")
-    );
+    let send_events = Cw721Event::from_events(&response.events);
     assert_eq!(
-        get_attribute(&send_event.attributes, "recipient"),
-        Some(receiver_contract.as_str())
+        send_events,
+        vec![Cw721Event::Send {
+            sender: admin.to_string(),
+            contract: receiver_contract.to_string(),
+            token_id: "test".to_string(),
+        }]
     );
 
-    let receive_event = wasm_events.next().unwrap();
-    assert_eq!(
-        get_attribute(&receive_event.attributes, "action"),
-        Some("receive_nft")
-    );
+    let receive_event = response
+        .events
+        .iter()
+        .find(|e| e.ty == "wasm" && get_attribute(&e.attributes, "action") == Some("receive_nft"))
+        .unwrap();
     assert_eq!(
         get_attribute(&receive_event.attributes, "token_id"),
         Some("test")
@@ -109,6 +104,7 @@ fn setup_contracts(app: &mut App, admin: Addr) -> Contracts {
     use cw721_receiver_tester::msg::*;
 
     use cw721_base::msg as base_msg;
+    use cw721_base::state::DefaultOptionCollectionInfoExtension;
 
     let code_id = app.store_code(Box::new(ContractWrapper::new(execute, instantiate, query)));
     let nft_code_id = app.store_code(Box::new(ContractWrapper::new(
@@ -122,11 +118,19 @@ fn setup_contracts(app: &mut App, admin: Addr) -> Contracts {
         .instantiate_contract(
             nft_code_id,
             admin.clone(),
-            &base_msg::InstantiateMsg {
+            &base_msg::InstantiateMsg::<DefaultOptionCollectionInfoExtension> {
                 name: "nft".to_string(),
                 symbol: "NFT".to_string(),
+                collection_info_extension: None,
+                creator: None,
                 minter: Some(admin.to_string()),
                 withdraw_address: None,
+                max_batch_size: None,
+                max_supply: None,
+                token_id_policy: None,
+                allow_remint: None,
+                base_uri: None,
+                enable_snapshots: None,
             },
             &[],
             "nft".to_string(),
@@ -149,7 +153,7 @@ fn setup_contracts(app: &mut App, admin: Addr) -> Contracts {
     app.execute_contract(
         admin.clone(),
         nft_contract.clone(),
-        &base_msg::ExecuteMsg::<(), ()>::Mint {
+        &base_msg::ExecuteMsg::<(), (), ()>::Mint {
             token_id: "test".to_string(),
             owner: admin.to_string(),
             token_uri: Some("https://example.com".to_string()),
@@ -6,13 +6,17 @@ use cosmwasm_schema::{export_schema, export_schema_with_title, remove_schemas, s
 use cosmwasm_std::Empty;
 use cw721::{
     msg::{
-        AllNftInfoResponse, ApprovalResponse, ApprovalsResponse, CollectionInfoMsg,
-        Cw721ExecuteMsg, Cw721InstantiateMsg, Cw721MigrateMsg, Cw721QueryMsg, MinterResponse,
-        NftInfoResponse, NumTokensResponse, OperatorResponse, OperatorsResponse, OwnerOfResponse,
-        TokensResponse,
+        AllNftInfoResponse, ApprovalResponse, ApprovalsResponse, ApprovedTokensResponse,
+        BalanceOfResponse, CollectionInfoAndExtensionResponse, CollectionInfoMsg, Cw721ExecuteMsg,
+        Cw721InstantiateMsg, Cw721MigrateMsg, Cw721QueryMsg, HooksResponse, MinterResponse,
+        NftInfoResponse, NftInfosResponse, NftLockResponse, NftLocksResponse, NumTokensResponse,
+        OperatorGrantsResponse, OperatorResponse, OperatorsResponse, OwnerOfAtHeightResponse,
+        OwnerOfResponse, PauseStatusResponse, SupplyResponse, TokenHistoryResponse, TokensResponse,
+        TotalMintedResponse, UserOfResponse,
     },
+    permit::PermitData,
     receiver::Cw721ReceiveMsg,
-    state::{CollectionInfo, DefaultOptionMetadataExtension},
+    state::{CollectionInfo, DefaultOptionCollectionInfoExtension, DefaultOptionMetadataExtension},
 };
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -22,17 +26,23 @@ fn main() {
 
     // entry points - generate always with title for avoiding name suffixes like "..._empty_for_..." due to generics
     export_schema_with_title(
-        &schema_for!(Cw721InstantiateMsg),
+        &schema_for!(Cw721InstantiateMsg<DefaultOptionCollectionInfoExtension>),
         &out_dir,
         "Cw721InstantiateMsg",
     );
     export_schema_with_title(
-        &schema_for!(Cw721ExecuteMsg::<DefaultOptionMetadataExtension, Empty>),
+        &schema_for!(
+            Cw721ExecuteMsg::<
+                DefaultOptionMetadataExtension,
+                Empty,
+                DefaultOptionCollectionInfoExtension,
+            >
+        ),
         &out_dir,
         "Cw721ExecuteMsg",
     );
     export_schema_with_title(
-        &schema_for!(Cw721QueryMsg<Empty>),
+        &schema_for!(Cw721QueryMsg<Empty, DefaultOptionCollectionInfoExtension>),
         &out_dir,
         "Cw721QueryMsg",
     );
@@ -40,7 +50,11 @@ fn main() {
 
     // messages
     export_schema_with_title(&schema_for!(Cw721ReceiveMsg), &out_dir, "Cw721ReceiveMsg");
-    export_schema(&schema_for!(CollectionInfoMsg), &out_dir);
+    export_schema_with_title(
+        &schema_for!(CollectionInfoMsg<DefaultOptionCollectionInfoExtension>),
+        &out_dir,
+        "CollectionInfoMsg",
+    );
 
     // responses
     export_schema_with_title(
@@ -53,11 +67,34 @@ fn main() {
         &out_dir,
         "AllNftInfoResponse",
     );
+    export_schema_with_title(
+        &schema_for!(NftInfosResponse<DefaultOptionMetadataExtension>),
+        &out_dir,
+        "NftInfosResponse",
+    );
     export_schema(&schema_for!(ApprovalResponse), &out_dir);
     export_schema(&schema_for!(ApprovalsResponse), &out_dir);
     export_schema(&schema_for!(OperatorResponse), &out_dir);
     export_schema(&schema_for!(OperatorsResponse), &out_dir);
+    export_schema(&schema_for!(OperatorGrantsResponse), &out_dir);
+    export_schema(&schema_for!(ApprovedTokensResponse), &out_dir);
+    export_schema(&schema_for!(NftLockResponse), &out_dir);
+    export_schema(&schema_for!(NftLocksResponse), &out_dir);
+    export_schema(&schema_for!(UserOfResponse), &out_dir);
+    export_schema(&schema_for!(PermitData), &out_dir);
+    export_schema(&schema_for!(BalanceOfResponse), &out_dir);
+    export_schema(&schema_for!(OwnerOfAtHeightResponse), &out_dir);
+    export_schema(&schema_for!(SupplyResponse), &out_dir);
+    export_schema(&schema_for!(TotalMintedResponse), &out_dir);
+    export_schema(&schema_for!(TokenHistoryResponse), &out_dir);
+    export_schema(&schema_for!(PauseStatusResponse), &out_dir);
+    export_schema(&schema_for!(HooksResponse), &out_dir);
     export_schema_with_title(&schema_for!(CollectionInfo), &out_dir, "CollectionInfo");
+    export_schema_with_title(
+        &schema_for!(CollectionInfoAndExtensionResponse<DefaultOptionCollectionInfoExtension>),
+        &out_dir,
+        "CollectionInfoAndExtensionResponse",
+    );
     export_schema(&schema_for!(OwnerOfResponse), &out_dir);
     export_schema(&schema_for!(MinterResponse), &out_dir);
     export_schema(&schema_for!(NumTokensResponse), &out_dir);
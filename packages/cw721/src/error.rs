@@ -1,5 +1,6 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Decimal, StdError};
 use cw_ownable::OwnershipError;
+use cw_utils::Expiration;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -24,4 +25,91 @@ pub enum Cw721ContractError {
 
     #[error("No withdraw address set")]
     NoWithdrawAddress {},
+
+    #[error("Collection description must not be empty")]
+    CollectionDescriptionEmpty {},
+
+    #[error("Collection description exceeds max length of {max_length}")]
+    CollectionDescriptionTooLong { max_length: u32 },
+
+    #[error("Invalid url: {url}")]
+    InvalidUrl { url: String },
+
+    #[error("Royalty share must not exceed {max_share}")]
+    InvalidRoyaltyShare { max_share: Decimal },
+
+    #[error("NFT info is frozen for token_id: {token_id}")]
+    NftInfoFrozen { token_id: String },
+
+    #[error("Base uri is frozen")]
+    BaseUriFrozen {},
+
+    #[error("NFT info is frozen for the whole collection")]
+    CollectionNftInfoFrozen {},
+
+    #[error("Extension msg not supported, use UpdateNftInfo for updating metadata")]
+    UnsupportedExtensionMsg {},
+
+    #[error("Metadata is already revealed")]
+    AlreadyRevealed {},
+
+    #[error("Batch must not be empty")]
+    EmptyBatch {},
+
+    #[error("Batch size exceeds max batch size of {max_batch_size}")]
+    BatchSizeExceeded { max_batch_size: u32 },
+
+    #[error("{target} paused")]
+    Paused { target: String },
+
+    #[error("Token {token_id} is locked until {until}")]
+    NftLocked { token_id: String, until: Expiration },
+
+    #[error("Token {token_id} is not locked")]
+    NftNotLocked { token_id: String },
+
+    #[error("Token {token_id} can only be unlocked by {locker} until the lock expires")]
+    NotLocker { token_id: String, locker: String },
+
+    #[error("Invalid permit signature")]
+    InvalidPermitSignature {},
+
+    #[error("Invalid permit pubkey, expected a compressed secp256k1 key of 33 bytes, got {length} bytes")]
+    InvalidPermitPubkey { length: usize },
+
+    #[error("Invalid permit nonce {nonce}, expected {expected}")]
+    InvalidPermitNonce { expected: u64, nonce: u64 },
+
+    #[error("Hook not registered: {addr}")]
+    HookNotRegistered { addr: String },
+
+    #[error("Max hooks reached: {max}")]
+    MaxHooksReached { max: u32 },
+
+    #[error("Unknown reply id: {id}")]
+    UnknownReplyId { id: u64 },
+
+    #[error("Token {token_id} has been burned and can not be minted again")]
+    TokenBurned { token_id: String },
+
+    #[error("Max supply of {max_supply} tokens reached")]
+    MaxSupplyReached { max_supply: u64 },
+
+    #[error("Invalid token id: {reason}")]
+    InvalidTokenId { reason: String },
+
+    #[error("Unsupported url scheme: {url}")]
+    UnsupportedUrlScheme { url: String },
+
+    #[error("Invalid background color: {color}, must be six hex characters without #")]
+    InvalidBackgroundColor { color: String },
+
+    #[error("Duplicate trait type: {trait_type}")]
+    DuplicateTraitType { trait_type: String },
+
+    #[error("Number of traits exceeds max of {max_traits}")]
+    TooManyTraits { max_traits: u32 },
+
+    #[error("Metadata field {field} exceeds max length of {max_length}")]
+    MetadataFieldTooLong { field: String, max_length: u32 },
 }
@@ -0,0 +1,617 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Event, Response, StdError, StdResult};
+
+/// Prefix added by wasmd to the type of events emitted by contracts
+const WASM_EVENT_PREFIX: &str = "wasm-";
+
+/// Events emitted by cw721 contracts. Each variant is emitted as a dedicated `Event`,
+/// its type being the action (e.g. `transfer_nft`) and its fields being the attributes.
+/// Optional fields are omitted when not set.
+#[cw_serde]
+pub enum Cw721Event {
+    Mint {
+        minter: String,
+        owner: String,
+        token_id: String,
+    },
+    Transfer {
+        sender: String,
+        recipient: String,
+        token_id: String,
+    },
+    Send {
+        sender: String,
+        contract: String,
+        token_id: String,
+    },
+    Approve {
+        sender: String,
+        spender: String,
+        token_id: String,
+    },
+    Revoke {
+        sender: String,
+        spender: String,
+        token_id: String,
+    },
+    ApproveAll {
+        sender: String,
+        operator: String,
+    },
+    RevokeAll {
+        sender: String,
+        operator: String,
+    },
+    Burn {
+        sender: String,
+        token_id: String,
+    },
+    PruneExpired {
+        sender: String,
+        pruned_approvals: u64,
+        pruned_operators: u64,
+    },
+    LockNft {
+        sender: String,
+        token_id: String,
+        until: String,
+    },
+    UnlockNft {
+        sender: String,
+        token_id: String,
+    },
+    /// `user` is not set when the user has been removed
+    SetUser {
+        sender: String,
+        token_id: String,
+        user: Option<String>,
+        expires: String,
+    },
+    Pause {
+        sender: String,
+        target: String,
+        expires: String,
+    },
+    Unpause {
+        sender: String,
+        target: String,
+    },
+    /// Only changed fields are set, `extension` is JSON encoded
+    UpdateNftInfo {
+        sender: String,
+        token_id: String,
+        token_uri: Option<String>,
+        extension: Option<String>,
+    },
+    /// `token_id` is not set when the whole collection has been frozen
+    FreezeNftInfo {
+        sender: String,
+        token_id: Option<String>,
+    },
+    UpdateMinterOwnership {
+        sender: String,
+        owner: Option<String>,
+        pending_owner: Option<String>,
+        pending_expiry: Option<String>,
+    },
+    UpdateCreatorOwnership {
+        sender: String,
+        owner: Option<String>,
+        pending_owner: Option<String>,
+        pending_expiry: Option<String>,
+    },
+    UpdateCollectionInfo {
+        sender: String,
+    },
+    /// `base_uri` is not set when the base uri has been removed
+    UpdateBaseUri {
+        sender: String,
+        base_uri: Option<String>,
+    },
+    SetPlaceholder {
+        sender: String,
+        reveal_at: String,
+    },
+    /// `base_uri` is only set when the base uri has been updated on reveal
+    Reveal {
+        sender: String,
+        base_uri: Option<String>,
+    },
+    /// `max_entries` is not set when the cap has been removed
+    UpdateProvenanceCap {
+        sender: String,
+        max_entries: Option<u64>,
+    },
+    AddHook {
+        sender: String,
+        hook: String,
+        mode: String,
+    },
+    RemoveHook {
+        sender: String,
+        hook: String,
+    },
+    HookFailed {
+        error: String,
+    },
+    SetWithdrawAddress {
+        sender: String,
+        address: String,
+    },
+    RemoveWithdrawAddress {
+        sender: String,
+        address: String,
+    },
+    WithdrawFunds {
+        address: String,
+        amount: String,
+        denom: String,
+    },
+    /// Only migrated or updated fields are set
+    Migrate {
+        from_version: String,
+        to_version: String,
+        creator: Option<String>,
+        minter: Option<String>,
+        /// Legacy minter migrated to both roles, `none` in case there was no owner
+        creator_and_minter: Option<String>,
+        migrated_collection_name: Option<String>,
+        migrated_collection_symbol: Option<String>,
+        migrated_operator_grants: Option<u64>,
+        migrated_balances: Option<u64>,
+        migrated_approved_tokens: Option<u64>,
+        migrated_total_minted: Option<u64>,
+        /// First height snapshots are available at
+        enabled_snapshots: Option<u64>,
+    },
+}
+
+impl Cw721Event {
+    /// Type of the emitted event
+    pub fn event_type(&self) -> &str {
+        match self {
+            Cw721Event::Mint { .. } => "mint",
+            Cw721Event::Transfer { .. } => "transfer_nft",
+            Cw721Event::Send { .. } => "send_nft",
+            Cw721Event::Approve { .. } => "approve",
+            Cw721Event::Revoke { .. } => "revoke",
+            Cw721Event::ApproveAll { .. } => "approve_all",
+            Cw721Event::RevokeAll { .. } => "revoke_all",
+            Cw721Event::Burn { .. } => "burn",
+            Cw721Event::PruneExpired { .. } => "prune_expired",
+            Cw721Event::LockNft { .. } => "lock_nft",
+            Cw721Event::UnlockNft { .. } => "unlock_nft",
+            Cw721Event::SetUser { .. } => "set_user",
+            Cw721Event::Pause { .. } => "pause",
+            Cw721Event::Unpause { .. } => "unpause",
+            Cw721Event::UpdateNftInfo { .. } => "update_nft_info",
+            Cw721Event::FreezeNftInfo { .. } => "freeze_nft_info",
+            Cw721Event::UpdateMinterOwnership { .. } => "update_minter_ownership",
+            Cw721Event::UpdateCreatorOwnership { .. } => "update_creator_ownership",
+            Cw721Event::UpdateCollectionInfo { .. } => "update_collection_info",
+            Cw721Event::UpdateBaseUri { .. } => "update_base_uri",
+            Cw721Event::SetPlaceholder { .. } => "set_placeholder",
+            Cw721Event::Reveal { .. } => "reveal",
+            Cw721Event::UpdateProvenanceCap { .. } => "update_provenance_cap",
+            Cw721Event::AddHook { .. } => "add_hook",
+            Cw721Event::RemoveHook { .. } => "remove_hook",
+            Cw721Event::HookFailed { .. } => "hook_failed",
+            Cw721Event::SetWithdrawAddress { .. } => "set_withdraw_address",
+            Cw721Event::RemoveWithdrawAddress { .. } => "remove_withdraw_address",
+            Cw721Event::WithdrawFunds { .. } => "withdraw_funds",
+            Cw721Event::Migrate { .. } => "migrate",
+        }
+    }
+
+    /// Adds the event to the response. For consumers matching on the default `wasm` event
+    /// (e.g. `wasm.action=transfer_nft`), its type is also added as `action` attribute, once per
+    /// type in case of batches.
+    pub fn add_to_response<T>(self, response: Response<T>) -> Response<T> {
+        let has_action = response
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "action" && attr.value == self.event_type());
+        let response = if has_action {
+            response
+        } else {
+            response.add_attribute("action", self.event_type())
+        };
+        response.add_event(self.into())
+    }
+
+    /// Parses all cw721 events. Events of other types or not matching the cw721 schema, e.g. a
+    /// `mint` event of another contract in the same transaction, are skipped.
+    pub fn from_events(events: &[Event]) -> Vec<Self> {
+        events
+            .iter()
+            .filter_map(|event| Self::try_from(event).ok())
+            .collect()
+    }
+}
+
+impl From<Cw721Event> for Event {
+    fn from(event: Cw721Event) -> Self {
+        let ty = event.event_type().to_string();
+        let attributes = match event {
+            Cw721Event::Mint {
+                minter,
+                owner,
+                token_id,
+            } => vec![("minter", minter), ("owner", owner), ("token_id", token_id)],
+            Cw721Event::Transfer {
+                sender,
+                recipient,
+                token_id,
+            } => vec![
+                ("sender", sender),
+                ("recipient", recipient),
+                ("token_id", token_id),
+            ],
+            Cw721Event::Send {
+                sender,
+                contract,
+                token_id,
+            } => vec![
+                ("sender", sender),
+                ("contract", contract),
+                ("token_id", token_id),
+            ],
+            Cw721Event::Approve {
+                sender,
+                spender,
+                token_id,
+            }
+            | Cw721Event::Revoke {
+                sender,
+                spender,
+                token_id,
+            } => vec![
+                ("sender", sender),
+                ("spender", spender),
+                ("token_id", token_id),
+            ],
+            Cw721Event::ApproveAll { sender, operator }
+            | Cw721Event::RevokeAll { sender, operator } => {
+                vec![("sender", sender), ("operator", operator)]
+            }
+            Cw721Event::Burn { sender, token_id } => {
+                vec![("sender", sender), ("token_id", token_id)]
+            }
+            Cw721Event::PruneExpired {
+                sender,
+                pruned_approvals,
+                pruned_operators,
+            } => vec![
+                ("sender", sender),
+                ("pruned_approvals", pruned_approvals.to_string()),
+                ("pruned_operators", pruned_operators.to_string()),
+            ],
+            Cw721Event::LockNft {
+                sender,
+                token_id,
+                until,
+            } => vec![("sender", sender), ("token_id", token_id), ("until", until)],
+            Cw721Event::UnlockNft { sender, token_id } => {
+                vec![("sender", sender), ("token_id", token_id)]
+            }
+            Cw721Event::SetUser {
+                sender,
+                token_id,
+                user,
+                expires,
+            } => {
+                let mut attributes = vec![("sender", sender), ("token_id", token_id)];
+                attributes.extend(user.map(|user| ("user", user)));
+                attributes.push(("expires", expires));
+                attributes
+            }
+            Cw721Event::Pause {
+                sender,
+                target,
+                expires,
+            } => vec![("sender", sender), ("target", target), ("expires", expires)],
+            Cw721Event::Unpause { sender, target } => {
+                vec![("sender", sender), ("target", target)]
+            }
+            Cw721Event::UpdateNftInfo {
+                sender,
+                token_id,
+                token_uri,
+                extension,
+            } => {
+                let mut attributes = vec![("sender", sender), ("token_id", token_id)];
+                attributes.extend(token_uri.map(|token_uri| ("token_uri", token_uri)));
+                attributes.extend(extension.map(|extension| ("extension", extension)));
+                attributes
+            }
+            Cw721Event::FreezeNftInfo { sender, token_id } => {
+                let mut attributes = vec![("sender", sender)];
+                attributes.extend(token_id.map(|token_id| ("token_id", token_id)));
+                attributes
+            }
+            Cw721Event::UpdateMinterOwnership {
+                sender,
+                owner,
+                pending_owner,
+                pending_expiry,
+            }
+            | Cw721Event::UpdateCreatorOwnership {
+                sender,
+                owner,
+                pending_owner,
+                pending_expiry,
+            } => {
+                let mut attributes = vec![("sender", sender)];
+                attributes.extend(owner.map(|owner| ("owner", owner)));
+                attributes
+                    .extend(pending_owner.map(|pending_owner| ("pending_owner", pending_owner)));
+                attributes.extend(
+                    pending_expiry.map(|pending_expiry| ("pending_expiry", pending_expiry)),
+                );
+                attributes
+            }
+            Cw721Event::UpdateCollectionInfo { sender } => vec![("sender", sender)],
+            Cw721Event::UpdateBaseUri { sender, base_uri }
+            | Cw721Event::Reveal { sender, base_uri } => {
+                let mut attributes = vec![("sender", sender)];
+                attributes.extend(base_uri.map(|base_uri| ("base_uri", base_uri)));
+                attributes
+            }
+            Cw721Event::SetPlaceholder { sender, reveal_at } => {
+                vec![("sender", sender), ("reveal_at", reveal_at)]
+            }
+            Cw721Event::UpdateProvenanceCap {
+                sender,
+                max_entries,
+            } => {
+                let mut attributes = vec![("sender", sender)];
+                attributes.extend(
+                    max_entries.map(|max_entries| ("max_entries", max_entries.to_string())),
+                );
+                attributes
+            }
+            Cw721Event::AddHook { sender, hook, mode } => {
+                vec![("sender", sender), ("hook", hook), ("mode", mode)]
+            }
+            Cw721Event::RemoveHook { sender, hook } => vec![("sender", sender), ("hook", hook)],
+            Cw721Event::HookFailed { error } => vec![("error", error)],
+            Cw721Event::SetWithdrawAddress { sender, address }
+            | Cw721Event::RemoveWithdrawAddress { sender, address } => {
+                vec![("sender", sender), ("address", address)]
+            }
+            Cw721Event::WithdrawFunds {
+                address,
+                amount,
+                denom,
+            } => vec![("address", address), ("amount", amount), ("denom", denom)],
+            Cw721Event::Migrate {
+                from_version,
+                to_version,
+                creator,
+                minter,
+                creator_and_minter,
+                migrated_collection_name,
+                migrated_collection_symbol,
+                migrated_operator_grants,
+                migrated_balances,
+                migrated_approved_tokens,
+                migrated_total_minted,
+                enabled_snapshots,
+            } => {
+                let mut attributes =
+                    vec![("from_version", from_version), ("to_version", to_version)];
+                attributes.extend(creator.map(|creator| ("creator", creator)));
+                attributes.extend(minter.map(|minter| ("minter", minter)));
+                attributes.extend(creator_and_minter.map(|value| ("creator_and_minter", value)));
+                attributes.extend(
+                    migrated_collection_name.map(|value| ("migrated_collection_name", value)),
+                );
+                attributes.extend(
+                    migrated_collection_symbol.map(|value| ("migrated_collection_symbol", value)),
+                );
+                for (key, count) in [
+                    ("migrated_operator_grants", migrated_operator_grants),
+                    ("migrated_balances", migrated_balances),
+                    ("migrated_approved_tokens", migrated_approved_tokens),
+                    ("migrated_total_minted", migrated_total_minted),
+                    ("enabled_snapshots", enabled_snapshots),
+                ] {
+                    attributes.extend(count.map(|count| (key, count.to_string())));
+                }
+                attributes
+            }
+        };
+        Event::new(ty).add_attributes(attributes)
+    }
+}
+
+impl TryFrom<&Event> for Cw721Event {
+    type Error = StdError;
+
+    /// Parses an event emitted by a cw721 contract, with or without the `wasm-` prefix.
+    /// Attributes added by the chain (e.g. `_contract_address`) are ignored.
+    fn try_from(event: &Event) -> StdResult<Self> {
+        let optional_attr = |key: &str| -> Option<String> {
+            event
+                .attributes
+                .iter()
+                .find(|attr| attr.key == key)
+                .map(|attr| attr.value.clone())
+        };
+        let attr = |key: &str| -> StdResult<String> {
+            optional_attr(key).ok_or_else(|| {
+                StdError::generic_err(format!("Missing attribute {key} in event {}", event.ty))
+            })
+        };
+        let optional_count_attr = |key: &str| -> StdResult<Option<u64>> {
+            optional_attr(key)
+                .map(|value| {
+                    value.parse().map_err(|_| {
+                        StdError::generic_err(format!(
+                            "Invalid attribute {key} in event {}",
+                            event.ty
+                        ))
+                    })
+                })
+                .transpose()
+        };
+        let count_attr = |key: &str| -> StdResult<u64> {
+            optional_count_attr(key)?.ok_or_else(|| {
+                StdError::generic_err(format!("Missing attribute {key} in event {}", event.ty))
+            })
+        };
+        let ty = event
+            .ty
+            .strip_prefix(WASM_EVENT_PREFIX)
+            .unwrap_or(&event.ty);
+        match ty {
+            "mint" => Ok(Cw721Event::Mint {
+                minter: attr("minter")?,
+                owner: attr("owner")?,
+                token_id: attr("token_id")?,
+            }),
+            "transfer_nft" => Ok(Cw721Event::Transfer {
+                sender: attr("sender")?,
+                recipient: attr("recipient")?,
+                token_id: attr("token_id")?,
+            }),
+            "send_nft" => Ok(Cw721Event::Send {
+                sender: attr("sender")?,
+                contract: attr("contract")?,
+                token_id: attr("token_id")?,
+            }),
+            "approve" => Ok(Cw721Event::Approve {
+                sender: attr("sender")?,
+                spender: attr("spender")?,
+                token_id: attr("token_id")?,
+            }),
+            "revoke" => Ok(Cw721Event::Revoke {
+                sender: attr("sender")?,
+                spender: attr("spender")?,
+                token_id: attr("token_id")?,
+            }),
+            "approve_all" => Ok(Cw721Event::ApproveAll {
+                sender: attr("sender")?,
+                operator: attr("operator")?,
+            }),
+            "revoke_all" => Ok(Cw721Event::RevokeAll {
+                sender: attr("sender")?,
+                operator: attr("operator")?,
+            }),
+            "burn" => Ok(Cw721Event::Burn {
+                sender: attr("sender")?,
+                token_id: attr("token_id")?,
+            }),
+            "prune_expired" => Ok(Cw721Event::PruneExpired {
+                sender: attr("sender")?,
+                pruned_approvals: count_attr("pruned_approvals")?,
+                pruned_operators: count_attr("pruned_operators")?,
+            }),
+            "lock_nft" => Ok(Cw721Event::LockNft {
+                sender: attr("sender")?,
+                token_id: attr("token_id")?,
+                until: attr("until")?,
+            }),
+            "unlock_nft" => Ok(Cw721Event::UnlockNft {
+                sender: attr("sender")?,
+                token_id: attr("token_id")?,
+            }),
+            "set_user" => Ok(Cw721Event::SetUser {
+                sender: attr("sender")?,
+                token_id: attr("token_id")?,
+                user: optional_attr("user"),
+                expires: attr("expires")?,
+            }),
+            "pause" => Ok(Cw721Event::Pause {
+                sender: attr("sender")?,
+                target: attr("target")?,
+                expires: attr("expires")?,
+            }),
+            "unpause" => Ok(Cw721Event::Unpause {
+                sender: attr("sender")?,
+                target: attr("target")?,
+            }),
+            "update_nft_info" => Ok(Cw721Event::UpdateNftInfo {
+                sender: attr("sender")?,
+                token_id: attr("token_id")?,
+                token_uri: optional_attr("token_uri"),
+                extension: optional_attr("extension"),
+            }),
+            "freeze_nft_info" => Ok(Cw721Event::FreezeNftInfo {
+                sender: attr("sender")?,
+                token_id: optional_attr("token_id"),
+            }),
+            "update_minter_ownership" => Ok(Cw721Event::UpdateMinterOwnership {
+                sender: attr("sender")?,
+                owner: optional_attr("owner"),
+                pending_owner: optional_attr("pending_owner"),
+                pending_expiry: optional_attr("pending_expiry"),
+            }),
+            "update_creator_ownership" => Ok(Cw721Event::UpdateCreatorOwnership {
+                sender: attr("sender")?,
+                owner: optional_attr("owner"),
+                pending_owner: optional_attr("pending_owner"),
+                pending_expiry: optional_attr("pending_expiry"),
+            }),
+            "update_collection_info" => Ok(Cw721Event::UpdateCollectionInfo {
+                sender: attr("sender")?,
+            }),
+            "update_base_uri" => Ok(Cw721Event::UpdateBaseUri {
+                sender: attr("sender")?,
+                base_uri: optional_attr("base_uri"),
+            }),
+            "set_placeholder" => Ok(Cw721Event::SetPlaceholder {
+                sender: attr("sender")?,
+                reveal_at: attr("reveal_at")?,
+            }),
+            "reveal" => Ok(Cw721Event::Reveal {
+                sender: attr("sender")?,
+                base_uri: optional_attr("base_uri"),
+            }),
+            "update_provenance_cap" => Ok(Cw721Event::UpdateProvenanceCap {
+                sender: attr("sender")?,
+                max_entries: optional_count_attr("max_entries")?,
+            }),
+            "add_hook" => Ok(Cw721Event::AddHook {
+                sender: attr("sender")?,
+                hook: attr("hook")?,
+                mode: attr("mode")?,
+            }),
+            "remove_hook" => Ok(Cw721Event::RemoveHook {
+                sender: attr("sender")?,
+                hook: attr("hook")?,
+            }),
+            "hook_failed" => Ok(Cw721Event::HookFailed {
+                error: attr("error")?,
+            }),
+            "set_withdraw_address" => Ok(Cw721Event::SetWithdrawAddress {
+                sender: attr("sender")?,
+                address: attr("address")?,
+            }),
+            "remove_withdraw_address" => Ok(Cw721Event::RemoveWithdrawAddress {
+                sender: attr("sender")?,
+                address: attr("address")?,
+            }),
+            "withdraw_funds" => Ok(Cw721Event::WithdrawFunds {
+                address: attr("address")?,
+                amount: attr("amount")?,
+                denom: attr("denom")?,
+            }),
+            "migrate" => Ok(Cw721Event::Migrate {
+                from_version: attr("from_version")?,
+                to_version: attr("to_version")?,
+                creator: optional_attr("creator"),
+                minter: optional_attr("minter"),
+                creator_and_minter: optional_attr("creator_and_minter"),
+                migrated_collection_name: optional_attr("migrated_collection_name"),
+                migrated_collection_symbol: optional_attr("migrated_collection_symbol"),
+                migrated_operator_grants: optional_count_attr("migrated_operator_grants")?,
+                migrated_balances: optional_count_attr("migrated_balances")?,
+                migrated_approved_tokens: optional_count_attr("migrated_approved_tokens")?,
+                migrated_total_minted: optional_count_attr("migrated_total_minted")?,
+                enabled_snapshots: optional_count_attr("enabled_snapshots")?,
+            }),
+            ty => Err(StdError::generic_err(format!("Unknown cw721 event: {ty}"))),
+        }
+    }
+}
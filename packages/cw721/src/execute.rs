@@ -1,8 +1,8 @@
 use cosmwasm_std::{
-    Addr, Api, BankMsg, Binary, Coin, CustomMsg, Deps, DepsMut, Empty, Env, MessageInfo, Response,
-    StdResult, Storage,
+    to_json_string, Addr, Api, BankMsg, Binary, BlockInfo, Coin, CustomMsg, Deps, DepsMut, Empty,
+    Env, MessageInfo, Order, Reply, Response, StdResult, Storage, SubMsgResult,
 };
-use cw_ownable::{none_or, Action, Ownership, OwnershipError, OwnershipStore};
+use cw_ownable::{none_or, Action, Ownership, OwnershipError};
 use cw_storage_plus::Item;
 use cw_utils::Expiration;
 use serde::de::DeserializeOwned;
@@ -10,9 +10,17 @@ use serde::Serialize;
 
 use crate::{
     error::Cw721ContractError,
-    msg::{Cw721ExecuteMsg, Cw721InstantiateMsg, Cw721MigrateMsg},
+    event::Cw721Event,
+    hook::{Cw721HookMsg, HOOK_REPLY_ID, MAX_HOOKS},
+    msg::{CollectionInfoMsg, Cw721ExecuteMsg, Cw721InstantiateMsg, Cw721MigrateMsg, MintMsg},
+    permit::{verify_permit, PermitData},
     receiver::Cw721ReceiveMsg,
-    state::{CollectionInfo, Cw721Config, DefaultOptionMetadataExtension, NftInfo, MINTER},
+    state::{
+        validate_metadata_url, BaseUri, CollectionInfo, Cw721Config, Cw721Storage,
+        Cw721StorageKeys, DefaultOptionMetadataExtension, HookMode, NftInfo, NftLock, NftUser,
+        PauseTarget, Placeholder, ProvenanceAction, ValidateCollectionInfo, ValidateMetadata,
+        BACKFILL_APPROVED_TOKENS, BACKFILL_BALANCES, BACKFILL_OPERATOR_GRANTS,
+    },
     Approval,
 };
 
@@ -23,22 +31,38 @@ pub trait Cw721Execute<
     TCustomResponseMessage,
     // Message passed for updating metadata.
     TMetadataExtensionMsg,
+    // Extension defined in CollectionInfo.
+    TCollectionInfoExtension,
 > where
-    TMetadataExtension: Serialize + DeserializeOwned + Clone,
+    Self: Cw721Storage,
+    TMetadataExtension: Serialize + DeserializeOwned + Clone + ValidateMetadata,
     TCustomResponseMessage: CustomMsg,
     TMetadataExtensionMsg: CustomMsg,
+    TCollectionInfoExtension: Serialize + DeserializeOwned + Clone + ValidateCollectionInfo,
 {
+    /// Config using the stores of `storage_keys`.
+    fn config(
+        &self,
+    ) -> Cw721Config<
+        TMetadataExtension,
+        TCustomResponseMessage,
+        TMetadataExtensionMsg,
+        TCollectionInfoExtension,
+    > {
+        Cw721Config::new(self.storage_keys())
+    }
+
     fn instantiate(
         &self,
         deps: DepsMut,
-        _env: Env,
+        env: Env,
         info: MessageInfo,
-        msg: Cw721InstantiateMsg,
+        msg: Cw721InstantiateMsg<TCollectionInfoExtension>,
         contract_name: &str,
         contract_version: &str,
     ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
         cw2::set_contract_version(deps.storage, contract_name, contract_version)?;
-        let config = Cw721Config::<Empty, Empty, Empty>::default();
+        let config = self.config();
         let collection_info = CollectionInfo {
             name: msg.name,
             symbol: msg.symbol,
@@ -46,6 +70,48 @@ pub trait Cw721Execute<
         config
             .collection_info
             .save(deps.storage, &collection_info)?;
+        msg.collection_info_extension
+            .validate_collection_info(deps.api)?;
+        config
+            .collection_info_extension
+            .save(deps.storage, &msg.collection_info_extension)?;
+        if let Some(max_batch_size) = msg.max_batch_size {
+            config.max_batch_size.save(deps.storage, &max_batch_size)?;
+        }
+        if let Some(max_supply) = msg.max_supply {
+            config.max_supply.save(deps.storage, &max_supply)?;
+        }
+        if let Some(token_id_policy) = msg.token_id_policy {
+            config
+                .token_id_policy
+                .save(deps.storage, &token_id_policy)?;
+        }
+        if let Some(allow_remint) = msg.allow_remint {
+            config.allow_remint.save(deps.storage, &allow_remint)?;
+        }
+        if let Some(base_uri) = msg.base_uri {
+            base_uri.validate()?;
+            config.base_uri.save(deps.storage, &base_uri)?;
+        }
+        if msg.enable_snapshots.unwrap_or_default() {
+            config
+                .snapshot_height
+                .save(deps.storage, &env.block.height)?;
+        }
+        // stores are maintained from the start, so there is nothing to backfill on migration
+        for backfill in [
+            BACKFILL_OPERATOR_GRANTS,
+            BACKFILL_BALANCES,
+            BACKFILL_APPROVED_TOKENS,
+        ] {
+            config.backfills.save(deps.storage, backfill, &true)?;
+        }
+
+        let creator = match msg.creator {
+            Some(creator) => deps.api.addr_validate(&creator)?,
+            None => info.sender.clone(),
+        };
+        self.initialize_creator(deps.storage, deps.api, Some(creator.as_ref()))?;
 
         let minter = match msg.minter {
             Some(owner) => deps.api.addr_validate(&owner)?,
@@ -54,10 +120,12 @@ pub trait Cw721Execute<
         self.initialize_minter(deps.storage, deps.api, Some(minter.as_ref()))?;
 
         if let Some(withdraw_address) = msg.withdraw_address {
-            self.set_withdraw_address(deps, &minter, withdraw_address)?;
+            self.set_withdraw_address(deps, &creator, withdraw_address)?;
         }
 
-        Ok(Response::default().add_attribute("minter", minter))
+        Ok(Response::default()
+            .add_attribute("creator", creator)
+            .add_attribute("minter", minter))
     }
 
     fn execute(
@@ -65,7 +133,7 @@ pub trait Cw721Execute<
         deps: DepsMut,
         env: Env,
         info: MessageInfo,
-        msg: Cw721ExecuteMsg<TMetadataExtension, TMetadataExtensionMsg>,
+        msg: Cw721ExecuteMsg<TMetadataExtension, TMetadataExtensionMsg, TCollectionInfoExtension>,
     ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
         match msg {
             Cw721ExecuteMsg::Mint {
@@ -73,7 +141,12 @@ pub trait Cw721Execute<
                 owner,
                 token_uri,
                 extension,
-            } => self.mint(deps, info, token_id, owner, token_uri, extension),
+            } => self.mint(deps, env, info, token_id, owner, token_uri, extension),
+            Cw721ExecuteMsg::MintNext {
+                owner,
+                token_uri,
+                extension,
+            } => self.mint_next(deps, env, info, owner, token_uri, extension),
             Cw721ExecuteMsg::Approve {
                 spender,
                 token_id,
@@ -96,9 +169,94 @@ pub trait Cw721Execute<
                 msg,
             } => self.send_nft(deps, env, info, contract, token_id, msg),
             Cw721ExecuteMsg::Burn { token_id } => self.burn_nft(deps, env, info, token_id),
-            Cw721ExecuteMsg::UpdateOwnership(action) => {
+            Cw721ExecuteMsg::BatchMint { mints } => self.batch_mint(deps, env, info, mints),
+            Cw721ExecuteMsg::BatchTransferNft {
+                recipient,
+                token_ids,
+            } => self.batch_transfer_nft(deps, env, info, recipient, token_ids),
+            Cw721ExecuteMsg::BatchApprove {
+                spender,
+                token_ids,
+                expires,
+            } => self.batch_approve(deps, env, info, spender, token_ids, expires),
+            Cw721ExecuteMsg::BatchRevoke { spender, token_ids } => {
+                self.batch_revoke(deps, env, info, spender, token_ids)
+            }
+            Cw721ExecuteMsg::BatchBurn { token_ids } => self.batch_burn(deps, env, info, token_ids),
+            Cw721ExecuteMsg::PruneExpired {
+                token_ids,
+                owners,
+                limit,
+            } => self.prune_expired(deps, env, info, token_ids, owners, limit),
+            Cw721ExecuteMsg::UpdateNftInfo {
+                token_id,
+                token_uri,
+                extension,
+            } => self.update_nft_info(deps, env, info, token_id, token_uri, extension),
+            Cw721ExecuteMsg::FreezeNftInfo { token_id } => {
+                self.freeze_nft_info(deps, env, info, token_id)
+            }
+            Cw721ExecuteMsg::UpdateBaseUri { base_uri } => {
+                self.update_base_uri(deps, env, info, base_uri)
+            }
+            Cw721ExecuteMsg::SetPlaceholder {
+                token_uri,
+                extension,
+                reveal_at,
+            } => self.set_placeholder(deps, env, info, token_uri, extension, reveal_at),
+            Cw721ExecuteMsg::Reveal { base_uri } => self.reveal(deps, env, info, base_uri),
+            Cw721ExecuteMsg::UpdateProvenanceCap { max_entries } => {
+                self.update_provenance_cap(deps, env, info, max_entries)
+            }
+            Cw721ExecuteMsg::Pause { target, expires } => self.pause(
+                deps,
+                env,
+                info,
+                target.unwrap_or(PauseTarget::Transfers),
+                expires,
+            ),
+            Cw721ExecuteMsg::Unpause { target } => {
+                self.unpause(deps, env, info, target.unwrap_or(PauseTarget::Transfers))
+            }
+            Cw721ExecuteMsg::LockNft { token_id, until } => {
+                self.lock_nft(deps, env, info, token_id, until)
+            }
+            Cw721ExecuteMsg::UnlockNft { token_id } => self.unlock_nft(deps, env, info, token_id),
+            Cw721ExecuteMsg::SetUser {
+                token_id,
+                user,
+                expires,
+            } => self.set_user(deps, env, info, token_id, user, expires),
+            Cw721ExecuteMsg::Permit {
+                owner_pubkey,
+                token_id,
+                spender,
+                expires,
+                nonce,
+                signature,
+            } => self.permit(
+                deps,
+                env,
+                info,
+                owner_pubkey,
+                token_id,
+                spender,
+                expires,
+                nonce,
+                signature,
+            ),
+            Cw721ExecuteMsg::AddHook { addr, mode } => self.add_hook(deps, env, info, addr, mode),
+            Cw721ExecuteMsg::RemoveHook { addr } => self.remove_hook(deps, env, info, addr),
+            Cw721ExecuteMsg::UpdateOwnership(action)
+            | Cw721ExecuteMsg::UpdateMinterOwnership(action) => {
                 self.update_minter_ownership(deps, env, info, action)
             }
+            Cw721ExecuteMsg::UpdateCreatorOwnership(action) => {
+                self.update_creator_ownership(deps, env, info, action)
+            }
+            Cw721ExecuteMsg::UpdateCollectionInfo { collection_info } => {
+                self.update_collection_info(deps, info, collection_info)
+            }
             Cw721ExecuteMsg::Extension { msg } => {
                 self.update_metadata_extension(deps, env, info, msg)
             }
@@ -120,39 +278,84 @@ pub trait Cw721Execute<
         contract_name: &str,
         contract_version: &str,
     ) -> Result<Response, Cw721ContractError> {
-        let response = Response::<Empty>::default();
+        let keys = self.storage_keys();
         // first migrate legacy data ...
-        let response =
-            migrate_legacy_minter_and_creator(deps.storage, deps.api, &env, &msg, response)?;
-        let response = migrate_legacy_collection_info(deps.storage, &env, &msg, response)?;
+        let creator_and_minter =
+            migrate_legacy_minter_and_creator(deps.storage, deps.api, &env, &msg, keys)?;
+        let legacy_collection_info =
+            migrate_legacy_collection_info(deps.storage, &env, &msg, keys)?;
         // ... then migrate
-        let response = migrate_version(deps.storage, contract_name, contract_version, response)?;
+        let from_version = migrate_version(deps.storage, contract_name, contract_version)?;
         // ... and update creator and minter AFTER legacy migration
-        let response = migrate_minter(deps.storage, deps.api, &env, &msg, response)?;
-        Ok(response)
+        let creator = migrate_creator(deps.storage, deps.api, &env, &msg, keys)?;
+        let minter = migrate_minter(deps.storage, deps.api, &env, &msg, keys)?;
+        let migrated_operator_grants = migrate_operator_grants(deps.storage, &env, &msg, keys)?;
+        let migrated_balances =
+            migrate_balances::<TMetadataExtension>(deps.storage, &env, &msg, keys)?;
+        let migrated_approved_tokens =
+            migrate_approved_tokens::<TMetadataExtension>(deps.storage, &env, &msg, keys)?;
+        let migrated_total_minted = migrate_total_minted(deps.storage, &env, &msg, keys)?;
+        // snapshots rely on backfilled balances
+        let enabled_snapshots =
+            migrate_snapshots::<TMetadataExtension>(deps.storage, &env, &msg, keys)?;
+        let (migrated_collection_name, migrated_collection_symbol) = match legacy_collection_info {
+            Some(collection_info) => (Some(collection_info.name), Some(collection_info.symbol)),
+            None => (None, None),
+        };
+        Ok(Cw721Event::Migrate {
+            from_version,
+            to_version: contract_version.to_string(),
+            creator,
+            minter,
+            creator_and_minter,
+            migrated_collection_name,
+            migrated_collection_symbol,
+            migrated_operator_grants,
+            migrated_balances,
+            migrated_approved_tokens,
+            migrated_total_minted,
+            enabled_snapshots,
+        }
+        .add_to_response(Response::default()))
     }
 
     // ------- ERC721-based functions -------
     fn transfer_nft(
         &self,
-        deps: DepsMut,
+        mut deps: DepsMut,
         env: Env,
         info: MessageInfo,
         recipient: String,
         token_id: String,
     ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
-        _transfer_nft::<TMetadataExtension>(deps, &env, &info, &recipient, &token_id)?;
+        let previous_owner = _transfer_nft::<TMetadataExtension>(
+            deps.branch(),
+            &env,
+            &info,
+            &recipient,
+            &token_id,
+            ProvenanceAction::Transfer,
+            self.storage_keys(),
+        )?;
+        let hook_msg = Cw721HookMsg::Transfer {
+            sender: info.sender.to_string(),
+            previous_owner: previous_owner.to_string(),
+            recipient: recipient.clone(),
+            token_id: token_id.clone(),
+        };
+        let hooks = self.config().hook_sub_msgs(deps.storage, &hook_msg)?;
 
-        Ok(Response::new()
-            .add_attribute("action", "transfer_nft")
-            .add_attribute("sender", info.sender)
-            .add_attribute("recipient", recipient)
-            .add_attribute("token_id", token_id))
+        Ok(Cw721Event::Transfer {
+            sender: info.sender.to_string(),
+            recipient,
+            token_id,
+        }
+        .add_to_response(Response::new().add_submessages(hooks)))
     }
 
     fn send_nft(
         &self,
-        deps: DepsMut,
+        mut deps: DepsMut,
         env: Env,
         info: MessageInfo,
         contract: String,
@@ -160,21 +363,39 @@ pub trait Cw721Execute<
         msg: Binary,
     ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
         // Transfer token
-        _transfer_nft::<TMetadataExtension>(deps, &env, &info, &contract, &token_id)?;
+        let previous_owner = _transfer_nft::<TMetadataExtension>(
+            deps.branch(),
+            &env,
+            &info,
+            &contract,
+            &token_id,
+            ProvenanceAction::Send,
+            self.storage_keys(),
+        )?;
 
         let send = Cw721ReceiveMsg {
             sender: info.sender.to_string(),
             token_id: token_id.clone(),
             msg,
         };
+        let hook_msg = Cw721HookMsg::Send {
+            sender: info.sender.to_string(),
+            previous_owner: previous_owner.to_string(),
+            contract: contract.clone(),
+            token_id: token_id.clone(),
+        };
+        let hooks = self.config().hook_sub_msgs(deps.storage, &hook_msg)?;
 
         // Send message
-        Ok(Response::new()
+        let response = Response::new()
             .add_message(send.into_cosmos_msg(contract.clone())?)
-            .add_attribute("action", "send_nft")
-            .add_attribute("sender", info.sender)
-            .add_attribute("recipient", contract)
-            .add_attribute("token_id", token_id))
+            .add_submessages(hooks);
+        Ok(Cw721Event::Send {
+            sender: info.sender.to_string(),
+            contract,
+            token_id,
+        }
+        .add_to_response(response))
     }
 
     fn approve(
@@ -187,14 +408,22 @@ pub trait Cw721Execute<
         expires: Option<Expiration>,
     ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
         _update_approvals::<TMetadataExtension>(
-            deps, &env, &info, &spender, &token_id, true, expires,
+            deps,
+            &env,
+            &info,
+            &spender,
+            &token_id,
+            true,
+            expires,
+            self.storage_keys(),
         )?;
 
-        Ok(Response::new()
-            .add_attribute("action", "approve")
-            .add_attribute("sender", info.sender)
-            .add_attribute("spender", spender)
-            .add_attribute("token_id", token_id))
+        Ok(Cw721Event::Approve {
+            sender: info.sender.to_string(),
+            spender,
+            token_id,
+        }
+        .add_to_response(Response::new()))
     }
 
     fn revoke(
@@ -206,14 +435,65 @@ pub trait Cw721Execute<
         token_id: String,
     ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
         _update_approvals::<TMetadataExtension>(
-            deps, &env, &info, &spender, &token_id, false, None,
+            deps,
+            &env,
+            &info,
+            &spender,
+            &token_id,
+            false,
+            None,
+            self.storage_keys(),
         )?;
 
-        Ok(Response::new()
-            .add_attribute("action", "revoke")
-            .add_attribute("sender", info.sender)
-            .add_attribute("spender", spender)
-            .add_attribute("token_id", token_id))
+        Ok(Cw721Event::Revoke {
+            sender: info.sender.to_string(),
+            spender,
+            token_id,
+        }
+        .add_to_response(Response::new()))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn permit(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        _info: MessageInfo,
+        owner_pubkey: Binary,
+        token_id: Option<String>,
+        spender: String,
+        expires: Option<Expiration>,
+        nonce: u64,
+        signature: Binary,
+    ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
+        let data = PermitData {
+            chain_id: env.block.chain_id.clone(),
+            contract: env.contract.address.to_string(),
+            token_id: token_id.clone(),
+            spender: spender.clone(),
+            expires,
+            nonce,
+        };
+        let owner = verify_permit(deps.api, &owner_pubkey, &signature, &data)?;
+        let config = self.config();
+        let expected = config.permit_nonce(deps.storage, &owner)?;
+        if nonce != expected {
+            return Err(Cw721ContractError::InvalidPermitNonce { expected, nonce });
+        }
+        config
+            .permit_nonces
+            .save(deps.storage, &owner, &(nonce + 1))?;
+
+        // same as if the owner approved the spender
+        let owner_info = MessageInfo {
+            sender: owner,
+            funds: vec![],
+        };
+        let response = match token_id {
+            Some(token_id) => self.approve(deps, env, owner_info, spender, token_id, expires)?,
+            None => self.approve_all(deps, env, owner_info, spender, expires)?,
+        };
+        Ok(response.add_attribute("permit_nonce", nonce.to_string()))
     }
 
     fn approve_all(
@@ -232,21 +512,22 @@ pub trait Cw721Execute<
 
         // set the operator for us
         let operator_addr = deps.api.addr_validate(&operator)?;
-        let config = Cw721Config::<
-            TMetadataExtension,
-            TCustomResponseMessage,
-            TMetadataExtensionMsg,
-        >::default();
+        let config = self.config();
+        config.assert_not_paused(deps.storage, &env.block, &PauseTarget::Transfers)?;
         config
             .operators
             // stores info.sender as key (=granter, NFT owner) and operator as value (operator only(!) has control over NFTs of granter)
             // check is done in `check_can_send()`
             .save(deps.storage, (&info.sender, &operator_addr), &expires)?;
+        config
+            .operator_grants
+            .save(deps.storage, (&operator_addr, &info.sender), &expires)?;
 
-        Ok(Response::new()
-            .add_attribute("action", "approve_all")
-            .add_attribute("sender", info.sender)
-            .add_attribute("operator", operator))
+        Ok(Cw721Event::ApproveAll {
+            sender: info.sender.to_string(),
+            operator,
+        }
+        .add_to_response(Response::new()))
     }
 
     fn revoke_all(
@@ -257,88 +538,404 @@ pub trait Cw721Execute<
         operator: String,
     ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
         let operator_addr = deps.api.addr_validate(&operator)?;
-        let config = Cw721Config::<
-            TMetadataExtension,
-            TCustomResponseMessage,
-            TMetadataExtensionMsg,
-        >::default();
+        let config = self.config();
         config
             .operators
             .remove(deps.storage, (&info.sender, &operator_addr));
+        config
+            .operator_grants
+            .remove(deps.storage, (&operator_addr, &info.sender));
 
-        Ok(Response::new()
-            .add_attribute("action", "revoke_all")
-            .add_attribute("sender", info.sender)
-            .add_attribute("operator", operator))
+        Ok(Cw721Event::RevokeAll {
+            sender: info.sender.to_string(),
+            operator,
+        }
+        .add_to_response(Response::new()))
     }
 
     fn burn_nft(
         &self,
-        deps: DepsMut,
+        mut deps: DepsMut,
         env: Env,
         info: MessageInfo,
         token_id: String,
     ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
-        let config = Cw721Config::<
-            TMetadataExtension,
-            TCustomResponseMessage,
-            TMetadataExtensionMsg,
-        >::default();
-        let token = config.nft_info.load(deps.storage, &token_id)?;
-        check_can_send(deps.as_ref(), &env, &info, &token)?;
-
-        config.nft_info.remove(deps.storage, &token_id)?;
+        let config = self.config();
+        let token = _burn_nft::<TMetadataExtension>(
+            deps.branch(),
+            &env,
+            &info,
+            &token_id,
+            self.storage_keys(),
+        )?;
         config.decrement_tokens(deps.storage)?;
+        let hook_msg = Cw721HookMsg::Burn {
+            owner: token.owner.to_string(),
+            token_id: token_id.clone(),
+        };
+        let hooks = config.hook_sub_msgs(deps.storage, &hook_msg)?;
 
-        Ok(Response::new()
-            .add_attribute("action", "burn")
-            .add_attribute("sender", info.sender)
-            .add_attribute("token_id", token_id))
+        Ok(Cw721Event::Burn {
+            sender: info.sender.to_string(),
+            token_id,
+        }
+        .add_to_response(Response::new().add_submessages(hooks)))
     }
 
     // ------- opionated cw721 functions -------
+    fn initialize_creator(
+        &self,
+        storage: &mut dyn Storage,
+        api: &dyn Api,
+        creator: Option<&str>,
+    ) -> StdResult<Ownership<Addr>> {
+        let config = self.config();
+        config.creator.initialize_owner(storage, api, creator)
+    }
+
     fn initialize_minter(
         &self,
         storage: &mut dyn Storage,
         api: &dyn Api,
         minter: Option<&str>,
     ) -> StdResult<Ownership<Addr>> {
-        MINTER.initialize_owner(storage, api, minter)
+        let config = self.config();
+        config.minter.initialize_owner(storage, api, minter)
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn mint(
         &self,
         deps: DepsMut,
+        env: Env,
         info: MessageInfo,
         token_id: String,
         owner: String,
         token_uri: Option<String>,
         extension: TMetadataExtension,
     ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
-        MINTER.assert_owner(deps.storage, &info.sender)?;
+        let config = self.config();
+        config.minter.assert_owner(deps.storage, &info.sender)?;
+        config.assert_not_paused(deps.storage, &env.block, &PauseTarget::Minting)?;
+        config.assert_max_supply(deps.storage, 1)?;
 
         // create the token
-        let token = NftInfo {
-            owner: deps.api.addr_validate(&owner)?,
-            approvals: vec![],
+        _mint_nft(
+            deps.storage,
+            deps.api,
+            &env.block,
+            &token_id,
+            &owner,
             token_uri,
             extension,
+            self.storage_keys(),
+        )?;
+        config.increment_tokens(deps.storage)?;
+        let hook_msg = Cw721HookMsg::Mint {
+            owner: owner.clone(),
+            token_id: token_id.clone(),
         };
-        let config = Cw721Config::<TMetadataExtension, Empty, Empty>::default();
-        config
-            .nft_info
-            .update(deps.storage, &token_id, |old| match old {
-                Some(_) => Err(Cw721ContractError::Claimed {}),
-                None => Ok(token),
-            })?;
+        let hooks = config.hook_sub_msgs(deps.storage, &hook_msg)?;
 
-        config.increment_tokens(deps.storage)?;
+        Ok(Cw721Event::Mint {
+            minter: info.sender.to_string(),
+            owner,
+            token_id,
+        }
+        .add_to_response(Response::new().add_submessages(hooks)))
+    }
+
+    /// Mints a token using the next sequential token id.
+    fn mint_next(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        owner: String,
+        token_uri: Option<String>,
+        extension: TMetadataExtension,
+    ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
+        let config = self.config();
+        let token_id = config.next_token_id(deps.storage)?;
+        self.mint(
+            deps,
+            env,
+            info,
+            token_id.to_string(),
+            owner,
+            token_uri,
+            extension,
+        )
+    }
+
+    // ------- batch functions -------
+    fn batch_mint(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        mints: Vec<MintMsg<TMetadataExtension>>,
+    ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
+        let config = self.config();
+        config.minter.assert_owner(deps.storage, &info.sender)?;
+        config.assert_not_paused(deps.storage, &env.block, &PauseTarget::Minting)?;
+        config.assert_batch_size(deps.storage, mints.len())?;
+        config.assert_max_supply(deps.storage, mints.len() as u64)?;
 
-        Ok(Response::new()
-            .add_attribute("action", "mint")
-            .add_attribute("minter", info.sender)
-            .add_attribute("owner", owner)
-            .add_attribute("token_id", token_id))
+        let mut events: Vec<Cw721Event> = Vec::with_capacity(mints.len());
+        let mut hooks = vec![];
+        for mint in mints {
+            _mint_nft(
+                deps.storage,
+                deps.api,
+                &env.block,
+                &mint.token_id,
+                &mint.owner,
+                mint.token_uri,
+                mint.extension,
+                self.storage_keys(),
+            )?;
+            let hook_msg = Cw721HookMsg::Mint {
+                owner: mint.owner.clone(),
+                token_id: mint.token_id.clone(),
+            };
+            hooks.extend(config.hook_sub_msgs(deps.storage, &hook_msg)?);
+            events.push(Cw721Event::Mint {
+                minter: info.sender.to_string(),
+                owner: mint.owner,
+                token_id: mint.token_id,
+            });
+        }
+        config.increment_tokens_by(deps.storage, events.len() as u64)?;
+
+        Ok(events
+            .into_iter()
+            .fold(Response::new().add_submessages(hooks), |response, event| {
+                event.add_to_response(response)
+            }))
+    }
+
+    fn batch_transfer_nft(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        recipient: String,
+        token_ids: Vec<String>,
+    ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
+        let config = self.config();
+        config.assert_batch_size(deps.storage, token_ids.len())?;
+        let mut hooks = vec![];
+        for token_id in &token_ids {
+            let previous_owner = _transfer_nft::<TMetadataExtension>(
+                deps.branch(),
+                &env,
+                &info,
+                &recipient,
+                token_id,
+                ProvenanceAction::Transfer,
+                self.storage_keys(),
+            )?;
+            let hook_msg = Cw721HookMsg::Transfer {
+                sender: info.sender.to_string(),
+                previous_owner: previous_owner.to_string(),
+                recipient: recipient.clone(),
+                token_id: token_id.clone(),
+            };
+            hooks.extend(config.hook_sub_msgs(deps.storage, &hook_msg)?);
+        }
+
+        let events = token_ids.into_iter().map(|token_id| Cw721Event::Transfer {
+            sender: info.sender.to_string(),
+            recipient: recipient.clone(),
+            token_id,
+        });
+        Ok(
+            events.fold(Response::new().add_submessages(hooks), |response, event| {
+                event.add_to_response(response)
+            }),
+        )
+    }
+
+    fn batch_approve(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        spender: String,
+        token_ids: Vec<String>,
+        expires: Option<Expiration>,
+    ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
+        let config = self.config();
+        config.assert_batch_size(deps.storage, token_ids.len())?;
+        for token_id in &token_ids {
+            _update_approvals::<TMetadataExtension>(
+                deps.branch(),
+                &env,
+                &info,
+                &spender,
+                token_id,
+                true,
+                expires,
+                self.storage_keys(),
+            )?;
+        }
+
+        let events = token_ids.into_iter().map(|token_id| Cw721Event::Approve {
+            sender: info.sender.to_string(),
+            spender: spender.clone(),
+            token_id,
+        });
+        Ok(events.fold(Response::new(), |response, event| {
+            event.add_to_response(response)
+        }))
+    }
+
+    fn batch_revoke(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        spender: String,
+        token_ids: Vec<String>,
+    ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
+        let config = self.config();
+        config.assert_batch_size(deps.storage, token_ids.len())?;
+        for token_id in &token_ids {
+            _update_approvals::<TMetadataExtension>(
+                deps.branch(),
+                &env,
+                &info,
+                &spender,
+                token_id,
+                false,
+                None,
+                self.storage_keys(),
+            )?;
+        }
+
+        let events = token_ids.into_iter().map(|token_id| Cw721Event::Revoke {
+            sender: info.sender.to_string(),
+            spender: spender.clone(),
+            token_id,
+        });
+        Ok(events.fold(Response::new(), |response, event| {
+            event.add_to_response(response)
+        }))
+    }
+
+    fn batch_burn(
+        &self,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_ids: Vec<String>,
+    ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
+        let config = self.config();
+        config.assert_batch_size(deps.storage, token_ids.len())?;
+        let mut hooks = vec![];
+        for token_id in &token_ids {
+            let token = _burn_nft::<TMetadataExtension>(
+                deps.branch(),
+                &env,
+                &info,
+                token_id,
+                self.storage_keys(),
+            )?;
+            let hook_msg = Cw721HookMsg::Burn {
+                owner: token.owner.to_string(),
+                token_id: token_id.clone(),
+            };
+            hooks.extend(config.hook_sub_msgs(deps.storage, &hook_msg)?);
+        }
+        config.decrement_tokens_by(deps.storage, token_ids.len() as u64)?;
+
+        let events = token_ids.into_iter().map(|token_id| Cw721Event::Burn {
+            sender: info.sender.to_string(),
+            token_id,
+        });
+        Ok(
+            events.fold(Response::new().add_submessages(hooks), |response, event| {
+                event.add_to_response(response)
+            }),
+        )
+    }
+
+    /// Removes expired approvals of the given tokens and expired operators of the given owners,
+    /// at most `limit` entries in total.
+    fn prune_expired(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_ids: Vec<String>,
+        owners: Vec<String>,
+        limit: Option<u32>,
+    ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
+        let config = self.config();
+        config.assert_batch_size(deps.storage, token_ids.len() + owners.len())?;
+        let max_batch_size = config.max_batch_size(deps.storage)?;
+        let mut remaining = limit.unwrap_or(max_batch_size).min(max_batch_size) as usize;
+
+        let mut pruned_approvals = 0u64;
+        for token_id in &token_ids {
+            if remaining == 0 {
+                break;
+            }
+            // tokens may have been burned in the meantime
+            let mut token = match config.nft_info.may_load(deps.storage, token_id)? {
+                Some(token) => token,
+                None => continue,
+            };
+            let expired: Vec<Approval> = token
+                .approvals
+                .iter()
+                .filter(|apr| apr.is_expired(&env.block))
+                .take(remaining)
+                .cloned()
+                .collect();
+            if expired.is_empty() {
+                continue;
+            }
+            token.approvals.retain(|apr| !expired.contains(apr));
+            config.clear_approved_tokens(deps.storage, token_id, &expired);
+            config.nft_info.save(deps.storage, token_id, &token)?;
+            remaining -= expired.len();
+            pruned_approvals += expired.len() as u64;
+        }
+
+        let mut pruned_operators = 0u64;
+        for owner in &owners {
+            if remaining == 0 {
+                break;
+            }
+            let owner_addr = deps.api.addr_validate(owner)?;
+            let expired = config
+                .operators
+                .prefix(&owner_addr)
+                .range(deps.storage, None, None, Order::Ascending)
+                .filter(|r| r.is_err() || r.as_ref().unwrap().1.is_expired(&env.block))
+                .take(remaining)
+                .map(|item| item.map(|(operator, _)| operator))
+                .collect::<StdResult<Vec<_>>>()?;
+            for operator in &expired {
+                config
+                    .operators
+                    .remove(deps.storage, (&owner_addr, operator));
+                config
+                    .operator_grants
+                    .remove(deps.storage, (operator, &owner_addr));
+            }
+            remaining -= expired.len();
+            pruned_operators += expired.len() as u64;
+        }
+
+        Ok(Cw721Event::PruneExpired {
+            sender: info.sender.to_string(),
+            pruned_approvals,
+            pruned_operators,
+        }
+        .add_to_response(Response::new()))
     }
 
     fn update_minter_ownership(
@@ -348,14 +945,472 @@ pub trait Cw721Execute<
         info: MessageInfo,
         action: Action,
     ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
-        let ownership =
-            MINTER.update_ownership(deps.api, deps.storage, &env.block, &info.sender, action)?;
-        Ok(Response::new()
-            .add_attribute("update_minter_ownership", info.sender)
-            .add_attributes(ownership.into_attributes()))
+        let config = self.config();
+        let ownership = config.minter.update_ownership(
+            deps.api,
+            deps.storage,
+            &env.block,
+            &info.sender,
+            action,
+        )?;
+        Ok(Cw721Event::UpdateMinterOwnership {
+            sender: info.sender.to_string(),
+            owner: ownership.owner.map(|owner| owner.to_string()),
+            pending_owner: ownership.pending_owner.map(|owner| owner.to_string()),
+            pending_expiry: ownership.pending_expiry.map(|expiry| expiry.to_string()),
+        }
+        .add_to_response(Response::new()))
+    }
+
+    fn update_creator_ownership(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        action: Action,
+    ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
+        let config = self.config();
+        let ownership = config.creator.update_ownership(
+            deps.api,
+            deps.storage,
+            &env.block,
+            &info.sender,
+            action,
+        )?;
+        Ok(Cw721Event::UpdateCreatorOwnership {
+            sender: info.sender.to_string(),
+            owner: ownership.owner.map(|owner| owner.to_string()),
+            pending_owner: ownership.pending_owner.map(|owner| owner.to_string()),
+            pending_expiry: ownership.pending_expiry.map(|expiry| expiry.to_string()),
+        }
+        .add_to_response(Response::new()))
     }
 
-    /// Allows creator to update onchain metadata. For now this is a no-op.
+    fn update_collection_info(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        msg: CollectionInfoMsg<TCollectionInfoExtension>,
+    ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
+        let config = self.config();
+        config.creator.assert_owner(deps.storage, &info.sender)?;
+        msg.extension.validate_collection_info(deps.api)?;
+        let collection_info = CollectionInfo {
+            name: msg.name,
+            symbol: msg.symbol,
+        };
+        config
+            .collection_info
+            .save(deps.storage, &collection_info)?;
+        config
+            .collection_info_extension
+            .save(deps.storage, &msg.extension)?;
+        Ok(Cw721Event::UpdateCollectionInfo {
+            sender: info.sender.to_string(),
+        }
+        .add_to_response(Response::new()))
+    }
+
+    /// Allows creator to update token uri and metadata of an NFT, as long as it is not frozen.
+    fn update_nft_info(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        token_id: String,
+        token_uri: Option<String>,
+        extension: Option<TMetadataExtension>,
+    ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
+        let config = self.config();
+        config.creator.assert_owner(deps.storage, &info.sender)?;
+        if config.is_nft_info_frozen(deps.storage, &token_id)? {
+            return Err(Cw721ContractError::NftInfoFrozen { token_id });
+        }
+        let mut nft_info = config.nft_info.load(deps.storage, &token_id)?;
+
+        // only changed fields are updated and added to the event
+        if let Some(token_uri) = &token_uri {
+            if !token_uri.is_empty() {
+                validate_metadata_url(token_uri)?;
+            }
+            nft_info.token_uri = Some(token_uri.clone()).filter(|uri| !uri.is_empty());
+        }
+        let extension = match extension {
+            Some(extension) => {
+                extension.validate_metadata()?;
+                let extension_json = to_json_string(&extension)?;
+                nft_info.extension = extension;
+                Some(extension_json)
+            }
+            None => None,
+        };
+        config.nft_info.save(deps.storage, &token_id, &nft_info)?;
+
+        Ok(Cw721Event::UpdateNftInfo {
+            sender: info.sender.to_string(),
+            token_id,
+            token_uri,
+            extension,
+        }
+        .add_to_response(Response::new()))
+    }
+
+    /// Freezes metadata of a single NFT or, if no token id is given, of the whole collection.
+    /// A single NFT without token uri keeps the uri derived from the current base uri.
+    fn freeze_nft_info(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        token_id: Option<String>,
+    ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
+        let config = self.config();
+        config.creator.assert_owner(deps.storage, &info.sender)?;
+        match &token_id {
+            Some(token_id) => {
+                // make sure token exists
+                let mut nft_info = config.nft_info.load(deps.storage, token_id)?;
+                // pin the uri derived from base uri, so base uri updates do not change frozen tokens
+                if nft_info.token_uri.is_none() {
+                    if let Some(base_uri) = config.base_uri.may_load(deps.storage)? {
+                        nft_info.token_uri = Some(base_uri.token_uri(token_id));
+                        config.nft_info.save(deps.storage, token_id, &nft_info)?;
+                    }
+                }
+                config
+                    .frozen_nft_infos
+                    .save(deps.storage, token_id, &true)?;
+            }
+            None => config
+                .collection_nft_info_frozen
+                .save(deps.storage, &true)?,
+        }
+        Ok(Cw721Event::FreezeNftInfo {
+            sender: info.sender.to_string(),
+            token_id,
+        }
+        .add_to_response(Response::new()))
+    }
+
+    fn update_base_uri(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        base_uri: Option<BaseUri>,
+    ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
+        let config = self.config();
+        config.creator.assert_owner(deps.storage, &info.sender)?;
+        _update_base_uri(deps.storage, base_uri.as_ref(), self.storage_keys())?;
+        Ok(Cw721Event::UpdateBaseUri {
+            sender: info.sender.to_string(),
+            base_uri: base_uri.map(|base_uri| base_uri.uri),
+        }
+        .add_to_response(Response::new()))
+    }
+
+    fn set_placeholder(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        token_uri: Option<String>,
+        extension: TMetadataExtension,
+        reveal_at: Option<Expiration>,
+    ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
+        let config = self.config();
+        config.creator.assert_owner(deps.storage, &info.sender)?;
+        // hiding metadata of a frozen collection is not possible
+        if config
+            .collection_nft_info_frozen
+            .may_load(deps.storage)?
+            .unwrap_or_default()
+        {
+            return Err(Cw721ContractError::CollectionNftInfoFrozen {});
+        }
+        extension.validate_metadata()?;
+        let reveal_at = reveal_at.unwrap_or_default();
+        let placeholder = Placeholder {
+            token_uri,
+            extension,
+            reveal_at,
+        };
+        config.placeholder.save(deps.storage, &placeholder)?;
+        Ok(Cw721Event::SetPlaceholder {
+            sender: info.sender.to_string(),
+            reveal_at: reveal_at.to_string(),
+        }
+        .add_to_response(Response::new()))
+    }
+
+    fn reveal(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        base_uri: Option<BaseUri>,
+    ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
+        let config = self.config();
+        config.creator.assert_owner(deps.storage, &info.sender)?;
+        if !config.placeholder.exists(deps.storage) {
+            return Err(Cw721ContractError::AlreadyRevealed {});
+        }
+        config.placeholder.remove(deps.storage);
+        if base_uri.is_some() {
+            _update_base_uri(deps.storage, base_uri.as_ref(), self.storage_keys())?;
+        }
+        Ok(Cw721Event::Reveal {
+            sender: info.sender.to_string(),
+            base_uri: base_uri.map(|base_uri| base_uri.uri),
+        }
+        .add_to_response(Response::new()))
+    }
+
+    fn update_provenance_cap(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        max_entries: Option<u32>,
+    ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
+        let config = self.config();
+        config.creator.assert_owner(deps.storage, &info.sender)?;
+        match max_entries {
+            Some(max_entries) => config.provenance_cap.save(deps.storage, &max_entries)?,
+            None => config.provenance_cap.remove(deps.storage),
+        }
+        Ok(Cw721Event::UpdateProvenanceCap {
+            sender: info.sender.to_string(),
+            max_entries: max_entries.map(u64::from),
+        }
+        .add_to_response(Response::new()))
+    }
+
+    fn pause(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        target: PauseTarget,
+        expires: Option<Expiration>,
+    ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
+        let config = self.config();
+        config.creator.assert_owner(deps.storage, &info.sender)?;
+        // reject expired data as invalid
+        let expires = expires.unwrap_or_default();
+        if expires.is_expired(&env.block) {
+            return Err(Cw721ContractError::Expired {});
+        }
+        config
+            .paused
+            .save(deps.storage, target.as_str(), &expires)?;
+        Ok(Cw721Event::Pause {
+            sender: info.sender.to_string(),
+            target: target.as_str().to_string(),
+            expires: expires.to_string(),
+        }
+        .add_to_response(Response::new()))
+    }
+
+    fn unpause(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        target: PauseTarget,
+    ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
+        let config = self.config();
+        config.creator.assert_owner(deps.storage, &info.sender)?;
+        config.paused.remove(deps.storage, target.as_str());
+        Ok(Cw721Event::Unpause {
+            sender: info.sender.to_string(),
+            target: target.as_str().to_string(),
+        }
+        .add_to_response(Response::new()))
+    }
+
+    fn lock_nft(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        until: Expiration,
+    ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
+        // reject expired data as invalid
+        if until.is_expired(&env.block) {
+            return Err(Cw721ContractError::Expired {});
+        }
+        let config = self.config();
+        let token = config.nft_info.load(deps.storage, &token_id)?;
+        config.assert_not_locked(deps.storage, &env.block, &token.owner, &token_id)?;
+        // only owner and operators can lock, approvals for single tokens (e.g. listings) can not
+        check_can_approve(deps.as_ref(), &env, &info, &token, self.storage_keys())?;
+        let lock = NftLock {
+            locker: info.sender.clone(),
+            until,
+        };
+        config
+            .nft_locks
+            .save(deps.storage, (&token.owner, &token_id), &lock)?;
+        Ok(Cw721Event::LockNft {
+            sender: info.sender.to_string(),
+            token_id,
+            until: until.to_string(),
+        }
+        .add_to_response(Response::new()))
+    }
+
+    fn unlock_nft(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+    ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
+        let config = self.config();
+        let token = config.nft_info.load(deps.storage, &token_id)?;
+        let lock = match config
+            .nft_locks
+            .may_load(deps.storage, (&token.owner, &token_id))?
+        {
+            Some(lock) => lock,
+            None => return Err(Cw721ContractError::NftNotLocked { token_id }),
+        };
+        // owner can only remove locks set by others once they are expired
+        let owner_can_unlock = token.owner == info.sender && lock.is_expired(&env.block);
+        if lock.locker != info.sender && !owner_can_unlock {
+            return Err(Cw721ContractError::NotLocker {
+                token_id,
+                locker: lock.locker.to_string(),
+            });
+        }
+        config
+            .nft_locks
+            .remove(deps.storage, (&token.owner, &token_id));
+        Ok(Cw721Event::UnlockNft {
+            sender: info.sender.to_string(),
+            token_id,
+        }
+        .add_to_response(Response::new()))
+    }
+
+    fn set_user(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        user: Option<String>,
+        expires: Option<Expiration>,
+    ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
+        let config = self.config();
+        let token = config.nft_info.load(deps.storage, &token_id)?;
+        // locks only prevent transfers, so users can still be set e.g. for staked tokens
+        check_owner_or_approved(deps.as_ref(), &env, &info, &token, self.storage_keys())?;
+        config.clear_nft_user(deps.storage, &token_id)?;
+        let expires = expires.unwrap_or_default();
+        if let Some(user) = &user {
+            // removing a user is always possible, so owners can react while transfers are paused
+            config.assert_not_paused(deps.storage, &env.block, &PauseTarget::Transfers)?;
+            // reject expired data as invalid
+            if expires.is_expired(&env.block) {
+                return Err(Cw721ContractError::Expired {});
+            }
+            let user = deps.api.addr_validate(user)?;
+            config
+                .user_tokens
+                .save(deps.storage, (&user, &token_id), &expires)?;
+            config
+                .nft_users
+                .save(deps.storage, &token_id, &NftUser { user, expires })?;
+        }
+        Ok(Cw721Event::SetUser {
+            sender: info.sender.to_string(),
+            token_id,
+            user,
+            expires: expires.to_string(),
+        }
+        .add_to_response(Response::new()))
+    }
+
+    fn add_hook(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        addr: String,
+        mode: HookMode,
+    ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
+        let config = self.config();
+        config.creator.assert_owner(deps.storage, &info.sender)?;
+        let hook_addr = deps.api.addr_validate(&addr)?;
+        // updating the mode of a registered hook is always possible
+        if !config.hooks.has(deps.storage, &hook_addr)
+            && config
+                .hooks
+                .keys(deps.storage, None, None, Order::Ascending)
+                .count()
+                >= MAX_HOOKS as usize
+        {
+            return Err(Cw721ContractError::MaxHooksReached { max: MAX_HOOKS });
+        }
+        config.hooks.save(deps.storage, &hook_addr, &mode)?;
+        let mode = match mode {
+            HookMode::Blocking => "blocking",
+            HookMode::FireAndForget => "fire_and_forget",
+        };
+        Ok(Cw721Event::AddHook {
+            sender: info.sender.to_string(),
+            hook: hook_addr.to_string(),
+            mode: mode.to_string(),
+        }
+        .add_to_response(Response::new()))
+    }
+
+    fn remove_hook(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        addr: String,
+    ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
+        let config = self.config();
+        config.creator.assert_owner(deps.storage, &info.sender)?;
+        let hook_addr = deps.api.addr_validate(&addr)?;
+        if !config.hooks.has(deps.storage, &hook_addr) {
+            return Err(Cw721ContractError::HookNotRegistered { addr });
+        }
+        config.hooks.remove(deps.storage, &hook_addr);
+        Ok(Cw721Event::RemoveHook {
+            sender: info.sender.to_string(),
+            hook: hook_addr.to_string(),
+        }
+        .add_to_response(Response::new()))
+    }
+
+    /// Handles replies of fire-and-forget hooks, whose errors are ignored.
+    fn reply(
+        &self,
+        _deps: DepsMut,
+        _env: Env,
+        msg: Reply,
+    ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
+        match msg.id {
+            HOOK_REPLY_ID => {
+                let error = match msg.result {
+                    SubMsgResult::Err(error) => error,
+                    SubMsgResult::Ok(_) => String::default(),
+                };
+                Ok(Cw721Event::HookFailed { error }.add_to_response(Response::new()))
+            }
+            id => Err(Cw721ContractError::UnknownReplyId { id }),
+        }
+    }
+
+    /// Custom hook for contracts extending cw721, allowing creator to handle `TMetadataExtensionMsg`.
+    /// Rejected by default, so it never silently succeeds: use `update_nft_info` for updating
+    /// metadata of an NFT.
     fn update_metadata_extension(
         &self,
         deps: DepsMut,
@@ -363,8 +1418,9 @@ pub trait Cw721Execute<
         info: MessageInfo,
         _msg: TMetadataExtensionMsg,
     ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
-        cw_ownable::assert_owner(deps.storage, &info.sender)?;
-        Ok(Response::new().add_attribute("action", "update_metadata_extension"))
+        let config = self.config();
+        config.creator.assert_owner(deps.storage, &info.sender)?;
+        Err(Cw721ContractError::UnsupportedExtensionMsg {})
     }
 
     fn set_withdraw_address(
@@ -373,17 +1429,15 @@ pub trait Cw721Execute<
         sender: &Addr,
         address: String,
     ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
-        cw_ownable::assert_owner(deps.storage, sender)?;
+        let config = self.config();
+        config.creator.assert_owner(deps.storage, sender)?;
         deps.api.addr_validate(&address)?;
-        let config = Cw721Config::<
-            TMetadataExtension,
-            TCustomResponseMessage,
-            TMetadataExtensionMsg,
-        >::default();
         config.withdraw_address.save(deps.storage, &address)?;
-        Ok(Response::new()
-            .add_attribute("action", "set_withdraw_address")
-            .add_attribute("address", address))
+        Ok(Cw721Event::SetWithdrawAddress {
+            sender: sender.to_string(),
+            address,
+        }
+        .add_to_response(Response::new()))
     }
 
     fn remove_withdraw_address(
@@ -391,19 +1445,17 @@ pub trait Cw721Execute<
         storage: &mut dyn Storage,
         sender: &Addr,
     ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
-        cw_ownable::assert_owner(storage, sender)?;
-        let config = Cw721Config::<
-            TMetadataExtension,
-            TCustomResponseMessage,
-            TMetadataExtensionMsg,
-        >::default();
+        let config = self.config();
+        config.creator.assert_owner(storage, sender)?;
         let address = config.withdraw_address.may_load(storage)?;
         match address {
             Some(address) => {
                 config.withdraw_address.remove(storage);
-                Ok(Response::new()
-                    .add_attribute("action", "remove_withdraw_address")
-                    .add_attribute("address", address))
+                Ok(Cw721Event::RemoveWithdrawAddress {
+                    sender: sender.to_string(),
+                    address,
+                }
+                .add_to_response(Response::new()))
             }
             None => Err(Cw721ContractError::NoWithdrawAddress {}),
         }
@@ -414,24 +1466,19 @@ pub trait Cw721Execute<
         storage: &mut dyn Storage,
         amount: &Coin,
     ) -> Result<Response<TCustomResponseMessage>, Cw721ContractError> {
-        let withdraw_address = Cw721Config::<
-            TMetadataExtension,
-            TCustomResponseMessage,
-            TMetadataExtensionMsg,
-        >::default()
-        .withdraw_address
-        .may_load(storage)?;
+        let withdraw_address = self.config().withdraw_address.may_load(storage)?;
         match withdraw_address {
             Some(address) => {
                 let msg = BankMsg::Send {
-                    to_address: address,
+                    to_address: address.clone(),
                     amount: vec![amount.clone()],
                 };
-                Ok(Response::new()
-                    .add_message(msg)
-                    .add_attribute("action", "withdraw_funds")
-                    .add_attribute("amount", amount.amount.to_string())
-                    .add_attribute("denom", amount.denom.to_string()))
+                Ok(Cw721Event::WithdrawFunds {
+                    address,
+                    amount: amount.amount.to_string(),
+                    denom: amount.denom.to_string(),
+                }
+                .add_to_response(Response::new().add_message(msg)))
             }
             None => Err(Cw721ContractError::NoWithdrawAddress {}),
         }
@@ -439,25 +1486,166 @@ pub trait Cw721Execute<
 }
 
 // ------- helper cw721 functions -------
+#[allow(clippy::too_many_arguments)]
+fn _mint_nft<TMetadataExtension>(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    block: &BlockInfo,
+    token_id: &str,
+    owner: &str,
+    token_uri: Option<String>,
+    extension: TMetadataExtension,
+    keys: Cw721StorageKeys,
+) -> Result<NftInfo<TMetadataExtension>, Cw721ContractError>
+where
+    TMetadataExtension: Serialize + DeserializeOwned + Clone + ValidateMetadata,
+{
+    if let Some(token_uri) = &token_uri {
+        validate_metadata_url(token_uri)?;
+    }
+    extension.validate_metadata()?;
+    let token = NftInfo {
+        owner: api.addr_validate(owner)?,
+        approvals: vec![],
+        token_uri,
+        extension,
+    };
+    let config = Cw721Config::<TMetadataExtension, Empty, Empty, Empty>::new(keys);
+    config.token_id_policy(storage)?.validate(token_id)?;
+    config.assert_not_burned(storage, token_id)?;
+    let token = config.nft_info.update(storage, token_id, |old| match old {
+        Some(_) => Err(Cw721ContractError::Claimed {}),
+        None => Ok(token),
+    })?;
+    config.burned_tokens.remove(storage, token_id);
+    config.advance_next_token_id(storage, token_id)?;
+    config.increment_total_minted(storage)?;
+    config.increment_balance(storage, &token.owner)?;
+    config.record_ownership_snapshot(storage, block, token_id, None, Some(&token.owner))?;
+    config.record_provenance(
+        storage,
+        block,
+        token_id,
+        None,
+        Some(token.owner.clone()),
+        ProvenanceAction::Mint,
+    )?;
+    Ok(token)
+}
+
+/// Sets or removes the base uri, which is not possible anymore once the whole collection is frozen.
+fn _update_base_uri(
+    storage: &mut dyn Storage,
+    base_uri: Option<&BaseUri>,
+    keys: Cw721StorageKeys,
+) -> Result<(), Cw721ContractError> {
+    let config = Cw721Config::<Empty, Empty, Empty, Empty>::new(keys);
+    if config
+        .collection_nft_info_frozen
+        .may_load(storage)?
+        .unwrap_or_default()
+    {
+        return Err(Cw721ContractError::BaseUriFrozen {});
+    }
+    match base_uri {
+        Some(base_uri) => {
+            base_uri.validate()?;
+            config.base_uri.save(storage, base_uri)?;
+        }
+        None => config.base_uri.remove(storage),
+    }
+    Ok(())
+}
+
+/// Removes the token, token count must be updated by caller
+fn _burn_nft<TMetadataExtension>(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    token_id: &str,
+    keys: Cw721StorageKeys,
+) -> Result<NftInfo<TMetadataExtension>, Cw721ContractError>
+where
+    TMetadataExtension: Serialize + DeserializeOwned + Clone,
+{
+    let config = Cw721Config::<TMetadataExtension, Empty, Empty, Empty>::new(keys);
+    config.assert_not_paused(deps.storage, &env.block, &PauseTarget::Transfers)?;
+    let token = config.nft_info.load(deps.storage, token_id)?;
+    check_can_send(deps.as_ref(), env, info, token_id, &token, keys)?;
+
+    config.nft_info.remove(deps.storage, token_id)?;
+    config.frozen_nft_infos.remove(deps.storage, token_id);
+    config
+        .nft_locks
+        .remove(deps.storage, (&token.owner, token_id));
+    config.clear_approved_tokens(deps.storage, token_id, &token.approvals);
+    config.clear_nft_user(deps.storage, token_id)?;
+    config.burned_tokens.save(deps.storage, token_id, &true)?;
+    config.decrement_balance(deps.storage, &token.owner)?;
+    config.record_ownership_snapshot(
+        deps.storage,
+        &env.block,
+        token_id,
+        Some(&token.owner),
+        None,
+    )?;
+    config.record_provenance(
+        deps.storage,
+        &env.block,
+        token_id,
+        Some(token.owner.clone()),
+        None,
+        ProvenanceAction::Burn,
+    )?;
+    Ok(token)
+}
+
+/// Transfers the token to `recipient`, returns the previous owner.
 fn _transfer_nft<TMetadataExtension>(
     deps: DepsMut,
     env: &Env,
     info: &MessageInfo,
     recipient: &str,
     token_id: &str,
-) -> Result<NftInfo<TMetadataExtension>, Cw721ContractError>
+    action: ProvenanceAction,
+    keys: Cw721StorageKeys,
+) -> Result<Addr, Cw721ContractError>
 where
     TMetadataExtension: Serialize + DeserializeOwned + Clone,
 {
-    let config = Cw721Config::<TMetadataExtension, Empty, Empty>::default();
+    let config = Cw721Config::<TMetadataExtension, Empty, Empty, Empty>::new(keys);
+    config.assert_not_paused(deps.storage, &env.block, &PauseTarget::Transfers)?;
     let mut token = config.nft_info.load(deps.storage, token_id)?;
     // ensure we have permissions
-    check_can_send(deps.as_ref(), env, info, &token)?;
-    // set owner and remove existing approvals
-    token.owner = deps.api.addr_validate(recipient)?;
+    check_can_send(deps.as_ref(), env, info, token_id, &token, keys)?;
+    // set owner and remove existing approvals and expired lock
+    let recipient = deps.api.addr_validate(recipient)?;
+    config
+        .nft_locks
+        .remove(deps.storage, (&token.owner, token_id));
+    config.decrement_balance(deps.storage, &token.owner)?;
+    config.increment_balance(deps.storage, &recipient)?;
+    config.record_ownership_snapshot(
+        deps.storage,
+        &env.block,
+        token_id,
+        Some(&token.owner),
+        Some(&recipient),
+    )?;
+    config.record_provenance(
+        deps.storage,
+        &env.block,
+        token_id,
+        Some(token.owner.clone()),
+        Some(recipient.clone()),
+        action,
+    )?;
+    let previous_owner = std::mem::replace(&mut token.owner, recipient);
+    config.clear_approved_tokens(deps.storage, token_id, &token.approvals);
+    config.clear_nft_user(deps.storage, token_id)?;
     token.approvals = vec![];
     config.nft_info.save(deps.storage, token_id, &token)?;
-    Ok(token)
+    Ok(previous_owner)
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -470,26 +1658,35 @@ fn _update_approvals<TMetadataExtension>(
     // if add == false, remove. if add == true, remove then set with this expiration
     add: bool,
     expires: Option<Expiration>,
+    keys: Cw721StorageKeys,
 ) -> Result<NftInfo<TMetadataExtension>, Cw721ContractError>
 where
     TMetadataExtension: Serialize + DeserializeOwned + Clone,
 {
-    let config = Cw721Config::<TMetadataExtension, Empty, Empty>::default();
+    let config = Cw721Config::<TMetadataExtension, Empty, Empty, Empty>::new(keys);
     let mut token = config.nft_info.load(deps.storage, token_id)?;
     // ensure we have permissions
-    check_can_approve(deps.as_ref(), env, info, &token)?;
+    check_can_approve(deps.as_ref(), env, info, &token, keys)?;
 
     // update the approval list (remove any for the same spender before adding)
     let spender_addr = deps.api.addr_validate(spender)?;
     token.approvals.retain(|apr| apr.spender != spender_addr);
+    config
+        .approved_tokens
+        .remove(deps.storage, (&spender_addr, token_id));
 
     // only difference between approve and revoke
     if add {
+        // revoking is always possible, so owners can react while transfers are paused
+        config.assert_not_paused(deps.storage, &env.block, &PauseTarget::Transfers)?;
         // reject expired data as invalid
         let expires = expires.unwrap_or_default();
         if expires.is_expired(&env.block) {
             return Err(Cw721ContractError::Expired {});
         }
+        config
+            .approved_tokens
+            .save(deps.storage, (&spender_addr, token_id), &expires)?;
         let approval = Approval {
             spender: spender_addr,
             expires,
@@ -508,6 +1705,7 @@ pub fn check_can_approve<TMetadataExtension>(
     env: &Env,
     info: &MessageInfo,
     token: &NftInfo<TMetadataExtension>,
+    keys: Cw721StorageKeys,
 ) -> Result<(), Cw721ContractError>
 where
     TMetadataExtension: Serialize + DeserializeOwned + Clone,
@@ -517,7 +1715,7 @@ where
         return Ok(());
     }
     // operator can approve
-    let config = Cw721Config::<TMetadataExtension, Empty, Empty>::default();
+    let config = Cw721Config::<TMetadataExtension, Empty, Empty, Empty>::new(keys);
     let op = config
         .operators
         .may_load(deps.storage, (&token.owner, &info.sender))?;
@@ -535,10 +1733,26 @@ where
 
 /// returns true iff the sender can transfer ownership of the token
 pub fn check_can_send<TMetadataExtension>(
+    deps: Deps,
+    env: &Env,
+    info: &MessageInfo,
+    token_id: &str,
+    token: &NftInfo<TMetadataExtension>,
+    keys: Cw721StorageKeys,
+) -> Result<(), Cw721ContractError> {
+    let config = Cw721Config::<Empty, Empty, Empty, Empty>::new(keys);
+    // locked tokens can not be sent, not even by the owner
+    config.assert_not_locked(deps.storage, &env.block, &token.owner, token_id)?;
+    check_owner_or_approved(deps, env, info, token, keys)
+}
+
+/// returns true iff the sender is the owner, an approved spender or an operator of the token
+pub fn check_owner_or_approved<TMetadataExtension>(
     deps: Deps,
     env: &Env,
     info: &MessageInfo,
     token: &NftInfo<TMetadataExtension>,
+    keys: Cw721StorageKeys,
 ) -> Result<(), Cw721ContractError> {
     // owner can send
     if token.owner == info.sender {
@@ -555,8 +1769,7 @@ pub fn check_can_send<TMetadataExtension>(
     }
 
     // operator can send
-    let config = Cw721Config::<Empty, Empty, Empty>::default();
-    let op = config
+    let op = Cw721Config::<Empty, Empty, Empty, Empty>::new(keys)
         .operators
         // has token owner approved/gave grant to sender for full control over owner's NFTs?
         .may_load(deps.storage, (&token.owner, &info.sender))?;
@@ -574,37 +1787,59 @@ pub fn check_can_send<TMetadataExtension>(
 }
 
 // ------- migrate -------
+/// Returns the previous contract version.
 pub fn migrate_version(
     storage: &mut dyn Storage,
     contradct_name: &str,
     contract_version: &str,
-    response: Response,
-) -> StdResult<Response> {
-    let response = response
-        .add_attribute("from_version", cw2::get_contract_version(storage)?.version)
-        .add_attribute("to_version", contract_version);
+) -> StdResult<String> {
+    let from_version = cw2::get_contract_version(storage)?.version;
 
     // update contract version
     cw2::set_contract_version(storage, contradct_name, contract_version)?;
-    Ok(response)
+    Ok(from_version)
+}
+
+/// Returns the new creator, if updated.
+pub fn migrate_creator(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    _env: &Env,
+    msg: &Cw721MigrateMsg,
+    keys: Cw721StorageKeys,
+) -> StdResult<Option<String>> {
+    let config = Cw721Config::<Empty, Empty, Empty, Empty>::new(keys);
+    match msg {
+        Cw721MigrateMsg::WithUpdate { creator, .. } => {
+            if let Some(creator) = creator {
+                config
+                    .creator
+                    .initialize_owner(storage, api, Some(creator.as_str()))?;
+            }
+            Ok(creator.clone())
+        }
+    }
 }
 
+/// Returns the new minter, if updated.
 pub fn migrate_minter(
     storage: &mut dyn Storage,
     api: &dyn Api,
     _env: &Env,
     msg: &Cw721MigrateMsg,
-    response: Response,
-) -> StdResult<Response> {
+    keys: Cw721StorageKeys,
+) -> StdResult<Option<String>> {
+    let config = Cw721Config::<Empty, Empty, Empty, Empty>::new(keys);
     match msg {
         Cw721MigrateMsg::WithUpdate { minter, .. } => {
             if let Some(minter) = minter {
-                MINTER.initialize_owner(storage, api, Some(minter.as_str()))?;
-                return Ok(response.add_attribute("creator", minter));
+                config
+                    .minter
+                    .initialize_owner(storage, api, Some(minter.as_str()))?;
             }
+            Ok(minter.clone())
         }
     }
-    Ok(response)
 }
 
 /// Migrates only in case ownership is not present
@@ -615,65 +1850,238 @@ pub fn migrate_minter(
 /// Before v0.19.0 there were confusing naming conventions:
 /// - v0.17.0: minter was replaced by cw_ownable, as a result minter is owner
 /// - v0.16.0 and below: minter was stored in dedicated `minter` store (so NOT using cw_ownable at all)
+///
+/// Returns the migrated creator and minter, `none` in case there is no owner.
 pub fn migrate_legacy_minter_and_creator(
     storage: &mut dyn Storage,
     api: &dyn Api,
     _env: &Env,
     _msg: &Cw721MigrateMsg,
-    response: Response,
-) -> Result<Response, Cw721ContractError> {
-    let minter = MINTER.item.may_load(storage)?;
+    keys: Cw721StorageKeys,
+) -> Result<Option<String>, Cw721ContractError> {
+    let config = Cw721Config::<Empty, Empty, Empty, Empty>::new(keys);
+    let minter = config.minter.item.may_load(storage)?;
     // no migration in case minter is already set
     if minter.is_some() {
-        return Ok(response);
+        return Ok(None);
     }
     // in v0.17/18 cw_ownable::OWNERSHIP was used for minter, now it is used for creator
-    let ownership_previously_used_as_minter = OwnershipStore::new("collection_minter")
-        .item
-        .may_load(storage)?;
+    let ownership_previously_used_as_minter = config.creator.item.may_load(storage)?;
     let creator_and_minter = match ownership_previously_used_as_minter {
         // v0.18 migration
         Some(ownership) => {
             // owner is used for both: creator and minter
             // since it is already set for creator, we only need to migrate minter
             let owner = ownership.owner.map(|a| a.to_string());
-            MINTER.initialize_owner(storage, api, owner.as_deref())?;
+            config
+                .minter
+                .initialize_owner(storage, api, owner.as_deref())?;
             owner
         }
         // v0.17 and older migration
         None => {
             let legacy_minter_store: Item<Addr> = Item::new("minter");
             let legacy_minter = legacy_minter_store.load(storage)?;
-            MINTER.initialize_owner(storage, api, Some(legacy_minter.as_str()))?;
+            config
+                .minter
+                .initialize_owner(storage, api, Some(legacy_minter.as_str()))?;
+            config
+                .creator
+                .initialize_owner(storage, api, Some(legacy_minter.as_str()))?;
             Some(legacy_minter.to_string())
         }
     };
-    Ok(response.add_attribute("creator_and_minter", none_or(creator_and_minter.as_ref())))
+    Ok(Some(none_or(creator_and_minter.as_ref())))
 }
 
-/// Migrates only in case collection_info is not present
+/// Migrates only in case collection_info is not present, returns the migrated collection info.
 pub fn migrate_legacy_collection_info(
     storage: &mut dyn Storage,
     _env: &Env,
     _msg: &Cw721MigrateMsg,
-    response: Response,
-) -> Result<Response, Cw721ContractError> {
-    let contract = Cw721Config::<DefaultOptionMetadataExtension, Empty, Empty>::default();
+    keys: Cw721StorageKeys,
+) -> Result<Option<CollectionInfo>, Cw721ContractError> {
+    let contract = Cw721Config::<DefaultOptionMetadataExtension, Empty, Empty, Empty>::new(keys);
     match contract.collection_info.may_load(storage)? {
-        Some(_) => Ok(response),
+        Some(_) => Ok(None),
         None => {
             // contract info is legacy collection info
             let legacy_collection_info_store: Item<cw721_016::ContractInfoResponse> =
                 Item::new("nft_info");
             let legacy_collection_info = legacy_collection_info_store.load(storage)?;
             let collection_info = CollectionInfo {
-                name: legacy_collection_info.name.clone(),
-                symbol: legacy_collection_info.symbol.clone(),
+                name: legacy_collection_info.name,
+                symbol: legacy_collection_info.symbol,
             };
             contract.collection_info.save(storage, &collection_info)?;
-            Ok(response
-                .add_attribute("migrated collection name", legacy_collection_info.name)
-                .add_attribute("migrated collection symbol", legacy_collection_info.symbol))
+            Ok(Some(collection_info))
         }
     }
 }
+
+/// Backfills `operator_grants` from `operators`, in case contract has been instantiated
+/// before reverse operator lookup was available. Only done once, since it ranges over all operators.
+/// Returns the number of backfilled grants.
+pub fn migrate_operator_grants(
+    storage: &mut dyn Storage,
+    _env: &Env,
+    _msg: &Cw721MigrateMsg,
+    keys: Cw721StorageKeys,
+) -> StdResult<Option<u64>> {
+    let contract = Cw721Config::<DefaultOptionMetadataExtension, Empty, Empty, Empty>::new(keys);
+    if contract.backfills.has(storage, BACKFILL_OPERATOR_GRANTS) {
+        return Ok(None);
+    }
+    let operators = contract
+        .operators
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for ((granter, operator), expires) in &operators {
+        contract
+            .operator_grants
+            .save(storage, (operator, granter), expires)?;
+    }
+    contract
+        .backfills
+        .save(storage, BACKFILL_OPERATOR_GRANTS, &true)?;
+    Ok(Some(operators.len() as u64))
+}
+
+/// Backfills `balances` from the `tokens__owner` index, in case contract has been instantiated
+/// before balances were tracked. Only done once, since it ranges over all tokens.
+/// Returns the number of backfilled owners.
+pub fn migrate_balances<TMetadataExtension>(
+    storage: &mut dyn Storage,
+    _env: &Env,
+    _msg: &Cw721MigrateMsg,
+    keys: Cw721StorageKeys,
+) -> StdResult<Option<u64>>
+where
+    TMetadataExtension: Serialize + DeserializeOwned + Clone,
+{
+    let contract = Cw721Config::<TMetadataExtension, Empty, Empty, Empty>::new(keys);
+    if contract.backfills.has(storage, BACKFILL_BALANCES) {
+        return Ok(None);
+    }
+    // owner index is sorted by owner, so all tokens of an owner are consecutive
+    let owners = contract
+        .nft_info
+        .idx
+        .owner
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, nft_info)| nft_info.owner))
+        .collect::<StdResult<Vec<_>>>()?;
+    let mut balances: Vec<(Addr, u64)> = vec![];
+    for owner in owners {
+        match balances.last_mut() {
+            Some((last_owner, balance)) if *last_owner == owner => *balance += 1,
+            _ => balances.push((owner, 1)),
+        }
+    }
+    for (owner, balance) in &balances {
+        contract.balances.save(storage, owner, balance)?;
+    }
+    contract.backfills.save(storage, BACKFILL_BALANCES, &true)?;
+    Ok(Some(balances.len() as u64))
+}
+
+/// Backfills `approved_tokens` from the approvals of all tokens, in case contract has been
+/// instantiated before the spender index was available. Only done once, since it ranges over all
+/// tokens. Returns the number of backfilled approvals.
+pub fn migrate_approved_tokens<TMetadataExtension>(
+    storage: &mut dyn Storage,
+    _env: &Env,
+    _msg: &Cw721MigrateMsg,
+    keys: Cw721StorageKeys,
+) -> StdResult<Option<u64>>
+where
+    TMetadataExtension: Serialize + DeserializeOwned + Clone,
+{
+    let contract = Cw721Config::<TMetadataExtension, Empty, Empty, Empty>::new(keys);
+    if contract.backfills.has(storage, BACKFILL_APPROVED_TOKENS) {
+        return Ok(None);
+    }
+    let approvals = contract
+        .nft_info
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(token_id, nft_info)| (token_id, nft_info.approvals)))
+        .collect::<StdResult<Vec<_>>>()?;
+    let mut count = 0;
+    for (token_id, approvals) in &approvals {
+        for approval in approvals {
+            contract.approved_tokens.save(
+                storage,
+                (&approval.spender, token_id),
+                &approval.expires,
+            )?;
+            count += 1;
+        }
+    }
+    contract
+        .backfills
+        .save(storage, BACKFILL_APPROVED_TOKENS, &true)?;
+    Ok(Some(count))
+}
+
+/// Initializes `total_minted` with the current number of tokens, in case contract has been
+/// instantiated before mints were counted. Tokens burned before are not known.
+/// Returns the initialized number of minted tokens.
+pub fn migrate_total_minted(
+    storage: &mut dyn Storage,
+    _env: &Env,
+    _msg: &Cw721MigrateMsg,
+    keys: Cw721StorageKeys,
+) -> StdResult<Option<u64>> {
+    let contract = Cw721Config::<Empty, Empty, Empty, Empty>::new(keys);
+    if contract.total_minted.may_load(storage)?.is_some() {
+        return Ok(None);
+    }
+    let total_minted = contract.token_count(storage)?;
+    contract.total_minted.save(storage, &total_minted)?;
+    Ok(Some(total_minted))
+}
+
+/// Enables snapshots if requested and not yet enabled, snapshotting current owners and balances
+/// at migration height. Queries are available from the next height on, which is returned.
+pub fn migrate_snapshots<TMetadataExtension>(
+    storage: &mut dyn Storage,
+    env: &Env,
+    msg: &Cw721MigrateMsg,
+    keys: Cw721StorageKeys,
+) -> StdResult<Option<u64>>
+where
+    TMetadataExtension: Serialize + DeserializeOwned + Clone,
+{
+    let enable_snapshots = match msg {
+        Cw721MigrateMsg::WithUpdate {
+            enable_snapshots, ..
+        } => enable_snapshots.unwrap_or_default(),
+    };
+    let contract = Cw721Config::<TMetadataExtension, Empty, Empty, Empty>::new(keys);
+    if !enable_snapshots || contract.snapshot_height.exists(storage) {
+        return Ok(None);
+    }
+    // snapshots saved at a height are only visible to queries from the next height on
+    let height = env.block.height;
+    contract.snapshot_height.save(storage, &(height + 1))?;
+    let owners = contract
+        .nft_info
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(token_id, nft_info)| (token_id, nft_info.owner)))
+        .collect::<StdResult<Vec<_>>>()?;
+    for (token_id, owner) in &owners {
+        contract
+            .owner_snapshots
+            .save(storage, token_id, owner, height)?;
+    }
+    let balances = contract
+        .balances
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (owner, balance) in &balances {
+        contract
+            .balance_snapshots
+            .save(storage, owner, balance, height)?;
+    }
+    Ok(Some(height + 1))
+}
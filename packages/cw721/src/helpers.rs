@@ -1,11 +1,14 @@
 use std::marker::PhantomData;
 
 use crate::msg::{
-    AllNftInfoResponse, ApprovalResponse, ApprovalsResponse, NftInfoResponse, NumTokensResponse,
-    OperatorsResponse, OwnerOfResponse, TokensResponse,
+    AllNftInfoResponse, ApprovalResponse, ApprovalsResponse, ApprovedTokensResponse,
+    BalanceOfResponse, CollectionInfoAndExtensionResponse, NftInfoResponse, NftInfosResponse,
+    NftLockResponse, NftLocksResponse, NumTokensResponse, OperatorGrantsResponse,
+    OperatorsResponse, OwnerOfAtHeightResponse, OwnerOfResponse, SupplyResponse,
+    TokenHistoryResponse, TokensResponse, TotalMintedResponse, UserOfResponse,
 };
 use crate::msg::{Cw721ExecuteMsg, Cw721QueryMsg};
-use crate::state::CollectionInfo;
+use crate::state::{CollectionInfo, NftLock, NftUser};
 use crate::Approval;
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
@@ -15,17 +18,23 @@ use serde::de::DeserializeOwned;
 use serde::Serialize;
 
 #[cw_serde]
-pub struct Cw721Contract<TMetadataExtension, TMetadataExtensionMsg: CustomMsg>(
+pub struct Cw721Contract<
+    TMetadataExtension,
+    TMetadataExtensionMsg: CustomMsg,
+    TCollectionInfoExtension,
+>(
     pub Addr,
     pub PhantomData<TMetadataExtension>,
     pub PhantomData<TMetadataExtensionMsg>,
+    pub PhantomData<TCollectionInfoExtension>,
 );
 
 #[allow(dead_code)]
-impl<TMetadataExtension, TMetadataExtensionMsg: CustomMsg>
-    Cw721Contract<TMetadataExtension, TMetadataExtensionMsg>
+impl<TMetadataExtension, TMetadataExtensionMsg: CustomMsg, TCollectionInfoExtension>
+    Cw721Contract<TMetadataExtension, TMetadataExtensionMsg, TCollectionInfoExtension>
 where
     TMetadataExtension: Serialize + DeserializeOwned + Clone,
+    TCollectionInfoExtension: Serialize + DeserializeOwned + Clone,
 {
     pub fn addr(&self) -> Addr {
         self.0.clone()
@@ -33,7 +42,7 @@ where
 
     pub fn call(
         &self,
-        msg: Cw721ExecuteMsg<TMetadataExtension, TMetadataExtensionMsg>,
+        msg: Cw721ExecuteMsg<TMetadataExtension, TMetadataExtensionMsg, TCollectionInfoExtension>,
     ) -> StdResult<CosmosMsg> {
         let msg = to_json_binary(&msg)?;
         Ok(WasmMsg::Execute {
@@ -47,7 +56,7 @@ where
     pub fn query<T: DeserializeOwned>(
         &self,
         querier: &QuerierWrapper,
-        req: Cw721QueryMsg<TMetadataExtension>,
+        req: Cw721QueryMsg<TMetadataExtension, TCollectionInfoExtension>,
     ) -> StdResult<T> {
         let query = WasmQuery::Smart {
             contract_addr: self.addr().into(),
@@ -72,6 +81,20 @@ where
         self.query(querier, req)
     }
 
+    pub fn owner_of_at_height<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        token_id: T,
+        height: u64,
+    ) -> StdResult<Option<String>> {
+        let req = Cw721QueryMsg::OwnerOfAtHeight {
+            token_id: token_id.into(),
+            height,
+        };
+        let res: OwnerOfAtHeightResponse = self.query(querier, req)?;
+        Ok(res.owner)
+    }
+
     pub fn approval<T: Into<String>>(
         &self,
         querier: &QuerierWrapper,
@@ -120,18 +143,192 @@ where
         Ok(res.operators)
     }
 
+    pub fn operator_grants<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        operator: T,
+        include_expired: bool,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<OperatorGrantsResponse> {
+        let req = Cw721QueryMsg::OperatorGrants {
+            operator: operator.into(),
+            include_expired: Some(include_expired),
+            start_after,
+            limit,
+        };
+        self.query(querier, req)
+    }
+
+    pub fn approved_tokens<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        spender: T,
+        include_expired: bool,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<ApprovedTokensResponse> {
+        let req = Cw721QueryMsg::ApprovedTokens {
+            spender: spender.into(),
+            include_expired: Some(include_expired),
+            start_after,
+            limit,
+        };
+        self.query(querier, req)
+    }
+
+    pub fn nft_lock<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        token_id: T,
+        include_expired: bool,
+    ) -> StdResult<Option<NftLock>> {
+        let req = Cw721QueryMsg::NftLock {
+            token_id: token_id.into(),
+            include_expired: Some(include_expired),
+        };
+        let res: NftLockResponse = self.query(querier, req)?;
+        Ok(res.lock)
+    }
+
+    pub fn nft_locks<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        owner: T,
+        include_expired: bool,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<NftLocksResponse> {
+        let req = Cw721QueryMsg::NftLocks {
+            owner: owner.into(),
+            include_expired: Some(include_expired),
+            start_after,
+            limit,
+        };
+        self.query(querier, req)
+    }
+
+    pub fn permit_nonce<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        owner: T,
+    ) -> StdResult<u64> {
+        let req = Cw721QueryMsg::PermitNonce {
+            owner: owner.into(),
+        };
+        self.query(querier, req)
+    }
+
+    pub fn user_of<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        token_id: T,
+    ) -> StdResult<Option<NftUser>> {
+        let req = Cw721QueryMsg::UserOf {
+            token_id: token_id.into(),
+        };
+        let res: UserOfResponse = self.query(querier, req)?;
+        Ok(res.user)
+    }
+
+    pub fn tokens_of_user<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        user: T,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<TokensResponse> {
+        let req = Cw721QueryMsg::TokensOfUser {
+            user: user.into(),
+            start_after,
+            limit,
+        };
+        self.query(querier, req)
+    }
+
+    pub fn token_history<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        token_id: T,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<TokenHistoryResponse> {
+        let req = Cw721QueryMsg::TokenHistory {
+            token_id: token_id.into(),
+            start_after,
+            limit,
+        };
+        self.query(querier, req)
+    }
+
+    pub fn balance_of<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        owner: T,
+    ) -> StdResult<u64> {
+        let req = Cw721QueryMsg::BalanceOf {
+            owner: owner.into(),
+        };
+        let res: BalanceOfResponse = self.query(querier, req)?;
+        Ok(res.balance)
+    }
+
+    pub fn balance_of_at_height<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        owner: T,
+        height: u64,
+    ) -> StdResult<u64> {
+        let req = Cw721QueryMsg::BalanceOfAtHeight {
+            owner: owner.into(),
+            height,
+        };
+        let res: BalanceOfResponse = self.query(querier, req)?;
+        Ok(res.balance)
+    }
+
     pub fn num_tokens(&self, querier: &QuerierWrapper) -> StdResult<u64> {
         let req = Cw721QueryMsg::NumTokens {};
         let res: NumTokensResponse = self.query(querier, req)?;
         Ok(res.count)
     }
 
+    pub fn supply(&self, querier: &QuerierWrapper) -> StdResult<SupplyResponse> {
+        let req = Cw721QueryMsg::Supply {};
+        self.query(querier, req)
+    }
+
+    pub fn total_minted(&self, querier: &QuerierWrapper) -> StdResult<TotalMintedResponse> {
+        let req = Cw721QueryMsg::TotalMinted {};
+        self.query(querier, req)
+    }
+
+    pub fn is_burned<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        token_id: T,
+    ) -> StdResult<bool> {
+        let req = Cw721QueryMsg::IsBurned {
+            token_id: token_id.into(),
+        };
+        self.query(querier, req)
+    }
+
     /// With metadata extension
     pub fn collection_info(&self, querier: &QuerierWrapper) -> StdResult<CollectionInfo> {
         let req = Cw721QueryMsg::ContractInfo {};
         self.query(querier, req)
     }
 
+    /// With collection info extension
+    pub fn collection_info_and_extension<U: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+    ) -> StdResult<CollectionInfoAndExtensionResponse<U>> {
+        let req = Cw721QueryMsg::GetCollectionInfo {};
+        self.query(querier, req)
+    }
+
     /// With metadata extension
     pub fn nft_info<T: Into<String>, U: DeserializeOwned>(
         &self,
@@ -158,6 +355,40 @@ where
         self.query(querier, req)
     }
 
+    /// With metadata and enumerable extension
+    pub fn all_nft_infos<U: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        include_expired: bool,
+    ) -> StdResult<NftInfosResponse<U>> {
+        let req = Cw721QueryMsg::AllNftInfos {
+            start_after,
+            limit,
+            include_expired: Some(include_expired),
+        };
+        self.query(querier, req)
+    }
+
+    /// With metadata and enumerable extension
+    pub fn nft_infos_by_owner<T: Into<String>, U: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        owner: T,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        include_expired: bool,
+    ) -> StdResult<NftInfosResponse<U>> {
+        let req = Cw721QueryMsg::NftInfosByOwner {
+            owner: owner.into(),
+            start_after,
+            limit,
+            include_expired: Some(include_expired),
+        };
+        self.query(querier, req)
+    }
+
     /// With enumerable extension
     pub fn tokens<T: Into<String>>(
         &self,
@@ -169,7 +400,9 @@ where
         let req = Cw721QueryMsg::Tokens {
             owner: owner.into(),
             start_after,
+            end_before: None,
             limit,
+            order: None,
         };
         self.query(querier, req)
     }
@@ -181,7 +414,12 @@ where
         start_after: Option<String>,
         limit: Option<u32>,
     ) -> StdResult<TokensResponse> {
-        let req = Cw721QueryMsg::AllTokens { start_after, limit };
+        let req = Cw721QueryMsg::AllTokens {
+            start_after,
+            end_before: None,
+            limit,
+            order: None,
+        };
         self.query(querier, req)
     }
 
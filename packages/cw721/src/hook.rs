@@ -0,0 +1,77 @@
+use schemars::JsonSchema;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_json_binary, Binary, StdResult, SubMsg, WasmMsg};
+
+use crate::state::HookMode;
+
+/// Reply id of fire-and-forget hooks, used for ignoring their errors
+pub const HOOK_REPLY_ID: u64 = 721;
+
+/// Max number of registered hooks, since all of them are called on each mint, transfer, send and burn
+pub const MAX_HOOKS: u32 = 10;
+
+/// Cw721HookMsg is sent to all registered hooks whenever an NFT is minted, transferred, sent or burned.
+/// It should be de/serialized under `Cw721Hook()` variant in a ExecuteMsg
+#[cw_serde]
+pub enum Cw721HookMsg {
+    Mint {
+        owner: String,
+        token_id: String,
+    },
+    /// `sender` may be an operator or approved spender, `previous_owner` is the owner before the transfer
+    Transfer {
+        sender: String,
+        previous_owner: String,
+        recipient: String,
+        token_id: String,
+    },
+    Send {
+        sender: String,
+        previous_owner: String,
+        contract: String,
+        token_id: String,
+    },
+    Burn {
+        owner: String,
+        token_id: String,
+    },
+}
+
+impl Cw721HookMsg {
+    /// serializes the message
+    pub fn into_json_binary(self) -> StdResult<Binary> {
+        let msg = HookExecuteMsg::Cw721Hook(self);
+        to_json_binary(&msg)
+    }
+
+    /// creates a sub message sending this message to the named hook contract.
+    /// Errors of blocking hooks revert the whole transaction, errors of fire-and-forget hooks
+    /// are handled in reply.
+    pub fn into_sub_msg<TAddress: Into<String>, TCustomResponseMessage>(
+        self,
+        contract_addr: TAddress,
+        mode: &HookMode,
+    ) -> StdResult<SubMsg<TCustomResponseMessage>>
+    where
+        TCustomResponseMessage: Clone + std::fmt::Debug + PartialEq + JsonSchema,
+    {
+        let msg = self.into_json_binary()?;
+        let execute = WasmMsg::Execute {
+            contract_addr: contract_addr.into(),
+            msg,
+            funds: vec![],
+        };
+        Ok(match mode {
+            HookMode::Blocking => SubMsg::new(execute),
+            HookMode::FireAndForget => SubMsg::reply_on_error(execute, HOOK_REPLY_ID),
+        })
+    }
+}
+
+/// This is just a helper to properly serialize the above message.
+/// The actual hook contract should include this variant in the larger ExecuteMsg enum
+#[cw_serde]
+enum HookExecuteMsg {
+    Cw721Hook(Cw721HookMsg),
+}
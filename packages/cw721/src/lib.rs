@@ -1,7 +1,10 @@
 pub mod error;
+pub mod event;
 pub mod execute;
 pub mod helpers;
+pub mod hook;
 pub mod msg;
+pub mod permit;
 pub mod query;
 pub mod receiver;
 pub mod state;
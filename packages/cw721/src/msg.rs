@@ -1,20 +1,31 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Binary, Coin};
+use cosmwasm_std::{Addr, Binary, Coin, Order};
 use cw_ownable::{Action, Ownership};
 use cw_utils::Expiration;
 
-use crate::state::CollectionInfo;
+use crate::state::{
+    BaseUri, CollectionInfo, HookMode, NftLock, NftUser, PauseTarget, Placeholder, ProvenanceEntry,
+    TokenIdPolicy,
+};
 use crate::Approval;
 
 #[cw_serde]
-pub enum Cw721ExecuteMsg<TMetadataExtension, TMetadataExtensionMsg> {
+pub enum Cw721ExecuteMsg<TMetadataExtension, TMetadataExtensionMsg, TCollectionInfoExtension> {
+    /// Deprecated: use UpdateMinterOwnership instead! Updates the minter ownership.
     UpdateOwnership(Action),
+    /// Two-step ownership transfer of the minter, who is allowed to mint new NFTs.
+    UpdateMinterOwnership(Action),
+    /// Two-step ownership transfer of the creator, who owns the collection
+    /// and is allowed to update e.g. withdraw address.
+    UpdateCreatorOwnership(Action),
 
-    /// Transfer is a base message to move a token to another account without triggering actions
-    TransferNft {
-        recipient: String,
-        token_id: String,
+    /// Updates collection info and its extension. Only creator can call this.
+    UpdateCollectionInfo {
+        collection_info: CollectionInfoMsg<TCollectionInfoExtension>,
     },
+
+    /// Transfer is a base message to move a token to another account without triggering actions
+    TransferNft { recipient: String, token_id: String },
     /// Send is a base message to transfer a token to a contract and trigger an action
     /// on the receiving contract.
     SendNft {
@@ -30,10 +41,7 @@ pub enum Cw721ExecuteMsg<TMetadataExtension, TMetadataExtensionMsg> {
         expires: Option<Expiration>,
     },
     /// Remove previously granted Approval
-    Revoke {
-        spender: String,
-        token_id: String,
-    },
+    Revoke { spender: String, token_id: String },
     /// Allows operator to transfer / send any token from the owner's account.
     /// If expiration is set, then this allowance has a time/height limit
     ApproveAll {
@@ -41,9 +49,7 @@ pub enum Cw721ExecuteMsg<TMetadataExtension, TMetadataExtensionMsg> {
         expires: Option<Expiration>,
     },
     /// Remove previously granted ApproveAll permission
-    RevokeAll {
-        operator: String,
-    },
+    RevokeAll { operator: String },
 
     /// Mint a new NFT, can only be called by the contract minter
     Mint {
@@ -59,35 +65,149 @@ pub enum Cw721ExecuteMsg<TMetadataExtension, TMetadataExtensionMsg> {
         extension: TMetadataExtension,
     },
 
+    /// Mint a new NFT with the next sequential token id, can only be called by the contract minter
+    MintNext {
+        /// The owner of the newly minted NFT
+        owner: String,
+        /// Universal resource identifier for this NFT
+        token_uri: Option<String>,
+        /// Any custom extension used by this contract
+        extension: TMetadataExtension,
+    },
+
     /// Burn an NFT the sender has access to
-    Burn {
+    Burn { token_id: String },
+
+    /// Mints multiple NFTs at once, can only be called by the contract minter.
+    /// Either all NFTs are minted or none.
+    BatchMint {
+        mints: Vec<MintMsg<TMetadataExtension>>,
+    },
+    /// Transfers multiple NFTs to the same recipient, fails in case any of them can not be transferred.
+    BatchTransferNft {
+        recipient: String,
+        token_ids: Vec<String>,
+    },
+    /// Approves spender for multiple NFTs, fails in case any of them can not be approved.
+    BatchApprove {
+        spender: String,
+        token_ids: Vec<String>,
+        expires: Option<Expiration>,
+    },
+    /// Removes previously granted approvals for multiple NFTs.
+    BatchRevoke {
+        spender: String,
+        token_ids: Vec<String>,
+    },
+    /// Burns multiple NFTs, fails in case any of them can not be burned.
+    BatchBurn { token_ids: Vec<String> },
+    /// Removes expired approvals of the given NFTs and expired operators of the given owners,
+    /// unknown NFTs (e.g. burned in the meantime) are skipped.
+    /// Anyone can call this. At most `limit` entries are removed, which defaults to and is
+    /// capped by the max batch size.
+    PruneExpired {
+        token_ids: Vec<String>,
+        owners: Vec<String>,
+        limit: Option<u32>,
+    },
+
+    /// Updates metadata of an NFT, only creator can call this.
+    /// Fields set to `None` are left unchanged, an empty `token_uri` removes it.
+    UpdateNftInfo {
         token_id: String,
+        token_uri: Option<String>,
+        extension: Option<TMetadataExtension>,
+    },
+    /// Freezes metadata of a single NFT, or of all NFTs in case `token_id` is not set.
+    /// A single NFT without token uri gets the uri derived from the base uri, so later
+    /// base uri updates do not affect it. Only creator can call this. Freezing can not be undone!
+    FreezeNftInfo { token_id: Option<String> },
+    /// Sets the base uri used for deriving the token uri of NFTs without one, or removes it in case
+    /// `base_uri` is not set. Only creator can call this, until metadata of the whole collection is frozen.
+    UpdateBaseUri { base_uri: Option<BaseUri> },
+    /// Hides metadata of all NFTs behind a placeholder until `Reveal` is called or `reveal_at`
+    /// (defaults to never) expires. Only creator can call this, until metadata of the whole
+    /// collection is frozen.
+    SetPlaceholder {
+        token_uri: Option<String>,
+        extension: TMetadataExtension,
+        reveal_at: Option<Expiration>,
+    },
+    /// Reveals metadata of all NFTs and, if given, sets the base uri. Only creator can call this.
+    Reveal { base_uri: Option<BaseUri> },
+    /// Sets the maximum number of provenance entries kept per token, oldest entries are removed first.
+    /// Provenance is not recorded in case `max_entries` is not set or 0. Only creator can call this.
+    UpdateProvenanceCap { max_entries: Option<u32> },
+
+    /// Pauses the target (defaults to transfers) until `expires` (defaults to never).
+    /// Only creator can call this.
+    Pause {
+        target: Option<PauseTarget>,
+        expires: Option<Expiration>,
     },
+    /// Unpauses the target (defaults to transfers). Only creator can call this.
+    Unpause { target: Option<PauseTarget> },
 
-    /// Extension msg
-    Extension {
-        msg: TMetadataExtensionMsg,
+    /// Locks an NFT until `until`, so it can neither be transferred, sent nor burned.
+    /// Can be called by the owner or an operator of the owner. A locked NFT can not be locked again.
+    LockNft { token_id: String, until: Expiration },
+    /// Removes the lock of an NFT, only the locker can call this. The owner can also remove it once
+    /// the lock is expired.
+    UnlockNft { token_id: String },
+
+    /// Grants `user` the right to use an NFT until `expires` (defaults to never), without transferring
+    /// ownership, or removes the user in case `user` is not set. Can be called by the owner or anyone
+    /// allowed to send the NFT. The user is removed on transfer and burn.
+    SetUser {
+        token_id: String,
+        user: Option<String>,
+        expires: Option<Expiration>,
     },
 
-    /// Sets address to send withdrawn fees to. Only owner can call this.
-    SetWithdrawAddress {
-        address: String,
+    /// Approves `spender` for a token, or as operator for all tokens in case `token_id` is not set,
+    /// on behalf of the owner. The owner signs `PermitData` off-chain as ADR-036 arbitrary data,
+    /// so anyone can submit the permit. `nonce` must match the `PermitNonce` of the owner.
+    Permit {
+        /// Compressed secp256k1 public key of the owner (33 bytes)
+        owner_pubkey: Binary,
+        token_id: Option<String>,
+        spender: String,
+        expires: Option<Expiration>,
+        nonce: u64,
+        signature: Binary,
     },
-    /// Removes the withdraw address, so fees are sent to the contract. Only owner can call this.
+
+    /// Registers a contract receiving a `Cw721HookMsg` on mint, transfer, send and burn,
+    /// or updates its mode. Only creator can call this, at most `MAX_HOOKS` can be registered.
+    AddHook { addr: String, mode: HookMode },
+    /// Unregisters a hook contract. Only creator can call this.
+    RemoveHook { addr: String },
+
+    /// Extension msg, for contracts extending cw721 by overriding `update_metadata_extension`.
+    /// Rejected by default, use `UpdateNftInfo` for updating metadata of an NFT.
+    Extension { msg: TMetadataExtensionMsg },
+
+    /// Sets address to send withdrawn fees to. Only creator can call this.
+    SetWithdrawAddress { address: String },
+    /// Removes the withdraw address, so fees are sent to the contract. Only creator can call this.
     RemoveWithdrawAddress {},
     /// Withdraw from the contract to the given address. Anyone can call this,
     /// which is okay since withdraw address has been set by owner.
-    WithdrawFunds {
-        amount: Coin,
-    },
+    WithdrawFunds { amount: Coin },
 }
 
 #[cw_serde]
-pub struct Cw721InstantiateMsg {
+pub struct Cw721InstantiateMsg<TCollectionInfoExtension> {
     /// Name of the NFT contract
     pub name: String,
     /// Symbol of the NFT contract
     pub symbol: String,
+    /// Optional extension of the collection metadata
+    pub collection_info_extension: TCollectionInfoExtension,
+
+    /// The creator owns the collection and is allowed to e.g. set the withdraw address.
+    /// If not set, the sender of the instantiate message is the creator.
+    pub creator: Option<String>,
 
     /// The minter is the only one who can create new NFTs.
     /// This is designed for a base NFT that is controlled by an external program
@@ -95,11 +215,43 @@ pub struct Cw721InstantiateMsg {
     pub minter: Option<String>,
 
     pub withdraw_address: Option<String>,
+
+    /// Maximum number of items in batch messages, defaults to `DEFAULT_MAX_BATCH_SIZE`.
+    pub max_batch_size: Option<u32>,
+
+    /// Maximum number of tokens ever minted, including burned ones, unlimited if not set.
+    pub max_supply: Option<u64>,
+
+    /// Validation of token ids on mint, defaults to `TokenIdPolicy::default()`.
+    pub token_id_policy: Option<TokenIdPolicy>,
+
+    /// Allows minting ids of burned tokens again, defaults to false.
+    pub allow_remint: Option<bool>,
+
+    /// Used for deriving the token uri of tokens minted without one.
+    pub base_uri: Option<BaseUri>,
+
+    /// Records ownership changes for `OwnerOfAtHeight` and `BalanceOfAtHeight`, defaults to false.
+    pub enable_snapshots: Option<bool>,
+}
+
+#[cw_serde]
+pub struct MintMsg<TMetadataExtension> {
+    /// Unique ID of the NFT
+    pub token_id: String,
+    /// The owner of the newly minted NFT
+    pub owner: String,
+    /// Universal resource identifier for this NFT
+    /// Should point to a JSON file that conforms to the ERC721
+    /// Metadata JSON Schema
+    pub token_uri: Option<String>,
+    /// Any custom extension used by this contract
+    pub extension: TMetadataExtension,
 }
 
 #[cw_serde]
 #[derive(QueryResponses)]
-pub enum Cw721QueryMsg<TMetadataExtension> {
+pub enum Cw721QueryMsg<TMetadataExtension, TCollectionInfoExtension> {
     /// Return the owner of the given token, error if token does not exist
     #[returns(OwnerOfResponse)]
     OwnerOf {
@@ -136,16 +288,73 @@ pub enum Cw721QueryMsg<TMetadataExtension> {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    /// List all owners that granted the operator access to all of their tokens
+    #[returns(OperatorGrantsResponse)]
+    OperatorGrants {
+        operator: String,
+        /// unset or false will filter out expired items, you must set to true to see them
+        include_expired: Option<bool>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// List all tokens the spender is approved for
+    #[returns(ApprovedTokensResponse)]
+    ApprovedTokens {
+        spender: String,
+        /// unset or false will filter out expired approvals, you must set to true to see them
+        include_expired: Option<bool>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     /// Total number of tokens issued
     #[returns(NumTokensResponse)]
     NumTokens {},
+    /// Number of tokens owned by the given address
+    #[returns(BalanceOfResponse)]
+    BalanceOf { owner: String },
+    /// Return the owner of the given token at the beginning of the given block, `None` if it did
+    /// not exist. Error if snapshots are not enabled or height is before they have been enabled.
+    #[returns(OwnerOfAtHeightResponse)]
+    OwnerOfAtHeight { token_id: String, height: u64 },
+    /// Number of tokens owned by the given address at the beginning of the given block.
+    /// Error if snapshots are not enabled or height is before they have been enabled.
+    #[returns(BalanceOfResponse)]
+    BalanceOfAtHeight { owner: String, height: u64 },
+    /// Number of tokens, max supply and the token id used by the next `MintNext`
+    #[returns(SupplyResponse)]
+    Supply {},
+    /// Number of tokens ever minted and burned, `NumTokens` is the difference of both
+    #[returns(TotalMintedResponse)]
+    TotalMinted {},
+    /// Returns true in case the token has been burned
+    #[returns(bool)]
+    IsBurned { token_id: String },
+    /// List all ids of burned tokens
+    #[returns(TokensResponse)]
+    BurnedTokens {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 
     #[returns(CollectionInfo)]
     ContractInfo {},
 
+    /// Returns collection info including its extension
+    #[returns(CollectionInfoAndExtensionResponse<TCollectionInfoExtension>)]
+    GetCollectionInfo {},
+
+    /// Deprecated: use GetMinterOwnership instead!
     #[returns(Ownership<Addr>)]
     Ownership {},
 
+    /// Return the minter ownership, minter is allowed to mint new NFTs
+    #[returns(Ownership<Addr>)]
+    GetMinterOwnership {},
+
+    /// Return the creator ownership, creator owns the collection
+    #[returns(Ownership<Addr>)]
+    GetCreatorOwnership {},
+
     /// With MetaData Extension.
     /// Returns metadata about one particular token, based on *ERC721 Metadata JSON Schema*
     /// but directly from the contract
@@ -160,6 +369,88 @@ pub enum Cw721QueryMsg<TMetadataExtension> {
         /// unset or false will filter out expired approvals, you must set to true to see them
         include_expired: Option<bool>,
     },
+    /// With MetaData Extension.
+    /// Requires pagination. Returns the result of `AllNftInfo` for all tokens of the contract.
+    #[returns(NftInfosResponse<TMetadataExtension>)]
+    AllNftInfos {
+        start_after: Option<String>,
+        limit: Option<u32>,
+        /// unset or false will filter out expired approvals, you must set to true to see them
+        include_expired: Option<bool>,
+    },
+    /// With MetaData Extension.
+    /// Requires pagination. Returns the result of `AllNftInfo` for all tokens of the given owner.
+    #[returns(NftInfosResponse<TMetadataExtension>)]
+    NftInfosByOwner {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        /// unset or false will filter out expired approvals, you must set to true to see them
+        include_expired: Option<bool>,
+    },
+    /// Returns true in case metadata is frozen for the given token or, if no `token_id` is set,
+    /// for the whole collection.
+    #[returns(bool)]
+    IsNftInfoFrozen { token_id: Option<String> },
+
+    /// Returns the base uri used for deriving token uris, `None` if not set.
+    #[returns(Option<BaseUri>)]
+    BaseUri {},
+
+    /// Returns the metadata returned for all tokens until revealed, `None` if revealed.
+    #[returns(Option<Placeholder<TMetadataExtension>>)]
+    Placeholder {},
+
+    /// Returns until when transfers and minting are paused, `None` if not paused.
+    #[returns(PauseStatusResponse)]
+    PauseStatus {},
+
+    /// Returns the lock of a token, `None` if not locked
+    #[returns(NftLockResponse)]
+    NftLock {
+        token_id: String,
+        /// unset or false will filter out expired locks, you must set to true to see them
+        include_expired: Option<bool>,
+    },
+    /// List all locked tokens of the given owner
+    #[returns(NftLocksResponse)]
+    NftLocks {
+        owner: String,
+        /// unset or false will filter out expired locks, you must set to true to see them
+        include_expired: Option<bool>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Returns the nonce expected by the next `Permit` of the given owner
+    #[returns(u64)]
+    PermitNonce { owner: String },
+
+    /// Returns the user of a token, `None` if not set or expired
+    #[returns(UserOfResponse)]
+    UserOf { token_id: String },
+    /// List all tokens the given address is a non-expired user of
+    #[returns(TokensResponse)]
+    TokensOfUser {
+        user: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Returns the recorded mints, transfers, sends and burns of a token, oldest first
+    #[returns(TokenHistoryResponse)]
+    TokenHistory {
+        token_id: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    /// Returns all registered hook contracts
+    #[returns(HooksResponse)]
+    Hooks {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 
     /// With Enumerable extension.
     /// Returns all tokens owned by the given address, [] if unset.
@@ -167,14 +458,22 @@ pub enum Cw721QueryMsg<TMetadataExtension> {
     Tokens {
         owner: String,
         start_after: Option<String>,
+        /// Lists tokens up to, but excluding, this token id
+        end_before: Option<String>,
         limit: Option<u32>,
+        /// Defaults to ascending order
+        order: Option<OrderBy>,
     },
     /// With Enumerable extension.
     /// Requires pagination. Lists all token_ids controlled by the contract.
     #[returns(TokensResponse)]
     AllTokens {
         start_after: Option<String>,
+        /// Lists tokens up to, but excluding, this token id
+        end_before: Option<String>,
         limit: Option<u32>,
+        /// Defaults to ascending order
+        order: Option<OrderBy>,
     },
 
     /// Return the minter
@@ -190,6 +489,9 @@ pub enum Cw721QueryMsg<TMetadataExtension> {
     /// Do not use - dummy extension query, needed for inferring type parameter during compile
     #[returns(())]
     Extension { msg: TMetadataExtension },
+    /// Do not use - dummy collection info extension query, needed for inferring type parameter during compile
+    #[returns(())]
+    GetCollectionInfoExtension { msg: TCollectionInfoExtension },
 }
 
 #[cw_serde]
@@ -197,13 +499,44 @@ pub enum Cw721MigrateMsg {
     WithUpdate {
         minter: Option<String>,
         creator: Option<String>,
+        /// Enables snapshots for `OwnerOfAtHeight` and `BalanceOfAtHeight`, in case they are not
+        /// enabled yet. Owners and balances of all tokens are snapshotted once at migration height.
+        enable_snapshots: Option<bool>,
     },
 }
 
 #[cw_serde]
-pub struct CollectionInfoMsg {
+pub struct CollectionInfoMsg<TCollectionInfoExtension> {
+    pub name: String,
+    pub symbol: String,
+    pub extension: TCollectionInfoExtension,
+}
+
+#[cw_serde]
+pub struct Hook {
+    pub addr: Addr,
+    pub mode: HookMode,
+}
+
+#[cw_serde]
+pub struct HooksResponse {
+    pub hooks: Vec<Hook>,
+    /// Set to the last hook address in case there are more pages, to be used as `start_after`
+    /// of the next query.
+    pub next: Option<String>,
+}
+
+#[cw_serde]
+pub struct PauseStatusResponse {
+    pub transfers: Option<Expiration>,
+    pub minting: Option<Expiration>,
+}
+
+#[cw_serde]
+pub struct CollectionInfoAndExtensionResponse<TCollectionInfoExtension> {
     pub name: String,
     pub symbol: String,
+    pub extension: TCollectionInfoExtension,
 }
 
 #[cw_serde]
@@ -214,6 +547,12 @@ pub struct OwnerOfResponse {
     pub approvals: Vec<Approval>,
 }
 
+#[cw_serde]
+pub struct OwnerOfAtHeightResponse {
+    /// Owner of the token, `None` if it did not exist at that height
+    pub owner: Option<String>,
+}
+
 #[cw_serde]
 pub struct ApprovalResponse {
     pub approval: Approval,
@@ -234,11 +573,90 @@ pub struct OperatorsResponse {
     pub operators: Vec<Approval>,
 }
 
+#[cw_serde]
+pub struct OperatorGrant {
+    pub granter: Addr,
+    pub expires: Expiration,
+}
+
+#[cw_serde]
+pub struct OperatorGrantsResponse {
+    pub grants: Vec<OperatorGrant>,
+    /// Set to the last granter in case there are more pages, to be used as `start_after`
+    /// of the next query.
+    pub next: Option<String>,
+}
+
+#[cw_serde]
+pub struct ApprovedToken {
+    pub token_id: String,
+    pub expires: Expiration,
+}
+
+#[cw_serde]
+pub struct ApprovedTokensResponse {
+    pub tokens: Vec<ApprovedToken>,
+    /// Set to the last token id in case there are more pages, to be used as `start_after`
+    /// of the next query.
+    pub next: Option<String>,
+}
+
+#[cw_serde]
+pub struct UserOfResponse {
+    pub user: Option<NftUser>,
+}
+
+#[cw_serde]
+pub struct NftLockResponse {
+    pub lock: Option<NftLock>,
+}
+
+#[cw_serde]
+pub struct LockedToken {
+    pub token_id: String,
+    pub locker: Addr,
+    pub until: Expiration,
+}
+
+#[cw_serde]
+pub struct NftLocksResponse {
+    pub locks: Vec<LockedToken>,
+    /// Set to the last token id in case there are more pages, to be used as `start_after`
+    /// of the next query.
+    pub next: Option<String>,
+}
+
+#[cw_serde]
+pub struct TokenHistoryResponse {
+    pub entries: Vec<ProvenanceEntry>,
+    /// Set to the id of the last entry in case there are more pages, to be used as `start_after`
+    /// of the next query.
+    pub next: Option<u64>,
+}
+
 #[cw_serde]
 pub struct NumTokensResponse {
     pub count: u64,
 }
 
+#[cw_serde]
+pub struct BalanceOfResponse {
+    pub balance: u64,
+}
+
+#[cw_serde]
+pub struct SupplyResponse {
+    pub num_tokens: u64,
+    pub max_supply: Option<u64>,
+    pub next_token_id: u64,
+}
+
+#[cw_serde]
+pub struct TotalMintedResponse {
+    pub total_minted: u64,
+    pub total_burned: u64,
+}
+
 #[cw_serde]
 pub struct NftInfoResponse<TMetadataExtension> {
     /// Universal resource identifier for this NFT
@@ -257,12 +675,49 @@ pub struct AllNftInfoResponse<TMetadataExtension> {
     pub info: NftInfoResponse<TMetadataExtension>,
 }
 
+#[cw_serde]
+pub struct NftInfoWithId<TMetadataExtension> {
+    pub token_id: String,
+    /// Who can transfer the token
+    pub access: OwnerOfResponse,
+    /// Data on the token itself,
+    pub info: NftInfoResponse<TMetadataExtension>,
+}
+
+#[cw_serde]
+pub struct NftInfosResponse<TMetadataExtension> {
+    /// Contains all tokens in lexicographical ordering of their token ids
+    pub nft_infos: Vec<NftInfoWithId<TMetadataExtension>>,
+    /// Set to the last token id in case there are more pages, to be used as `start_after`
+    /// of the next query.
+    pub next: Option<String>,
+}
+
 #[cw_serde]
 pub struct TokensResponse {
     /// Contains all token_ids in lexicographical ordering
     /// If there are more than `limit`, use `start_after` in future queries
     /// to achieve pagination.
     pub tokens: Vec<String>,
+    /// Set to the last token id in case there are more pages, to be used as `start_after`
+    /// of the next query.
+    pub next: Option<String>,
+}
+
+/// Order in which tokens are listed
+#[cw_serde]
+pub enum OrderBy {
+    Ascending,
+    Descending,
+}
+
+impl From<OrderBy> for Order {
+    fn from(order: OrderBy) -> Self {
+        match order {
+            OrderBy::Ascending => Order::Ascending,
+            OrderBy::Descending => Order::Descending,
+        }
+    }
 }
 
 /// Deprecated: use Cw721QueryMsg::GetMinterOwnership instead!
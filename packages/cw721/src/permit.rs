@@ -0,0 +1,66 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_json_vec, Addr, Api, Binary, CanonicalAddr, StdError, StdResult};
+use cw_utils::Expiration;
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+
+use crate::error::Cw721ContractError;
+
+/// Data signed by the owner for `Permit`. Chain id and contract prevent replaying the permit
+/// elsewhere, the nonce prevents replaying it on this contract.
+#[cw_serde]
+pub struct PermitData {
+    pub chain_id: String,
+    pub contract: String,
+    /// Approves the spender for this token, or as operator for all tokens if not set
+    pub token_id: Option<String>,
+    pub spender: String,
+    pub expires: Option<Expiration>,
+    pub nonce: u64,
+}
+
+/// Derives the address of a secp256k1 public key the same way the Cosmos SDK does,
+/// i.e. ripemd160(sha256(pubkey)) in the chain's address format.
+pub fn pubkey_to_addr(api: &dyn Api, pubkey: &[u8]) -> StdResult<Addr> {
+    let hash = Ripemd160::digest(Sha256::digest(pubkey));
+    api.addr_humanize(&CanonicalAddr::from(hash.as_slice()))
+}
+
+/// ADR-036 sign doc of arbitrary data, as created by wallets for `signArbitrary`.
+pub fn adr036_sign_doc(signer: &Addr, data: &[u8]) -> Vec<u8> {
+    format!(
+        r#"{{"account_number":"0","chain_id":"","fee":{{"amount":[],"gas":"0"}},"memo":"","msgs":[{{"type":"sign/MsgSignData","value":{{"data":"{}","signer":"{}"}}}}],"sequence":"0"}}"#,
+        Binary::from(data).to_base64(),
+        signer
+    )
+    .into_bytes()
+}
+
+/// Length of a compressed secp256k1 public key, as used for Cosmos SDK accounts.
+pub const PERMIT_PUBKEY_LENGTH: usize = 33;
+
+/// Verifies the ADR-036 signature of the permit data and returns the address of the signer.
+pub fn verify_permit(
+    api: &dyn Api,
+    pubkey: &[u8],
+    signature: &[u8],
+    data: &PermitData,
+) -> Result<Addr, Cw721ContractError> {
+    // address is derived from the compressed key, an uncompressed key of the same account would
+    // derive another address
+    if pubkey.len() != PERMIT_PUBKEY_LENGTH {
+        return Err(Cw721ContractError::InvalidPermitPubkey {
+            length: pubkey.len(),
+        });
+    }
+    let signer = pubkey_to_addr(api, pubkey)?;
+    let sign_doc = adr036_sign_doc(&signer, &to_json_vec(data)?);
+    let hash = Sha256::digest(sign_doc);
+    if !api
+        .secp256k1_verify(&hash, signature, pubkey)
+        .map_err(StdError::from)?
+    {
+        return Err(Cw721ContractError::InvalidPermitSignature {});
+    }
+    Ok(signer)
+}
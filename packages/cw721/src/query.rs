@@ -2,18 +2,25 @@ use cosmwasm_std::{
     to_json_binary, Addr, Binary, BlockInfo, Deps, Empty, Env, Order, StdError, StdResult, Storage,
 };
 use cw_ownable::Ownership;
-use cw_storage_plus::Bound;
+use cw_storage_plus::{Bound, PrimaryKey};
 use cw_utils::{maybe_addr, Expiration};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
 use crate::{
     msg::{
-        AllNftInfoResponse, ApprovalResponse, ApprovalsResponse, Cw721QueryMsg, MinterResponse,
-        NftInfoResponse, NumTokensResponse, OperatorResponse, OperatorsResponse, OwnerOfResponse,
-        TokensResponse,
+        AllNftInfoResponse, ApprovalResponse, ApprovalsResponse, ApprovedToken,
+        ApprovedTokensResponse, BalanceOfResponse, CollectionInfoAndExtensionResponse,
+        Cw721QueryMsg, Hook, HooksResponse, LockedToken, MinterResponse, NftInfoResponse,
+        NftInfoWithId, NftInfosResponse, NftLockResponse, NftLocksResponse, NumTokensResponse,
+        OperatorGrant, OperatorGrantsResponse, OperatorResponse, OperatorsResponse, OrderBy,
+        OwnerOfAtHeightResponse, OwnerOfResponse, PauseStatusResponse, SupplyResponse,
+        TokenHistoryResponse, TokensResponse, TotalMintedResponse, UserOfResponse,
+    },
+    state::{
+        Approval, BaseUri, CollectionInfo, Cw721Config, Cw721Storage, NftInfo, PauseTarget,
+        Placeholder,
     },
-    state::{Approval, CollectionInfo, Cw721Config, NftInfo, MINTER},
 };
 
 pub const DEFAULT_LIMIT: u32 = 10;
@@ -22,20 +29,32 @@ pub const MAX_LIMIT: u32 = 1000;
 pub trait Cw721Query<
     // Metadata defined in NftInfo.
     TMetadataExtension,
+    // Extension defined in CollectionInfo.
+    TCollectionInfoExtension,
 > where
+    Self: Cw721Storage,
     TMetadataExtension: Serialize + DeserializeOwned + Clone,
+    TCollectionInfoExtension: Serialize + DeserializeOwned + Clone + Default,
 {
+    /// Config using the stores of `storage_keys`.
+    fn config(&self) -> Cw721Config<TMetadataExtension, Empty, Empty, TCollectionInfoExtension> {
+        Cw721Config::new(self.storage_keys())
+    }
+
     fn query(
         &self,
         deps: Deps,
         env: Env,
-        msg: Cw721QueryMsg<TMetadataExtension>,
+        msg: Cw721QueryMsg<TMetadataExtension, TCollectionInfoExtension>,
     ) -> StdResult<Binary> {
         match msg {
             Cw721QueryMsg::Minter {} => to_json_binary(&self.query_minter(deps.storage)?),
             Cw721QueryMsg::ContractInfo {} => {
                 to_json_binary(&self.query_collection_info(deps, env)?)
             }
+            Cw721QueryMsg::GetCollectionInfo {} => {
+                to_json_binary(&self.query_collection_info_and_extension(deps)?)
+            }
             Cw721QueryMsg::NftInfo { token_id } => {
                 to_json_binary(&self.query_nft_info(deps, env, token_id)?)
             }
@@ -48,6 +67,59 @@ pub trait Cw721Query<
                 token_id,
                 include_expired.unwrap_or(false),
             )?),
+            Cw721QueryMsg::BaseUri {} => to_json_binary(&self.query_base_uri(deps)?),
+            Cw721QueryMsg::Placeholder {} => to_json_binary(&self.query_placeholder(deps, env)?),
+            Cw721QueryMsg::IsNftInfoFrozen { token_id } => {
+                to_json_binary(&self.query_is_nft_info_frozen(deps, token_id)?)
+            }
+            Cw721QueryMsg::PauseStatus {} => to_json_binary(&self.query_pause_status(deps, env)?),
+            Cw721QueryMsg::NftLock {
+                token_id,
+                include_expired,
+            } => to_json_binary(&self.query_nft_lock(
+                deps,
+                env,
+                token_id,
+                include_expired.unwrap_or(false),
+            )?),
+            Cw721QueryMsg::NftLocks {
+                owner,
+                include_expired,
+                start_after,
+                limit,
+            } => to_json_binary(&self.query_nft_locks(
+                deps,
+                env,
+                owner,
+                include_expired.unwrap_or(false),
+                start_after,
+                limit,
+            )?),
+            Cw721QueryMsg::PermitNonce { owner } => {
+                to_json_binary(&self.query_permit_nonce(deps, env, owner)?)
+            }
+            Cw721QueryMsg::UserOf { token_id } => {
+                to_json_binary(&self.query_user_of(deps, env, token_id)?)
+            }
+            Cw721QueryMsg::TokensOfUser {
+                user,
+                start_after,
+                limit,
+            } => to_json_binary(&self.query_tokens_of_user(deps, env, user, start_after, limit)?),
+            Cw721QueryMsg::TokenHistory {
+                token_id,
+                start_after,
+                limit,
+            } => to_json_binary(&self.query_token_history(
+                deps,
+                env,
+                token_id,
+                start_after,
+                limit,
+            )?),
+            Cw721QueryMsg::Hooks { start_after, limit } => {
+                to_json_binary(&self.query_hooks(deps, env, start_after, limit)?)
+            }
             Cw721QueryMsg::AllNftInfo {
                 token_id,
                 include_expired,
@@ -57,6 +129,30 @@ pub trait Cw721Query<
                 token_id,
                 include_expired.unwrap_or(false),
             )?),
+            Cw721QueryMsg::AllNftInfos {
+                start_after,
+                limit,
+                include_expired,
+            } => to_json_binary(&self.query_all_nft_infos(
+                deps,
+                env,
+                start_after,
+                limit,
+                include_expired.unwrap_or(false),
+            )?),
+            Cw721QueryMsg::NftInfosByOwner {
+                owner,
+                start_after,
+                limit,
+                include_expired,
+            } => to_json_binary(&self.query_nft_infos_by_owner(
+                deps,
+                env,
+                owner,
+                start_after,
+                limit,
+                include_expired.unwrap_or(false),
+            )?),
             Cw721QueryMsg::Operator {
                 owner,
                 operator,
@@ -81,15 +177,78 @@ pub trait Cw721Query<
                 start_after,
                 limit,
             )?),
+            Cw721QueryMsg::OperatorGrants {
+                operator,
+                include_expired,
+                start_after,
+                limit,
+            } => to_json_binary(&self.query_operator_grants(
+                deps,
+                env,
+                operator,
+                include_expired.unwrap_or(false),
+                start_after,
+                limit,
+            )?),
+            Cw721QueryMsg::ApprovedTokens {
+                spender,
+                include_expired,
+                start_after,
+                limit,
+            } => to_json_binary(&self.query_approved_tokens(
+                deps,
+                env,
+                spender,
+                include_expired.unwrap_or(false),
+                start_after,
+                limit,
+            )?),
             Cw721QueryMsg::NumTokens {} => to_json_binary(&self.query_num_tokens(deps, env)?),
+            Cw721QueryMsg::BalanceOf { owner } => {
+                to_json_binary(&self.query_balance_of(deps, env, owner)?)
+            }
+            Cw721QueryMsg::OwnerOfAtHeight { token_id, height } => {
+                to_json_binary(&self.query_owner_of_at_height(deps, env, token_id, height)?)
+            }
+            Cw721QueryMsg::BalanceOfAtHeight { owner, height } => {
+                to_json_binary(&self.query_balance_of_at_height(deps, env, owner, height)?)
+            }
+            Cw721QueryMsg::Supply {} => to_json_binary(&self.query_supply(deps, env)?),
+            Cw721QueryMsg::TotalMinted {} => to_json_binary(&self.query_total_minted(deps, env)?),
+            Cw721QueryMsg::IsBurned { token_id } => {
+                to_json_binary(&self.query_is_burned(deps, env, token_id)?)
+            }
+            Cw721QueryMsg::BurnedTokens { start_after, limit } => {
+                to_json_binary(&self.query_burned_tokens(deps, env, start_after, limit)?)
+            }
             Cw721QueryMsg::Tokens {
                 owner,
                 start_after,
+                end_before,
                 limit,
-            } => to_json_binary(&self.query_tokens(deps, env, owner, start_after, limit)?),
-            Cw721QueryMsg::AllTokens { start_after, limit } => {
-                to_json_binary(&self.query_all_tokens(deps, env, start_after, limit)?)
-            }
+                order,
+            } => to_json_binary(&self.query_tokens(
+                deps,
+                env,
+                owner,
+                start_after,
+                end_before,
+                limit,
+                order,
+            )?),
+            Cw721QueryMsg::AllTokens {
+                start_after,
+                end_before,
+                limit,
+                order,
+            } => to_json_binary(&self.query_all_tokens(
+                deps,
+                env,
+                start_after,
+                end_before,
+                limit,
+                order,
+            )?),
             Cw721QueryMsg::Approval {
                 token_id,
                 spender,
@@ -110,12 +269,18 @@ pub trait Cw721Query<
                 token_id,
                 include_expired.unwrap_or(false),
             )?),
-            Cw721QueryMsg::Ownership {} => {
+            Cw721QueryMsg::Ownership {} | Cw721QueryMsg::GetMinterOwnership {} => {
                 to_json_binary(&self.query_minter_ownership(deps.storage)?)
             }
+            Cw721QueryMsg::GetCreatorOwnership {} => {
+                to_json_binary(&self.query_creator_ownership(deps.storage)?)
+            }
             Cw721QueryMsg::Extension { msg } => {
                 to_json_binary(&self.query_extension(deps, env, msg)?)
             }
+            Cw721QueryMsg::GetCollectionInfoExtension { msg } => {
+                to_json_binary(&self.query_collection_info_extension(deps, env, msg)?)
+            }
             Cw721QueryMsg::GetWithdrawAddress {} => {
                 to_json_binary(&self.query_withdraw_address(deps)?)
             }
@@ -123,7 +288,9 @@ pub trait Cw721Query<
     }
 
     fn query_minter(&self, storage: &dyn Storage) -> StdResult<MinterResponse> {
-        let minter = MINTER
+        let minter = self
+            .config()
+            .minter
             .get_ownership(storage)?
             .owner
             .map(|a| a.into_string());
@@ -132,36 +299,148 @@ pub trait Cw721Query<
     }
 
     fn query_minter_ownership(&self, storage: &dyn Storage) -> StdResult<Ownership<Addr>> {
-        MINTER.get_ownership(storage)
+        self.config().minter.get_ownership(storage)
+    }
+
+    fn query_creator_ownership(&self, storage: &dyn Storage) -> StdResult<Ownership<Addr>> {
+        self.config().creator.get_ownership(storage)
     }
 
     fn query_collection_info(&self, deps: Deps, _env: Env) -> StdResult<CollectionInfo> {
-        Cw721Config::<TMetadataExtension, Empty, Empty>::default()
-            .collection_info
-            .load(deps.storage)
+        self.config().collection_info.load(deps.storage)
+    }
+
+    /// Collection info extension is optional in storage, e.g. for contracts migrated from
+    /// older versions, so default extension is returned in case it is not set.
+    fn query_collection_info_and_extension(
+        &self,
+        deps: Deps,
+    ) -> StdResult<CollectionInfoAndExtensionResponse<TCollectionInfoExtension>> {
+        let config = self.config();
+        let collection_info = config.collection_info.load(deps.storage)?;
+        let extension = config
+            .collection_info_extension
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+        Ok(CollectionInfoAndExtensionResponse {
+            name: collection_info.name,
+            symbol: collection_info.symbol,
+            extension,
+        })
     }
 
     fn query_num_tokens(&self, deps: Deps, _env: Env) -> StdResult<NumTokensResponse> {
-        let count =
-            Cw721Config::<TMetadataExtension, Empty, Empty>::default().token_count(deps.storage)?;
+        let count = self.config().token_count(deps.storage)?;
         Ok(NumTokensResponse { count })
     }
 
-    fn query_nft_info(
+    fn query_supply(&self, deps: Deps, _env: Env) -> StdResult<SupplyResponse> {
+        let config = self.config();
+        Ok(SupplyResponse {
+            num_tokens: config.token_count(deps.storage)?,
+            max_supply: config.max_supply.may_load(deps.storage)?,
+            next_token_id: config.next_token_id(deps.storage)?,
+        })
+    }
+
+    fn query_total_minted(&self, deps: Deps, _env: Env) -> StdResult<TotalMintedResponse> {
+        let config = self.config();
+        let total_minted = config.total_minted(deps.storage)?;
+        Ok(TotalMintedResponse {
+            total_minted,
+            total_burned: total_minted.saturating_sub(config.token_count(deps.storage)?),
+        })
+    }
+
+    fn query_is_burned(&self, deps: Deps, _env: Env, token_id: String) -> StdResult<bool> {
+        Ok(self.config().burned_tokens.has(deps.storage, &token_id))
+    }
+
+    fn query_burned_tokens(
+        &self,
+        deps: Deps,
+        _env: Env,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<TokensResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
+
+        let tokens: Vec<String> = self
+            .config()
+            .burned_tokens
+            .keys(deps.storage, start, None, Order::Ascending)
+            .take(limit + 1)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        let (tokens, next) = paginate(tokens, limit, String::clone);
+        Ok(TokensResponse { tokens, next })
+    }
+
+    fn query_balance_of(
+        &self,
+        deps: Deps,
+        _env: Env,
+        owner: String,
+    ) -> StdResult<BalanceOfResponse> {
+        let owner_addr = deps.api.addr_validate(&owner)?;
+        let balance = self.config().balance(deps.storage, &owner_addr)?;
+        Ok(BalanceOfResponse { balance })
+    }
+
+    fn query_owner_of_at_height(
         &self,
         deps: Deps,
         _env: Env,
         token_id: String,
-    ) -> StdResult<NftInfoResponse<TMetadataExtension>> {
-        let info = Cw721Config::<TMetadataExtension, Empty, Empty>::default()
-            .nft_info
-            .load(deps.storage, &token_id)?;
-        Ok(NftInfoResponse {
-            token_uri: info.token_uri,
-            extension: info.extension,
+        height: u64,
+    ) -> StdResult<OwnerOfAtHeightResponse> {
+        let config = self.config();
+        config.assert_snapshot_available(deps.storage, height)?;
+        let owner = config
+            .owner_snapshots
+            .may_load_at_height(deps.storage, &token_id, height)?;
+        Ok(OwnerOfAtHeightResponse {
+            owner: owner.map(|owner| owner.to_string()),
         })
     }
 
+    fn query_balance_of_at_height(
+        &self,
+        deps: Deps,
+        _env: Env,
+        owner: String,
+        height: u64,
+    ) -> StdResult<BalanceOfResponse> {
+        let owner_addr = deps.api.addr_validate(&owner)?;
+        let config = self.config();
+        config.assert_snapshot_available(deps.storage, height)?;
+        let balance = config
+            .balance_snapshots
+            .may_load_at_height(deps.storage, &owner_addr, height)?
+            .unwrap_or_default();
+        Ok(BalanceOfResponse { balance })
+    }
+
+    fn query_nft_info(
+        &self,
+        deps: Deps,
+        env: Env,
+        token_id: String,
+    ) -> StdResult<NftInfoResponse<TMetadataExtension>> {
+        let config = self.config();
+        let info = config.nft_info.load(deps.storage, &token_id)?;
+        let placeholder = config.placeholder(deps.storage, &env.block)?;
+        let base_uri = config.base_uri.may_load(deps.storage)?;
+        Ok(nft_info_response(
+            placeholder.as_ref(),
+            base_uri.as_ref(),
+            &token_id,
+            info.token_uri,
+            info.extension,
+        ))
+    }
+
     fn query_owner_of(
         &self,
         deps: Deps,
@@ -169,9 +448,7 @@ pub trait Cw721Query<
         token_id: String,
         include_expired_approval: bool,
     ) -> StdResult<OwnerOfResponse> {
-        let nft_info = Cw721Config::<TMetadataExtension, Empty, Empty>::default()
-            .nft_info
-            .load(deps.storage, &token_id)?;
+        let nft_info = self.config().nft_info.load(deps.storage, &token_id)?;
         Ok(OwnerOfResponse {
             owner: nft_info.owner.to_string(),
             approvals: humanize_approvals(&env.block, &nft_info, include_expired_approval),
@@ -190,7 +467,8 @@ pub trait Cw721Query<
         let owner_addr = deps.api.addr_validate(&owner)?;
         let operator_addr = deps.api.addr_validate(&operator)?;
 
-        let info = Cw721Config::<TMetadataExtension, Empty, Empty>::default()
+        let info = self
+            .config()
             .operators
             .may_load(deps.storage, (&owner_addr, &operator_addr))?;
 
@@ -225,14 +503,16 @@ pub trait Cw721Query<
         let start = start_addr.as_ref().map(Bound::exclusive);
 
         let owner_addr = deps.api.addr_validate(&owner)?;
-        let res: StdResult<Vec<_>> = Cw721Config::<TMetadataExtension, Empty, Empty>::default()
+        let res: StdResult<Vec<_>> = self
+            .config()
             .operators
             .prefix(&owner_addr)
             .range(deps.storage, start, None, Order::Ascending)
-            .filter(|r| {
-                include_expired_approval
-                    || r.is_err()
-                    || !r.as_ref().unwrap().1.is_expired(&env.block)
+            .filter_map(|item| match item {
+                Ok((_, expires)) if !include_expired_approval && expires.is_expired(&env.block) => {
+                    None
+                }
+                item => Some(item),
             })
             .take(limit)
             .map(parse_approval)
@@ -240,6 +520,70 @@ pub trait Cw721Query<
         Ok(OperatorsResponse { operators: res? })
     }
 
+    /// operator grants returns all owners that gave operator access to their tokens
+    fn query_operator_grants(
+        &self,
+        deps: Deps,
+        env: Env,
+        operator: String,
+        include_expired_approval: bool,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<OperatorGrantsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_addr = maybe_addr(deps.api, start_after)?;
+        let start = start_addr.as_ref().map(Bound::exclusive);
+
+        let operator_addr = deps.api.addr_validate(&operator)?;
+        let grants = self
+            .config()
+            .operator_grants
+            .prefix(&operator_addr)
+            .range(deps.storage, start, None, Order::Ascending)
+            .filter_map(|item| match item {
+                Ok((_, expires)) if !include_expired_approval && expires.is_expired(&env.block) => {
+                    None
+                }
+                item => Some(item),
+            })
+            .take(limit + 1)
+            .map(|item| item.map(|(granter, expires)| OperatorGrant { granter, expires }))
+            .collect::<StdResult<Vec<_>>>()?;
+        let (grants, next) = paginate(grants, limit, |grant| grant.granter.to_string());
+        Ok(OperatorGrantsResponse { grants, next })
+    }
+
+    fn query_approved_tokens(
+        &self,
+        deps: Deps,
+        env: Env,
+        spender: String,
+        include_expired_approval: bool,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<ApprovedTokensResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
+
+        let spender_addr = deps.api.addr_validate(&spender)?;
+        let tokens = self
+            .config()
+            .approved_tokens
+            .prefix(&spender_addr)
+            .range(deps.storage, start, None, Order::Ascending)
+            .filter_map(|item| match item {
+                Ok((_, expires)) if !include_expired_approval && expires.is_expired(&env.block) => {
+                    None
+                }
+                item => Some(item),
+            })
+            .take(limit + 1)
+            .map(|item| item.map(|(token_id, expires)| ApprovedToken { token_id, expires }))
+            .collect::<StdResult<Vec<_>>>()?;
+        let (tokens, next) = paginate(tokens, limit, |token| token.token_id.clone());
+        Ok(ApprovedTokensResponse { tokens, next })
+    }
+
     fn query_approval(
         &self,
         deps: Deps,
@@ -248,9 +592,7 @@ pub trait Cw721Query<
         spender: String,
         include_expired_approval: bool,
     ) -> StdResult<ApprovalResponse> {
-        let token = Cw721Config::<TMetadataExtension, Empty, Empty>::default()
-            .nft_info
-            .load(deps.storage, &token_id)?;
+        let token = self.config().nft_info.load(deps.storage, &token_id)?;
 
         // token owner has absolute approval
         if token.owner == spender {
@@ -289,9 +631,7 @@ pub trait Cw721Query<
         token_id: String,
         include_expired_approval: bool,
     ) -> StdResult<ApprovalsResponse> {
-        let token = Cw721Config::<TMetadataExtension, Empty, Empty>::default()
-            .nft_info
-            .load(deps.storage, &token_id)?;
+        let token = self.config().nft_info.load(deps.storage, &token_id)?;
         let approvals: Vec<_> = token
             .approvals
             .into_iter()
@@ -311,22 +651,27 @@ pub trait Cw721Query<
         _env: Env,
         owner: String,
         start_after: Option<String>,
+        end_before: Option<String>,
         limit: Option<u32>,
+        order: Option<OrderBy>,
     ) -> StdResult<TokensResponse> {
         let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-        let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
+        let order = order.map(Order::from).unwrap_or(Order::Ascending);
+        let (min, max) = range_bounds(start_after, end_before, order);
 
         let owner_addr = deps.api.addr_validate(&owner)?;
-        let tokens: Vec<String> = Cw721Config::<TMetadataExtension, Empty, Empty>::default()
+        let tokens: Vec<String> = self
+            .config()
             .nft_info
             .idx
             .owner
             .prefix(owner_addr)
-            .keys(deps.storage, start, None, Order::Ascending)
-            .take(limit)
+            .keys(deps.storage, min, max, order)
+            .take(limit + 1)
             .collect::<StdResult<Vec<_>>>()?;
 
-        Ok(TokensResponse { tokens })
+        let (tokens, next) = paginate(tokens, limit, String::clone);
+        Ok(TokensResponse { tokens, next })
     }
 
     fn query_all_tokens(
@@ -334,20 +679,100 @@ pub trait Cw721Query<
         deps: Deps,
         _env: Env,
         start_after: Option<String>,
+        end_before: Option<String>,
         limit: Option<u32>,
+        order: Option<OrderBy>,
     ) -> StdResult<TokensResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let order = order.map(Order::from).unwrap_or(Order::Ascending);
+        let (min, max) = range_bounds(start_after, end_before, order);
+
+        let tokens: Vec<String> = self
+            .config()
+            .nft_info
+            .keys(deps.storage, min, max, order)
+            .take(limit + 1)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        let (tokens, next) = paginate(tokens, limit, String::clone);
+        Ok(TokensResponse { tokens, next })
+    }
+
+    fn query_all_nft_infos(
+        &self,
+        deps: Deps,
+        env: Env,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        include_expired_approval: bool,
+    ) -> StdResult<NftInfosResponse<TMetadataExtension>> {
         let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
         let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
 
-        let tokens: StdResult<Vec<String>> =
-            Cw721Config::<TMetadataExtension, Empty, Empty>::default()
-                .nft_info
-                .range(deps.storage, start, None, Order::Ascending)
-                .take(limit)
-                .map(|item| item.map(|(k, _)| k))
-                .collect();
+        let config = self.config();
+        let placeholder = config.placeholder(deps.storage, &env.block)?;
+        let base_uri = config.base_uri.may_load(deps.storage)?;
+        let nft_infos = config
+            .nft_info
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit + 1)
+            .map(|item| {
+                item.map(|(token_id, nft_info)| {
+                    nft_info_with_id(
+                        &env.block,
+                        placeholder.as_ref(),
+                        base_uri.as_ref(),
+                        token_id,
+                        nft_info,
+                        include_expired_approval,
+                    )
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
 
-        Ok(TokensResponse { tokens: tokens? })
+        let (nft_infos, next) = paginate(nft_infos, limit, |nft_info| nft_info.token_id.clone());
+        Ok(NftInfosResponse { nft_infos, next })
+    }
+
+    fn query_nft_infos_by_owner(
+        &self,
+        deps: Deps,
+        env: Env,
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        include_expired_approval: bool,
+    ) -> StdResult<NftInfosResponse<TMetadataExtension>> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
+
+        let owner_addr = deps.api.addr_validate(&owner)?;
+        let config = self.config();
+        let placeholder = config.placeholder(deps.storage, &env.block)?;
+        let base_uri = config.base_uri.may_load(deps.storage)?;
+        let nft_infos = config
+            .nft_info
+            .idx
+            .owner
+            .prefix(owner_addr)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit + 1)
+            .map(|item| {
+                item.map(|(token_id, nft_info)| {
+                    nft_info_with_id(
+                        &env.block,
+                        placeholder.as_ref(),
+                        base_uri.as_ref(),
+                        token_id,
+                        nft_info,
+                        include_expired_approval,
+                    )
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        let (nft_infos, next) = paginate(nft_infos, limit, |nft_info| nft_info.token_id.clone());
+        Ok(NftInfosResponse { nft_infos, next })
     }
 
     fn query_all_nft_info(
@@ -357,21 +782,198 @@ pub trait Cw721Query<
         token_id: String,
         include_expired_approval: bool,
     ) -> StdResult<AllNftInfoResponse<TMetadataExtension>> {
-        let nft_info = Cw721Config::<TMetadataExtension, Empty, Empty>::default()
-            .nft_info
-            .load(deps.storage, &token_id)?;
+        let config = self.config();
+        let nft_info = config.nft_info.load(deps.storage, &token_id)?;
+        let placeholder = config.placeholder(deps.storage, &env.block)?;
+        let base_uri = config.base_uri.may_load(deps.storage)?;
         Ok(AllNftInfoResponse {
             access: OwnerOfResponse {
                 owner: nft_info.owner.to_string(),
                 approvals: humanize_approvals(&env.block, &nft_info, include_expired_approval),
             },
-            info: NftInfoResponse {
-                token_uri: nft_info.token_uri,
-                extension: nft_info.extension,
-            },
+            info: nft_info_response(
+                placeholder.as_ref(),
+                base_uri.as_ref(),
+                &token_id,
+                nft_info.token_uri,
+                nft_info.extension,
+            ),
+        })
+    }
+
+    fn query_is_nft_info_frozen(&self, deps: Deps, token_id: Option<String>) -> StdResult<bool> {
+        let config = self.config();
+        match token_id {
+            Some(token_id) => config.is_nft_info_frozen(deps.storage, &token_id),
+            None => Ok(config
+                .collection_nft_info_frozen
+                .may_load(deps.storage)?
+                .unwrap_or_default()),
+        }
+    }
+
+    fn query_base_uri(&self, deps: Deps) -> StdResult<Option<BaseUri>> {
+        self.config().base_uri.may_load(deps.storage)
+    }
+
+    fn query_placeholder(
+        &self,
+        deps: Deps,
+        env: Env,
+    ) -> StdResult<Option<Placeholder<TMetadataExtension>>> {
+        self.config().placeholder(deps.storage, &env.block)
+    }
+
+    fn query_pause_status(&self, deps: Deps, env: Env) -> StdResult<PauseStatusResponse> {
+        let config = self.config();
+        Ok(PauseStatusResponse {
+            transfers: config.pause_expiration(
+                deps.storage,
+                &env.block,
+                &PauseTarget::Transfers,
+            )?,
+            minting: config.pause_expiration(deps.storage, &env.block, &PauseTarget::Minting)?,
         })
     }
 
+    fn query_nft_lock(
+        &self,
+        deps: Deps,
+        env: Env,
+        token_id: String,
+        include_expired_lock: bool,
+    ) -> StdResult<NftLockResponse> {
+        let config = self.config();
+        let token = config.nft_info.load(deps.storage, &token_id)?;
+        let lock = config
+            .nft_locks
+            .may_load(deps.storage, (&token.owner, &token_id))?
+            .filter(|lock| include_expired_lock || !lock.is_expired(&env.block));
+        Ok(NftLockResponse { lock })
+    }
+
+    fn query_nft_locks(
+        &self,
+        deps: Deps,
+        env: Env,
+        owner: String,
+        include_expired_lock: bool,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<NftLocksResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
+
+        let owner_addr = deps.api.addr_validate(&owner)?;
+        let locks = self
+            .config()
+            .nft_locks
+            .prefix(&owner_addr)
+            .range(deps.storage, start, None, Order::Ascending)
+            .filter_map(|item| match item {
+                Ok((_, lock)) if !include_expired_lock && lock.is_expired(&env.block) => None,
+                item => Some(item),
+            })
+            .take(limit + 1)
+            .map(|item| {
+                item.map(|(token_id, lock)| LockedToken {
+                    token_id,
+                    locker: lock.locker,
+                    until: lock.until,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        let (locks, next) = paginate(locks, limit, |lock| lock.token_id.clone());
+        Ok(NftLocksResponse { locks, next })
+    }
+
+    fn query_permit_nonce(&self, deps: Deps, _env: Env, owner: String) -> StdResult<u64> {
+        let owner_addr = deps.api.addr_validate(&owner)?;
+        self.config().permit_nonce(deps.storage, &owner_addr)
+    }
+
+    fn query_user_of(&self, deps: Deps, env: Env, token_id: String) -> StdResult<UserOfResponse> {
+        let config = self.config();
+        // error if token does not exist
+        config.nft_info.load(deps.storage, &token_id)?;
+        let user = config.nft_user(deps.storage, &env.block, &token_id)?;
+        Ok(UserOfResponse { user })
+    }
+
+    fn query_tokens_of_user(
+        &self,
+        deps: Deps,
+        env: Env,
+        user: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<TokensResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
+
+        let user_addr = deps.api.addr_validate(&user)?;
+        let tokens: Vec<String> = self
+            .config()
+            .user_tokens
+            .prefix(&user_addr)
+            .range(deps.storage, start, None, Order::Ascending)
+            .filter_map(|item| match item {
+                Ok((_, user)) if user.is_expired(&env.block) => None,
+                item => Some(item),
+            })
+            .take(limit + 1)
+            .map(|item| item.map(|(token_id, _)| token_id))
+            .collect::<StdResult<Vec<_>>>()?;
+
+        let (tokens, next) = paginate(tokens, limit, String::clone);
+        Ok(TokensResponse { tokens, next })
+    }
+
+    fn query_token_history(
+        &self,
+        deps: Deps,
+        _env: Env,
+        token_id: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<TokenHistoryResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        let entries = self
+            .config()
+            .provenance
+            .prefix(&token_id)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit + 1)
+            .map(|item| item.map(|(_, entry)| entry))
+            .collect::<StdResult<Vec<_>>>()?;
+        let (entries, next) = paginate(entries, limit, |entry| entry.id);
+        Ok(TokenHistoryResponse { entries, next })
+    }
+
+    fn query_hooks(
+        &self,
+        deps: Deps,
+        _env: Env,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<HooksResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_addr = maybe_addr(deps.api, start_after)?;
+        let start = start_addr.as_ref().map(Bound::exclusive);
+
+        let hooks = self
+            .config()
+            .hooks
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit + 1)
+            .map(|item| item.map(|(addr, mode)| Hook { addr, mode }))
+            .collect::<StdResult<Vec<_>>>()?;
+        let (hooks, next) = paginate(hooks, limit, |hook| hook.addr.to_string());
+        Ok(HooksResponse { hooks, next })
+    }
+
     /// No-op returning empty Binary
     fn query_extension(
         &self,
@@ -382,13 +984,111 @@ pub trait Cw721Query<
         Ok(Binary::default())
     }
 
+    /// No-op returning empty Binary
+    fn query_collection_info_extension(
+        &self,
+        _deps: Deps,
+        _env: Env,
+        _msg: TCollectionInfoExtension,
+    ) -> StdResult<Binary> {
+        Ok(Binary::default())
+    }
+
     fn query_withdraw_address(&self, deps: Deps) -> StdResult<Option<String>> {
-        Cw721Config::<TMetadataExtension, Empty, Empty>::default()
-            .withdraw_address
-            .may_load(deps.storage)
+        self.config().withdraw_address.may_load(deps.storage)
     }
 }
 
+/// Returns the (min, max) bounds of a range. `start_after` and `end_before` are relative to the
+/// order, so for descending order `start_after` is the upper bound.
+fn range_bounds<'b, K: PrimaryKey<'b>>(
+    start_after: Option<String>,
+    end_before: Option<String>,
+    order: Order,
+) -> (Option<Bound<'b, K>>, Option<Bound<'b, K>>) {
+    let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
+    let end = end_before.map(|s| Bound::ExclusiveRaw(s.into()));
+    match order {
+        Order::Ascending => (start, end),
+        Order::Descending => (end, start),
+    }
+}
+
+/// Expects up to `limit + 1` items, the extra one only indicates that there are more pages.
+/// Returns the items and, in case there are more pages, the cursor of the last item.
+fn paginate<T, C>(
+    mut items: Vec<T>,
+    limit: usize,
+    cursor: impl Fn(&T) -> C,
+) -> (Vec<T>, Option<C>) {
+    let next = if items.len() > limit {
+        items.truncate(limit);
+        items.last().map(cursor)
+    } else {
+        None
+    };
+    (items, next)
+}
+
+fn nft_info_with_id<TMetadataExtension>(
+    block: &BlockInfo,
+    placeholder: Option<&Placeholder<TMetadataExtension>>,
+    base_uri: Option<&BaseUri>,
+    token_id: String,
+    nft_info: NftInfo<TMetadataExtension>,
+    include_expired_approval: bool,
+) -> NftInfoWithId<TMetadataExtension>
+where
+    TMetadataExtension: Serialize + DeserializeOwned + Clone,
+{
+    NftInfoWithId {
+        access: OwnerOfResponse {
+            owner: nft_info.owner.to_string(),
+            approvals: humanize_approvals(block, &nft_info, include_expired_approval),
+        },
+        info: nft_info_response(
+            placeholder,
+            base_uri,
+            &token_id,
+            nft_info.token_uri,
+            nft_info.extension,
+        ),
+        token_id,
+    }
+}
+
+/// Returns the placeholder until metadata is revealed, otherwise uri and extension of the token.
+fn nft_info_response<TMetadataExtension>(
+    placeholder: Option<&Placeholder<TMetadataExtension>>,
+    base_uri: Option<&BaseUri>,
+    token_id: &str,
+    token_uri: Option<String>,
+    extension: TMetadataExtension,
+) -> NftInfoResponse<TMetadataExtension>
+where
+    TMetadataExtension: Clone,
+{
+    match placeholder {
+        Some(placeholder) => NftInfoResponse {
+            token_uri: placeholder.token_uri.clone(),
+            extension: placeholder.extension.clone(),
+        },
+        None => NftInfoResponse {
+            token_uri: resolve_token_uri(base_uri, token_id, token_uri),
+            extension,
+        },
+    }
+}
+
+/// Returns the token uri of the token or, if it has none, derives it from the base uri.
+pub fn resolve_token_uri(
+    base_uri: Option<&BaseUri>,
+    token_id: &str,
+    token_uri: Option<String>,
+) -> Option<String> {
+    token_uri.or_else(|| base_uri.map(|base_uri| base_uri.token_uri(token_id)))
+}
+
 pub fn parse_approval(item: StdResult<(Addr, Expiration)>) -> StdResult<Approval> {
     item.map(|(spender, expires)| Approval { spender, expires })
 }
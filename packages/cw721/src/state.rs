@@ -1,19 +1,168 @@
+use std::collections::BTreeSet;
 use std::marker::PhantomData;
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, BlockInfo, CustomMsg, StdResult, Storage};
+use cosmwasm_std::{
+    Addr, Api, BlockInfo, CustomMsg, Decimal, Empty, Order, OverflowError, OverflowOperation,
+    StdError, StdResult, Storage, SubMsg, Timestamp,
+};
 use cw_ownable::{OwnershipStore, OWNERSHIP_KEY};
-use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, SnapshotMap, Strategy};
 use cw_utils::Expiration;
+use schemars::JsonSchema;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
+use crate::error::Cw721ContractError;
+use crate::hook::Cw721HookMsg;
+
+/// Creator owns this contract and can update collection info!
+/// !!! Important note here: !!!
+/// - creator is stored using cw-ownable's OWNERSHIP singleton, so `cw_ownable::assert_owner` checks the creator
+/// - in release v0.17 and v0.18 it was used for minter (which is confusing), but now it is used for creator
+#[deprecated(note = "use `Cw721Config::creator`, which respects custom storage keys")]
+pub const CREATOR: OwnershipStore = OwnershipStore::new(OWNERSHIP_KEY);
 /// - minter is stored in the contract storage using cw_ownable::OwnershipStore (same as for OWNERSHIP but with different key)
-pub const MINTER: OwnershipStore = OwnershipStore::new(OWNERSHIP_KEY);
+/// Note: `CREATOR` and `MINTER` use the default keys, configs with custom keys have their own `creator` and `minter`.
+#[deprecated(note = "use `Cw721Config::minter`, which respects custom storage keys")]
+pub const MINTER: OwnershipStore = OwnershipStore::new("collection_minter");
 
 /// Default CollectionInfoExtension with RoyaltyInfo
+pub type DefaultOptionCollectionInfoExtension = Option<CollectionInfoExtension<RoyaltyInfo>>;
 pub type DefaultOptionMetadataExtension = Option<Metadata>;
 
+/// Maximum length of a collection description
+pub const MAX_COLLECTION_DESCRIPTION_LENGTH: u32 = 512;
+/// Maximum royalty share in percent a creator may set for a collection
+pub const MAX_ROYALTY_SHARE_PCT: u64 = 10;
+/// Default maximum number of items in a batch message
+pub const DEFAULT_MAX_BATCH_SIZE: u32 = 100;
+/// Max length of text fields in `Metadata`, except `image_data`.
+pub const MAX_METADATA_TEXT_LENGTH: u32 = 2048;
+/// Max length of `Metadata::image_data`, e.g. an inline SVG.
+pub const MAX_METADATA_IMAGE_DATA_LENGTH: u32 = 32768;
+/// Max number of traits in `Metadata::attributes`.
+pub const MAX_METADATA_TRAITS: u32 = 100;
+/// Url schemes allowed in `Metadata`.
+pub const METADATA_URL_SCHEMES: [&str; 5] = ["https", "http", "ipfs", "ipns", "ar"];
+/// Max length of token ids, in case no token id policy is set on instantiation.
+pub const DEFAULT_MAX_TOKEN_ID_LENGTH: u32 = 256;
+/// Max number of claimed ids `next_token_id` skips, see `Cw721Config::next_token_id`.
+pub const MAX_TOKEN_ID_SKIPS: u64 = 100;
+/// Backfill of `operator_grants` on migration, see `migrate_operator_grants`.
+pub const BACKFILL_OPERATOR_GRANTS: &str = "operator_grants";
+/// Backfill of `balances` on migration, see `migrate_balances`.
+pub const BACKFILL_BALANCES: &str = "balances";
+/// Backfill of `approved_tokens` on migration, see `migrate_approved_tokens`.
+pub const BACKFILL_APPROVED_TOKENS: &str = "approved_tokens";
+
+/// Storage keys of `Cw721Config`. Contracts holding several collections, or using custom prefixes,
+/// need distinct keys for each config.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cw721StorageKeys<'a> {
+    pub collection_info: &'a str,
+    pub collection_info_extension: &'a str,
+    pub token_count: &'a str,
+    pub balances: &'a str,
+    pub operators: &'a str,
+    pub operator_grants: &'a str,
+    pub nft_info: &'a str,
+    pub nft_info_owner: &'a str,
+    pub approved_tokens: &'a str,
+    pub withdraw_address: &'a str,
+    pub collection_nft_info_frozen: &'a str,
+    pub frozen_nft_infos: &'a str,
+    pub max_batch_size: &'a str,
+    pub paused: &'a str,
+    pub hooks: &'a str,
+    pub max_supply: &'a str,
+    pub next_token_id: &'a str,
+    pub token_id_policy: &'a str,
+    pub nft_locks: &'a str,
+    pub burned_tokens: &'a str,
+    pub allow_remint: &'a str,
+    pub total_minted: &'a str,
+    pub base_uri: &'a str,
+    pub placeholder: &'a str,
+    pub provenance_cap: &'a str,
+    pub provenance: &'a str,
+    pub snapshot_height: &'a str,
+    pub owner_snapshots: &'a str,
+    pub owner_snapshots_checkpoints: &'a str,
+    pub owner_snapshots_changelog: &'a str,
+    pub balance_snapshots: &'a str,
+    pub balance_snapshots_checkpoints: &'a str,
+    pub balance_snapshots_changelog: &'a str,
+    pub nft_users: &'a str,
+    pub user_tokens: &'a str,
+    pub permit_nonces: &'a str,
+    pub backfills: &'a str,
+    pub creator: &'a str,
+    pub minter: &'a str,
+}
+
+impl Default for Cw721StorageKeys<'static> {
+    fn default() -> Self {
+        crate::cw721_storage_keys!("")
+    }
+}
+
+/// Provides the storage keys used by `Cw721Execute` and `Cw721Query`, so both always use the same
+/// stores, e.g. `self.config.keys` for contracts holding a `Cw721Config`.
+pub trait Cw721Storage {
+    fn storage_keys(&self) -> Cw721StorageKeys;
+}
+
+/// Storage keys with all default keys prefixed, e.g. `cw721_storage_keys!("second_")` for a second
+/// collection in the same contract.
+#[macro_export]
+macro_rules! cw721_storage_keys {
+    ($prefix:literal) => {
+        $crate::state::Cw721StorageKeys {
+            // Note: replaces deprecated/legacy key "nft_info"
+            collection_info: concat!($prefix, "collection_info"),
+            collection_info_extension: concat!($prefix, "collection_info_extension"),
+            token_count: concat!($prefix, "num_tokens"),
+            balances: concat!($prefix, "balances"),
+            operators: concat!($prefix, "operators"),
+            operator_grants: concat!($prefix, "operator_grants"),
+            nft_info: concat!($prefix, "tokens"),
+            nft_info_owner: concat!($prefix, "tokens__owner"),
+            approved_tokens: concat!($prefix, "approved_tokens"),
+            withdraw_address: concat!($prefix, "withdraw_address"),
+            collection_nft_info_frozen: concat!($prefix, "collection_nft_info_frozen"),
+            frozen_nft_infos: concat!($prefix, "frozen_nft_infos"),
+            max_batch_size: concat!($prefix, "max_batch_size"),
+            paused: concat!($prefix, "paused"),
+            hooks: concat!($prefix, "hooks"),
+            max_supply: concat!($prefix, "max_supply"),
+            next_token_id: concat!($prefix, "next_token_id"),
+            token_id_policy: concat!($prefix, "token_id_policy"),
+            nft_locks: concat!($prefix, "nft_locks"),
+            burned_tokens: concat!($prefix, "burned_tokens"),
+            allow_remint: concat!($prefix, "allow_remint"),
+            total_minted: concat!($prefix, "total_minted"),
+            base_uri: concat!($prefix, "base_uri"),
+            placeholder: concat!($prefix, "placeholder"),
+            provenance_cap: concat!($prefix, "provenance_cap"),
+            provenance: concat!($prefix, "provenance"),
+            snapshot_height: concat!($prefix, "snapshot_height"),
+            owner_snapshots: concat!($prefix, "owner_snapshots"),
+            owner_snapshots_checkpoints: concat!($prefix, "owner_snapshots__checkpoints"),
+            owner_snapshots_changelog: concat!($prefix, "owner_snapshots__changelog"),
+            balance_snapshots: concat!($prefix, "balance_snapshots"),
+            balance_snapshots_checkpoints: concat!($prefix, "balance_snapshots__checkpoints"),
+            balance_snapshots_changelog: concat!($prefix, "balance_snapshots__changelog"),
+            nft_users: concat!($prefix, "nft_users"),
+            user_tokens: concat!($prefix, "user_tokens"),
+            permit_nonces: concat!($prefix, "permit_nonces"),
+            backfills: concat!($prefix, "backfills"),
+            minter: concat!($prefix, "collection_minter"),
+            creator: concat!($prefix, "ownership"),
+        }
+    };
+}
+
 pub struct Cw721Config<
     'a,
     // Metadata defined in NftInfo (used for mint).
@@ -22,65 +171,178 @@ pub struct Cw721Config<
     TCustomResponseMessage,
     // Message passed for updating metadata.
     TMetadataExtensionMsg,
+    // Extension defined in CollectionInfo.
+    TCollectionInfoExtension,
 > where
     TMetadataExtension: Serialize + DeserializeOwned + Clone,
     TMetadataExtensionMsg: CustomMsg,
+    TCollectionInfoExtension: Serialize + DeserializeOwned + Clone,
 {
     /// Note: replaces deprecated/legacy key "nft_info"!
     pub collection_info: Item<'a, CollectionInfo>,
+    pub collection_info_extension: Item<'a, TCollectionInfoExtension>,
     pub token_count: Item<'a, u64>,
+    /// Number of tokens held by each owner.
+    pub balances: Map<'a, &'a Addr, u64>,
     /// Stored as (granter, operator) giving operator full control over granter's account.
     /// NOTE: granter is the owner, so operator has only control for NFTs owned by granter!
     pub operators: Map<'a, (&'a Addr, &'a Addr), Expiration>,
+    /// Mirror of `operators`, stored as (operator, granter), for looking up all granters of an operator.
+    pub operator_grants: Map<'a, (&'a Addr, &'a Addr), Expiration>,
     pub nft_info:
         IndexedMap<'a, &'a str, NftInfo<TMetadataExtension>, TokenIndexes<'a, TMetadataExtension>>,
+    /// Stored as (spender, token_id), mirrors the approvals of all tokens for looking up all tokens
+    /// a spender is approved for.
+    pub approved_tokens: Map<'a, (&'a Addr, &'a str), Expiration>,
     pub withdraw_address: Item<'a, String>,
+    /// Once set, metadata of all NFTs in the collection can not be updated anymore.
+    pub collection_nft_info_frozen: Item<'a, bool>,
+    /// Tokens whose metadata can not be updated anymore.
+    pub frozen_nft_infos: Map<'a, &'a str, bool>,
+    pub max_batch_size: Item<'a, u32>,
+    /// Paused targets (see `PauseTarget`) and until when they are paused.
+    pub paused: Map<'a, &'a str, Expiration>,
+    /// Contracts notified on mint, transfer, send and burn.
+    pub hooks: Map<'a, &'a Addr, HookMode>,
+    /// Maximum number of tokens ever minted in the collection, unlimited if not set.
+    pub max_supply: Item<'a, u64>,
+    /// Counter used by `MintNext` for sequential token ids.
+    pub next_token_id: Item<'a, u64>,
+    /// Validation applied to token ids on mint, defaults to `TokenIdPolicy::default()`.
+    pub token_id_policy: Item<'a, TokenIdPolicy>,
+    /// Stored as (owner, token_id), tokens that can not be transferred, sent or burned until the lock expires.
+    pub nft_locks: Map<'a, (&'a Addr, &'a str), NftLock>,
+    /// Tombstones of burned tokens, their ids can not be minted again unless `allow_remint` is set.
+    pub burned_tokens: Map<'a, &'a str, bool>,
+    pub allow_remint: Item<'a, bool>,
+    /// Number of tokens ever minted, including burned ones.
+    pub total_minted: Item<'a, u64>,
+    /// Used for deriving the token uri of tokens without one.
+    pub base_uri: Item<'a, BaseUri>,
+    /// Metadata returned for all tokens until revealed.
+    pub placeholder: Item<'a, Placeholder<TMetadataExtension>>,
+    /// Maximum number of provenance entries kept per token, provenance is not recorded if unset or 0.
+    pub provenance_cap: Item<'a, u32>,
+    /// Stored as (token_id, entry id), history of mints, transfers, sends and burns.
+    pub provenance: Map<'a, (&'a str, u64), ProvenanceEntry>,
+    /// Height at which snapshots have been enabled, ownership changes are not recorded if unset.
+    pub snapshot_height: Item<'a, u64>,
+    /// Owner of each token by height, for `OwnerOfAtHeight`.
+    pub owner_snapshots: SnapshotMap<'a, &'a str, Addr>,
+    /// Balance of each owner by height, for `BalanceOfAtHeight`.
+    pub balance_snapshots: SnapshotMap<'a, &'a Addr, u64>,
+    /// Account allowed to use a token until `expires`, without owning it.
+    pub nft_users: Map<'a, &'a str, NftUser>,
+    /// Stored as (user, token_id), for listing tokens of a user.
+    pub user_tokens: Map<'a, (&'a Addr, &'a str), Expiration>,
+    /// Nonce expected by the next `Permit` signed by an owner.
+    pub permit_nonces: Map<'a, &'a Addr, u64>,
+    /// Backfills done on migration (see `BACKFILL_*`), each one is only done once.
+    pub backfills: Map<'a, &'a str, bool>,
+    /// Creator of this collection, stored using cw-ownable's `OWNERSHIP_KEY` by default.
+    pub creator: OwnershipStore<'a>,
+    /// Minter of this collection, stored like creator using `OwnershipStore` with its own key.
+    pub minter: OwnershipStore<'a>,
+    /// Keys the stores above have been created with.
+    pub keys: Cw721StorageKeys<'a>,
 
     pub(crate) _custom_response: PhantomData<TCustomResponseMessage>,
     pub(crate) _custom_execute: PhantomData<TMetadataExtensionMsg>,
 }
 
-impl<TMetadataExtension, TCustomResponseMessage, TMetadataExtensionMsg> Default
-    for Cw721Config<'static, TMetadataExtension, TCustomResponseMessage, TMetadataExtensionMsg>
+impl<
+        TMetadataExtension,
+        TCustomResponseMessage,
+        TMetadataExtensionMsg,
+        TCollectionInfoExtension,
+    > Default
+    for Cw721Config<
+        'static,
+        TMetadataExtension,
+        TCustomResponseMessage,
+        TMetadataExtensionMsg,
+        TCollectionInfoExtension,
+    >
 where
     TMetadataExtension: Serialize + DeserializeOwned + Clone,
     TMetadataExtensionMsg: CustomMsg,
+    TCollectionInfoExtension: Serialize + DeserializeOwned + Clone,
 {
     fn default() -> Self {
-        Self::new(
-            "collection_info", // Note: replaces deprecated/legacy key "nft_info"
-            "num_tokens",
-            "operators",
-            "tokens",
-            "tokens__owner",
-            "withdraw_address",
-        )
+        Self::new(Cw721StorageKeys::default())
     }
 }
 
-impl<'a, TMetadataExtension, TCustomResponseMessage, TMetadataExtensionMsg>
-    Cw721Config<'a, TMetadataExtension, TCustomResponseMessage, TMetadataExtensionMsg>
+impl<
+        'a,
+        TMetadataExtension,
+        TCustomResponseMessage,
+        TMetadataExtensionMsg,
+        TCollectionInfoExtension,
+    >
+    Cw721Config<
+        'a,
+        TMetadataExtension,
+        TCustomResponseMessage,
+        TMetadataExtensionMsg,
+        TCollectionInfoExtension,
+    >
 where
     TMetadataExtension: Serialize + DeserializeOwned + Clone,
     TMetadataExtensionMsg: CustomMsg,
+    TCollectionInfoExtension: Serialize + DeserializeOwned + Clone,
 {
-    fn new(
-        collection_info_key: &'a str,
-        token_count_key: &'a str,
-        operator_key: &'a str,
-        nft_info_key: &'a str,
-        nft_info_owner_key: &'a str,
-        withdraw_address_key: &'a str,
-    ) -> Self {
+    /// Config using the given storage keys, `Default` uses `Cw721StorageKeys::default()`.
+    pub fn new(keys: Cw721StorageKeys<'a>) -> Self {
         let indexes = TokenIndexes {
-            owner: MultiIndex::new(token_owner_idx, nft_info_key, nft_info_owner_key),
+            owner: MultiIndex::new(token_owner_idx, keys.nft_info, keys.nft_info_owner),
         };
         Self {
-            collection_info: Item::new(collection_info_key),
-            token_count: Item::new(token_count_key),
-            operators: Map::new(operator_key),
-            nft_info: IndexedMap::new(nft_info_key, indexes),
-            withdraw_address: Item::new(withdraw_address_key),
+            collection_info: Item::new(keys.collection_info),
+            collection_info_extension: Item::new(keys.collection_info_extension),
+            token_count: Item::new(keys.token_count),
+            balances: Map::new(keys.balances),
+            operators: Map::new(keys.operators),
+            operator_grants: Map::new(keys.operator_grants),
+            nft_info: IndexedMap::new(keys.nft_info, indexes),
+            approved_tokens: Map::new(keys.approved_tokens),
+            withdraw_address: Item::new(keys.withdraw_address),
+            collection_nft_info_frozen: Item::new(keys.collection_nft_info_frozen),
+            frozen_nft_infos: Map::new(keys.frozen_nft_infos),
+            max_batch_size: Item::new(keys.max_batch_size),
+            paused: Map::new(keys.paused),
+            hooks: Map::new(keys.hooks),
+            max_supply: Item::new(keys.max_supply),
+            next_token_id: Item::new(keys.next_token_id),
+            token_id_policy: Item::new(keys.token_id_policy),
+            nft_locks: Map::new(keys.nft_locks),
+            burned_tokens: Map::new(keys.burned_tokens),
+            allow_remint: Item::new(keys.allow_remint),
+            total_minted: Item::new(keys.total_minted),
+            base_uri: Item::new(keys.base_uri),
+            placeholder: Item::new(keys.placeholder),
+            provenance_cap: Item::new(keys.provenance_cap),
+            provenance: Map::new(keys.provenance),
+            snapshot_height: Item::new(keys.snapshot_height),
+            owner_snapshots: SnapshotMap::new(
+                keys.owner_snapshots,
+                keys.owner_snapshots_checkpoints,
+                keys.owner_snapshots_changelog,
+                Strategy::EveryBlock,
+            ),
+            balance_snapshots: SnapshotMap::new(
+                keys.balance_snapshots,
+                keys.balance_snapshots_checkpoints,
+                keys.balance_snapshots_changelog,
+                Strategy::EveryBlock,
+            ),
+            nft_users: Map::new(keys.nft_users),
+            user_tokens: Map::new(keys.user_tokens),
+            permit_nonces: Map::new(keys.permit_nonces),
+            backfills: Map::new(keys.backfills),
+            creator: OwnershipStore::new(keys.creator),
+            minter: OwnershipStore::new(keys.minter),
+            keys,
             _custom_response: PhantomData,
             _custom_execute: PhantomData,
         }
@@ -91,16 +353,387 @@ where
     }
 
     pub fn increment_tokens(&self, storage: &mut dyn Storage) -> StdResult<u64> {
-        let val = self.token_count(storage)? + 1;
+        self.increment_tokens_by(storage, 1)
+    }
+
+    pub fn decrement_tokens(&self, storage: &mut dyn Storage) -> StdResult<u64> {
+        self.decrement_tokens_by(storage, 1)
+    }
+
+    pub fn increment_tokens_by(&self, storage: &mut dyn Storage, amount: u64) -> StdResult<u64> {
+        let val = self.token_count(storage)? + amount;
         self.token_count.save(storage, &val)?;
         Ok(val)
     }
 
-    pub fn decrement_tokens(&self, storage: &mut dyn Storage) -> StdResult<u64> {
-        let val = self.token_count(storage)? - 1;
+    pub fn decrement_tokens_by(&self, storage: &mut dyn Storage, amount: u64) -> StdResult<u64> {
+        let token_count = self.token_count(storage)?;
+        let val = token_count.checked_sub(amount).ok_or_else(|| {
+            StdError::overflow(OverflowError::new(
+                OverflowOperation::Sub,
+                token_count,
+                amount,
+            ))
+        })?;
         self.token_count.save(storage, &val)?;
         Ok(val)
     }
+
+    pub fn total_minted(&self, storage: &dyn Storage) -> StdResult<u64> {
+        Ok(self.total_minted.may_load(storage)?.unwrap_or_default())
+    }
+
+    pub fn increment_total_minted(&self, storage: &mut dyn Storage) -> StdResult<u64> {
+        let val = self.total_minted(storage)? + 1;
+        self.total_minted.save(storage, &val)?;
+        Ok(val)
+    }
+
+    pub fn balance(&self, storage: &dyn Storage, owner: &Addr) -> StdResult<u64> {
+        Ok(self.balances.may_load(storage, owner)?.unwrap_or_default())
+    }
+
+    pub fn permit_nonce(&self, storage: &dyn Storage, owner: &Addr) -> StdResult<u64> {
+        Ok(self
+            .permit_nonces
+            .may_load(storage, owner)?
+            .unwrap_or_default())
+    }
+
+    pub fn increment_balance(&self, storage: &mut dyn Storage, owner: &Addr) -> StdResult<u64> {
+        let val = self.balance(storage, owner)? + 1;
+        self.balances.save(storage, owner, &val)?;
+        Ok(val)
+    }
+
+    /// Fails with an overflow error in case balance is out of sync (e.g. missing backfill).
+    pub fn decrement_balance(&self, storage: &mut dyn Storage, owner: &Addr) -> StdResult<u64> {
+        let balance = self.balance(storage, owner)?;
+        let val = balance.checked_sub(1).ok_or_else(|| {
+            StdError::overflow(OverflowError::new(OverflowOperation::Sub, balance, 1))
+        })?;
+        if val == 0 {
+            self.balances.remove(storage, owner);
+        } else {
+            self.balances.save(storage, owner, &val)?;
+        }
+        Ok(val)
+    }
+
+    /// Removes the given approvals of a token from `approved_tokens`, e.g. on transfer and burn.
+    pub fn clear_approved_tokens(
+        &self,
+        storage: &mut dyn Storage,
+        token_id: &str,
+        approvals: &[Approval],
+    ) {
+        for approval in approvals {
+            self.approved_tokens
+                .remove(storage, (&approval.spender, token_id));
+        }
+    }
+
+    pub fn max_batch_size(&self, storage: &dyn Storage) -> StdResult<u32> {
+        Ok(self
+            .max_batch_size
+            .may_load(storage)?
+            .unwrap_or(DEFAULT_MAX_BATCH_SIZE))
+    }
+
+    /// Batches must not be empty and must not exceed the max batch size.
+    pub fn assert_batch_size(
+        &self,
+        storage: &dyn Storage,
+        batch_size: usize,
+    ) -> Result<(), Cw721ContractError> {
+        if batch_size == 0 {
+            return Err(Cw721ContractError::EmptyBatch {});
+        }
+        let max_batch_size = self.max_batch_size(storage)?;
+        if batch_size > max_batch_size as usize {
+            return Err(Cw721ContractError::BatchSizeExceeded { max_batch_size });
+        }
+        Ok(())
+    }
+
+    /// Minting the given amount of tokens must not exceed the max supply, if set. Burned tokens
+    /// count as well, so the max supply can not be bypassed by burning and minting again.
+    pub fn assert_max_supply(
+        &self,
+        storage: &dyn Storage,
+        amount: u64,
+    ) -> Result<(), Cw721ContractError> {
+        if let Some(max_supply) = self.max_supply.may_load(storage)? {
+            if self.total_minted(storage)? + amount > max_supply {
+                return Err(Cw721ContractError::MaxSupplyReached { max_supply });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn token_id_policy(&self, storage: &dyn Storage) -> StdResult<TokenIdPolicy> {
+        Ok(self.token_id_policy.may_load(storage)?.unwrap_or_default())
+    }
+
+    /// Returns the next sequential token id, starting at 1. Mints advance the cursor past their id
+    /// (see `advance_next_token_id`), so only ids minted before the cursor was tracked are skipped,
+    /// at most `MAX_TOKEN_ID_SKIPS`.
+    pub fn next_token_id(&self, storage: &dyn Storage) -> StdResult<u64> {
+        let start = self.next_token_id.may_load(storage)?.unwrap_or(1);
+        let mut id = start;
+        while self.nft_info.has(storage, &id.to_string())
+            || self.burned_tokens.has(storage, &id.to_string())
+        {
+            if id - start >= MAX_TOKEN_ID_SKIPS {
+                return Err(StdError::generic_err(format!(
+                    "No free token id within {MAX_TOKEN_ID_SKIPS} ids after {start}"
+                )));
+            }
+            id = id
+                .checked_add(1)
+                .ok_or_else(|| StdError::generic_err("Token id overflow"))?;
+        }
+        Ok(id)
+    }
+
+    /// Moves the cursor of `next_token_id` past a minted numeric token id, so `MintNext` never has
+    /// to skip ids claimed by explicit mints.
+    pub fn advance_next_token_id(
+        &self,
+        storage: &mut dyn Storage,
+        token_id: &str,
+    ) -> StdResult<()> {
+        let id = match token_id.parse::<u64>() {
+            Ok(id) if id.to_string() == token_id => id,
+            _ => return Ok(()),
+        };
+        if id >= self.next_token_id.may_load(storage)?.unwrap_or(1) {
+            self.next_token_id.save(storage, &id.saturating_add(1))?;
+        }
+        Ok(())
+    }
+
+    /// Burned token ids can only be minted again in case `allow_remint` is set.
+    pub fn assert_not_burned(
+        &self,
+        storage: &dyn Storage,
+        token_id: &str,
+    ) -> Result<(), Cw721ContractError> {
+        if self.burned_tokens.has(storage, token_id)
+            && !self.allow_remint.may_load(storage)?.unwrap_or_default()
+        {
+            return Err(Cw721ContractError::TokenBurned {
+                token_id: token_id.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns true in case metadata is frozen for either the whole collection or the given token.
+    pub fn is_nft_info_frozen(&self, storage: &dyn Storage, token_id: &str) -> StdResult<bool> {
+        if self
+            .collection_nft_info_frozen
+            .may_load(storage)?
+            .unwrap_or_default()
+        {
+            return Ok(true);
+        }
+        Ok(self
+            .frozen_nft_infos
+            .may_load(storage, token_id)?
+            .unwrap_or_default())
+    }
+
+    /// Returns until when the target is paused, or `None` in case it is not paused (anymore).
+    pub fn pause_expiration(
+        &self,
+        storage: &dyn Storage,
+        block: &BlockInfo,
+        target: &PauseTarget,
+    ) -> StdResult<Option<Expiration>> {
+        Ok(self
+            .paused
+            .may_load(storage, target.as_str())?
+            .filter(|expires| !expires.is_expired(block)))
+    }
+
+    pub fn assert_not_paused(
+        &self,
+        storage: &dyn Storage,
+        block: &BlockInfo,
+        target: &PauseTarget,
+    ) -> Result<(), Cw721ContractError> {
+        if self.pause_expiration(storage, block, target)?.is_some() {
+            return Err(Cw721ContractError::Paused {
+                target: target.as_str().to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns the lock of the token, or `None` in case it is not locked (anymore).
+    pub fn nft_lock(
+        &self,
+        storage: &dyn Storage,
+        block: &BlockInfo,
+        owner: &Addr,
+        token_id: &str,
+    ) -> StdResult<Option<NftLock>> {
+        Ok(self
+            .nft_locks
+            .may_load(storage, (owner, token_id))?
+            .filter(|lock| !lock.is_expired(block)))
+    }
+
+    pub fn assert_not_locked(
+        &self,
+        storage: &dyn Storage,
+        block: &BlockInfo,
+        owner: &Addr,
+        token_id: &str,
+    ) -> Result<(), Cw721ContractError> {
+        if let Some(lock) = self.nft_lock(storage, block, owner, token_id)? {
+            return Err(Cw721ContractError::NftLocked {
+                token_id: token_id.to_string(),
+                until: lock.until,
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns the user of a token, `None` if not set or expired.
+    pub fn nft_user(
+        &self,
+        storage: &dyn Storage,
+        block: &BlockInfo,
+        token_id: &str,
+    ) -> StdResult<Option<NftUser>> {
+        Ok(self
+            .nft_users
+            .may_load(storage, token_id)?
+            .filter(|user| !user.is_expired(block)))
+    }
+
+    /// Removes the user of a token, e.g. on transfer and burn.
+    pub fn clear_nft_user(&self, storage: &mut dyn Storage, token_id: &str) -> StdResult<()> {
+        if let Some(user) = self.nft_users.may_load(storage, token_id)? {
+            self.nft_users.remove(storage, token_id);
+            self.user_tokens.remove(storage, (&user.user, token_id));
+        }
+        Ok(())
+    }
+
+    /// Returns the placeholder, or `None` in case metadata is revealed.
+    pub fn placeholder(
+        &self,
+        storage: &dyn Storage,
+        block: &BlockInfo,
+    ) -> StdResult<Option<Placeholder<TMetadataExtension>>> {
+        Ok(self
+            .placeholder
+            .may_load(storage)?
+            .filter(|placeholder| !placeholder.reveal_at.is_expired(block)))
+    }
+
+    /// Appends an entry to the history of the token and removes the oldest entries exceeding the cap.
+    pub fn record_provenance(
+        &self,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        token_id: &str,
+        from: Option<Addr>,
+        to: Option<Addr>,
+        action: ProvenanceAction,
+    ) -> StdResult<()> {
+        let cap = self.provenance_cap.may_load(storage)?.unwrap_or_default();
+        if cap == 0 {
+            return Ok(());
+        }
+        let id = self
+            .provenance
+            .prefix(token_id)
+            .keys(storage, None, None, Order::Descending)
+            .next()
+            .transpose()?
+            .map_or(0, |id| id + 1);
+        let entry = ProvenanceEntry {
+            id,
+            height: block.height,
+            time: block.time,
+            from,
+            to,
+            action,
+        };
+        self.provenance.save(storage, (token_id, id), &entry)?;
+        let outdated: Vec<u64> = self
+            .provenance
+            .prefix(token_id)
+            .keys(storage, None, None, Order::Descending)
+            .skip(cap as usize)
+            .collect::<StdResult<_>>()?;
+        for id in outdated {
+            self.provenance.remove(storage, (token_id, id));
+        }
+        Ok(())
+    }
+
+    /// Records the owner of a token and the balances of the previous and new owner, in case
+    /// snapshots are enabled. Must be called after balances are updated.
+    pub fn record_ownership_snapshot(
+        &self,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        token_id: &str,
+        from: Option<&Addr>,
+        to: Option<&Addr>,
+    ) -> StdResult<()> {
+        if !self.snapshot_height.exists(storage) {
+            return Ok(());
+        }
+        match to {
+            Some(to) => self
+                .owner_snapshots
+                .save(storage, token_id, to, block.height)?,
+            None => self
+                .owner_snapshots
+                .remove(storage, token_id, block.height)?,
+        }
+        for owner in [from, to].into_iter().flatten() {
+            let balance = self.balance(storage, owner)?;
+            self.balance_snapshots
+                .save(storage, owner, &balance, block.height)?;
+        }
+        Ok(())
+    }
+
+    /// Snapshots are only available once enabled, at heights not before instantiation.
+    pub fn assert_snapshot_available(&self, storage: &dyn Storage, height: u64) -> StdResult<()> {
+        match self.snapshot_height.may_load(storage)? {
+            None => Err(StdError::generic_err("Snapshots are not enabled")),
+            Some(snapshot_height) if height < snapshot_height => Err(StdError::generic_err(
+                format!("No snapshots before height {snapshot_height}"),
+            )),
+            Some(_) => Ok(()),
+        }
+    }
+
+    /// Creates a sub message for each registered hook.
+    pub fn hook_sub_msgs<T>(
+        &self,
+        storage: &dyn Storage,
+        msg: &Cw721HookMsg,
+    ) -> StdResult<Vec<SubMsg<T>>>
+    where
+        T: Clone + std::fmt::Debug + PartialEq + JsonSchema,
+    {
+        self.hooks
+            .range(storage, None, None, Order::Ascending)
+            .map(|item| {
+                let (addr, mode) = item?;
+                msg.clone().into_sub_msg(addr, &mode)
+            })
+            .collect()
+    }
 }
 
 pub fn token_owner_idx<TMetadataExtension>(_pk: &[u8], d: &NftInfo<TMetadataExtension>) -> Addr {
@@ -123,6 +756,99 @@ pub struct NftInfo<TMetadataExtension> {
     pub extension: TMetadataExtension,
 }
 
+/// Metadata returned for all tokens until revealed, either by the creator or once `reveal_at` expires.
+#[cw_serde]
+pub struct Placeholder<TMetadataExtension> {
+    pub token_uri: Option<String>,
+    pub extension: TMetadataExtension,
+    pub reveal_at: Expiration,
+}
+
+/// Operations the creator can pause independently from each other.
+#[cw_serde]
+pub enum PauseTarget {
+    /// Transfers, sends, burns and approvals. Revoking approvals is always possible.
+    Transfers,
+    /// Minting of new NFTs.
+    Minting,
+}
+
+impl PauseTarget {
+    pub fn as_str(&self) -> &str {
+        match self {
+            PauseTarget::Transfers => "transfers",
+            PauseTarget::Minting => "minting",
+        }
+    }
+}
+
+/// Characters allowed in token ids.
+#[cw_serde]
+pub enum TokenIdCharset {
+    /// Any character except control characters.
+    Printable,
+    /// ASCII letters and digits, `-`, `_` and `.`.
+    UrlSafe,
+    /// ASCII digits only.
+    Numeric,
+}
+
+/// Validation policy for token ids, set on instantiation.
+#[cw_serde]
+pub struct TokenIdPolicy {
+    /// Max length of a token id in bytes.
+    pub max_length: u32,
+    pub charset: TokenIdCharset,
+}
+
+impl Default for TokenIdPolicy {
+    fn default() -> Self {
+        TokenIdPolicy {
+            max_length: DEFAULT_MAX_TOKEN_ID_LENGTH,
+            charset: TokenIdCharset::Printable,
+        }
+    }
+}
+
+impl TokenIdPolicy {
+    /// Token ids must not be empty, must not exceed the max length and must only contain
+    /// characters of the charset.
+    pub fn validate(&self, token_id: &str) -> Result<(), Cw721ContractError> {
+        if token_id.is_empty() {
+            return Err(Cw721ContractError::InvalidTokenId {
+                reason: "must not be empty".to_string(),
+            });
+        }
+        if token_id.len() > self.max_length as usize {
+            return Err(Cw721ContractError::InvalidTokenId {
+                reason: format!("exceeds max length of {}", self.max_length),
+            });
+        }
+        let valid_chars = match self.charset {
+            TokenIdCharset::Printable => !token_id.chars().any(char::is_control),
+            TokenIdCharset::UrlSafe => token_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')),
+            TokenIdCharset::Numeric => token_id.chars().all(|c| c.is_ascii_digit()),
+        };
+        if !valid_chars {
+            return Err(Cw721ContractError::InvalidTokenId {
+                reason: "contains characters not allowed by charset".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Defines how a hook contract is called.
+#[cw_serde]
+pub enum HookMode {
+    /// A failing hook reverts the whole transaction.
+    Blocking,
+    /// Errors of the hook are ignored.
+    FireAndForget,
+}
+
 #[cw_serde]
 pub struct Approval {
     /// Account that can transfer/send the token
@@ -137,6 +863,55 @@ impl Approval {
     }
 }
 
+#[cw_serde]
+pub enum ProvenanceAction {
+    Mint,
+    Transfer,
+    Send,
+    Burn,
+}
+
+/// Single entry in the history of a token.
+#[cw_serde]
+pub struct ProvenanceEntry {
+    /// Sequential per token, used for pagination
+    pub id: u64,
+    pub height: u64,
+    pub time: Timestamp,
+    /// Previous owner, `None` on mint
+    pub from: Option<Addr>,
+    /// New owner, `None` on burn
+    pub to: Option<Addr>,
+    pub action: ProvenanceAction,
+}
+
+/// Prevents a token from being transferred, sent or burned until `until`.
+#[cw_serde]
+pub struct NftLock {
+    /// Account that locked the token and is the only one allowed to unlock it
+    pub locker: Addr,
+    pub until: Expiration,
+}
+
+impl NftLock {
+    pub fn is_expired(&self, block: &BlockInfo) -> bool {
+        self.until.is_expired(block)
+    }
+}
+
+/// Grants usage rights of a token without transferring ownership, e.g. for renting.
+#[cw_serde]
+pub struct NftUser {
+    pub user: Addr,
+    pub expires: Expiration,
+}
+
+impl NftUser {
+    pub fn is_expired(&self, block: &BlockInfo) -> bool {
+        self.expires.is_expired(block)
+    }
+}
+
 pub struct TokenIndexes<'a, TMetadataExtension>
 where
     TMetadataExtension: Serialize + DeserializeOwned + Clone,
@@ -157,12 +932,134 @@ where
     }
 }
 
+/// Token uri of tokens without one is derived as `uri` + token id + `suffix`, e.g.
+/// `ipfs://<cid>/` + `1` + `.json`.
+#[cw_serde]
+pub struct BaseUri {
+    pub uri: String,
+    pub suffix: Option<String>,
+}
+
+impl BaseUri {
+    pub fn validate(&self) -> Result<(), Cw721ContractError> {
+        validate_metadata_url(&self.uri)?;
+        if let Some(suffix) = &self.suffix {
+            validate_text_length("suffix", suffix, MAX_METADATA_TEXT_LENGTH)?;
+        }
+        Ok(())
+    }
+
+    pub fn token_uri(&self, token_id: &str) -> String {
+        format!(
+            "{}{}{}",
+            self.uri,
+            token_id,
+            self.suffix.as_deref().unwrap_or_default()
+        )
+    }
+}
+
 #[cw_serde]
 pub struct CollectionInfo {
     pub name: String,
     pub symbol: String,
 }
 
+#[cw_serde]
+pub struct CollectionInfoExtension<TRoyaltyInfo> {
+    pub description: String,
+    pub image: String,
+    pub external_link: Option<String>,
+    pub explicit_content: Option<bool>,
+    pub start_trading_time: Option<Timestamp>,
+    pub royalty_info: Option<TRoyaltyInfo>,
+}
+
+#[cw_serde]
+pub struct RoyaltyInfo {
+    // address that receives the royalties
+    pub payment_address: Addr,
+    // royalty share in percentage (0 - 1)
+    pub share: Decimal,
+}
+
+/// Validates a collection info extension before it is stored, e.g. on instantiate and
+/// `UpdateCollectionInfo`. Custom extensions need to implement this trait.
+pub trait ValidateCollectionInfo {
+    fn validate_collection_info(&self, api: &dyn Api) -> Result<(), Cw721ContractError>;
+}
+
+impl ValidateCollectionInfo for Empty {
+    fn validate_collection_info(&self, _api: &dyn Api) -> Result<(), Cw721ContractError> {
+        Ok(())
+    }
+}
+
+impl<T> ValidateCollectionInfo for Option<T>
+where
+    T: ValidateCollectionInfo,
+{
+    fn validate_collection_info(&self, api: &dyn Api) -> Result<(), Cw721ContractError> {
+        match self {
+            Some(extension) => extension.validate_collection_info(api),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<TRoyaltyInfo> ValidateCollectionInfo for CollectionInfoExtension<TRoyaltyInfo>
+where
+    TRoyaltyInfo: ValidateCollectionInfo,
+{
+    fn validate_collection_info(&self, api: &dyn Api) -> Result<(), Cw721ContractError> {
+        if self.description.is_empty() {
+            return Err(Cw721ContractError::CollectionDescriptionEmpty {});
+        }
+        if self.description.len() > MAX_COLLECTION_DESCRIPTION_LENGTH as usize {
+            return Err(Cw721ContractError::CollectionDescriptionTooLong {
+                max_length: MAX_COLLECTION_DESCRIPTION_LENGTH,
+            });
+        }
+        validate_url(&self.image)?;
+        if let Some(external_link) = &self.external_link {
+            validate_url(external_link)?;
+        }
+        self.royalty_info.validate_collection_info(api)
+    }
+}
+
+impl ValidateCollectionInfo for RoyaltyInfo {
+    fn validate_collection_info(&self, api: &dyn Api) -> Result<(), Cw721ContractError> {
+        api.addr_validate(self.payment_address.as_str())?;
+        let max_share = Decimal::percent(MAX_ROYALTY_SHARE_PCT);
+        if self.share > max_share {
+            return Err(Cw721ContractError::InvalidRoyaltyShare { max_share });
+        }
+        Ok(())
+    }
+}
+
+/// A url must have a scheme (e.g. `https://` or `ipfs://`) and must not contain any whitespace.
+pub fn validate_url(url: &str) -> Result<(), Cw721ContractError> {
+    let valid = match url.split_once("://") {
+        Some((scheme, rest)) => {
+            !scheme.is_empty()
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.')
+                && !rest.is_empty()
+                && !url.chars().any(|c| c.is_whitespace() || c.is_control())
+        }
+        None => false,
+    };
+    if !valid {
+        return Err(Cw721ContractError::InvalidUrl {
+            url: url.to_string(),
+        });
+    }
+    Ok(())
+}
+
 // see: https://docs.opensea.io/docs/metadata-standards
 #[cw_serde]
 #[derive(Default)]
@@ -184,3 +1081,119 @@ pub struct Trait {
     pub trait_type: String,
     pub value: String,
 }
+
+/// Validates NFT metadata before it is stored, e.g. on mint and `UpdateNftInfo`.
+/// Custom extensions need to implement this trait.
+pub trait ValidateMetadata {
+    fn validate_metadata(&self) -> Result<(), Cw721ContractError>;
+}
+
+impl ValidateMetadata for Empty {
+    fn validate_metadata(&self) -> Result<(), Cw721ContractError> {
+        Ok(())
+    }
+}
+
+impl<T> ValidateMetadata for Option<T>
+where
+    T: ValidateMetadata,
+{
+    fn validate_metadata(&self) -> Result<(), Cw721ContractError> {
+        match self {
+            Some(extension) => extension.validate_metadata(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl ValidateMetadata for Metadata {
+    fn validate_metadata(&self) -> Result<(), Cw721ContractError> {
+        let urls = [
+            ("image", &self.image),
+            ("external_url", &self.external_url),
+            ("animation_url", &self.animation_url),
+            ("youtube_url", &self.youtube_url),
+        ];
+        for (field, url) in urls {
+            if let Some(url) = url {
+                validate_text_length(field, url, MAX_METADATA_TEXT_LENGTH)?;
+                validate_metadata_url(url)?;
+            }
+        }
+        if let Some(name) = &self.name {
+            validate_text_length("name", name, MAX_METADATA_TEXT_LENGTH)?;
+        }
+        if let Some(description) = &self.description {
+            validate_text_length("description", description, MAX_METADATA_TEXT_LENGTH)?;
+        }
+        if let Some(image_data) = &self.image_data {
+            validate_text_length("image_data", image_data, MAX_METADATA_IMAGE_DATA_LENGTH)?;
+        }
+        if let Some(background_color) = &self.background_color {
+            validate_background_color(background_color)?;
+        }
+        if let Some(attributes) = &self.attributes {
+            validate_traits(attributes)?;
+        }
+        Ok(())
+    }
+}
+
+/// Urls in metadata must be valid (see `validate_url`) and use one of the `METADATA_URL_SCHEMES`.
+pub fn validate_metadata_url(url: &str) -> Result<(), Cw721ContractError> {
+    validate_url(url)?;
+    let scheme = url.split_once("://").map(|(scheme, _)| scheme);
+    if !matches!(scheme, Some(scheme) if METADATA_URL_SCHEMES.contains(&scheme)) {
+        return Err(Cw721ContractError::UnsupportedUrlScheme {
+            url: url.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Background color must be six hexadecimal characters without a pre-pended `#`.
+pub fn validate_background_color(color: &str) -> Result<(), Cw721ContractError> {
+    if color.len() != 6 || !color.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(Cw721ContractError::InvalidBackgroundColor {
+            color: color.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Traits must not exceed `MAX_METADATA_TRAITS` and each trait type must be unique.
+pub fn validate_traits(traits: &[Trait]) -> Result<(), Cw721ContractError> {
+    if traits.len() > MAX_METADATA_TRAITS as usize {
+        return Err(Cw721ContractError::TooManyTraits {
+            max_traits: MAX_METADATA_TRAITS,
+        });
+    }
+    let mut trait_types = BTreeSet::new();
+    for t in traits {
+        validate_text_length("trait_type", &t.trait_type, MAX_METADATA_TEXT_LENGTH)?;
+        validate_text_length("value", &t.value, MAX_METADATA_TEXT_LENGTH)?;
+        if let Some(display_type) = &t.display_type {
+            validate_text_length("display_type", display_type, MAX_METADATA_TEXT_LENGTH)?;
+        }
+        if !trait_types.insert(t.trait_type.as_str()) {
+            return Err(Cw721ContractError::DuplicateTraitType {
+                trait_type: t.trait_type.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+fn validate_text_length(
+    field: &str,
+    text: &str,
+    max_length: u32,
+) -> Result<(), Cw721ContractError> {
+    if text.len() > max_length as usize {
+        return Err(Cw721ContractError::MetadataFieldTooLong {
+            field: field.to_string(),
+            max_length,
+        });
+    }
+    Ok(())
+}
@@ -1,19 +1,36 @@
 #![cfg(test)]
 
-use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use std::marker::PhantomData;
+
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
 
 use cosmwasm_std::{
-    from_json, to_json_binary, Addr, Coin, CosmosMsg, DepsMut, Empty, Response, StdError, WasmMsg,
+    from_json, to_json_binary, to_json_string, Addr, Api, Binary, CanonicalAddr, Coin, CosmosMsg,
+    Decimal, DepsMut, Empty, Event, OwnedDeps, RecoverPubkeyError, Reply, ReplyOn, Response,
+    StdError, StdResult, SubMsgResult, Timestamp, VerificationError, WasmMsg,
 };
 
 use crate::error::Cw721ContractError;
+use crate::event::Cw721Event;
+use crate::hook::{Cw721HookMsg, HOOK_REPLY_ID, MAX_HOOKS};
 use crate::msg::{
-    ApprovalResponse, NftInfoResponse, OperatorResponse, OperatorsResponse, OwnerOfResponse,
+    ApprovalResponse, ApprovedToken, ApprovedTokensResponse, CollectionInfoAndExtensionResponse,
+    CollectionInfoMsg, Hook, LockedToken, MintMsg, NftInfoResponse, NftInfoWithId,
+    NftInfosResponse, OperatorGrant, OperatorGrantsResponse, OperatorResponse, OperatorsResponse,
+    OrderBy, OwnerOfResponse, PauseStatusResponse, SupplyResponse, TokensResponse,
+    TotalMintedResponse,
 };
 use crate::msg::{Cw721ExecuteMsg, Cw721InstantiateMsg, Cw721QueryMsg};
 use crate::receiver::Cw721ReceiveMsg;
-use crate::state::{CollectionInfo, DefaultOptionMetadataExtension, MINTER};
-use crate::{execute::Cw721Execute, query::Cw721Query, Approval, Expiration};
+use crate::state::{
+    BaseUri, CollectionInfo, CollectionInfoExtension, Cw721Config,
+    DefaultOptionCollectionInfoExtension, DefaultOptionMetadataExtension, HookMode, Metadata,
+    NftLock, NftUser, PauseTarget, ProvenanceAction, ProvenanceEntry, RoyaltyInfo, TokenIdCharset,
+    TokenIdPolicy, DEFAULT_MAX_TOKEN_ID_LENGTH, MAX_ROYALTY_SHARE_PCT, MAX_TOKEN_ID_SKIPS,
+};
+use crate::{cw721_storage_keys, execute::Cw721Execute, query::Cw721Query, Approval, Expiration};
 use cw_ownable::{Action, Ownership, OwnershipError};
 
 use super::contract::Cw721Contract;
@@ -25,13 +42,27 @@ const SYMBOL: &str = "MGK";
 
 fn setup_contract(
     deps: DepsMut<'_>,
-) -> Cw721Contract<'static, DefaultOptionMetadataExtension, Empty, Empty> {
+) -> Cw721Contract<
+    'static,
+    DefaultOptionMetadataExtension,
+    Empty,
+    Empty,
+    DefaultOptionCollectionInfoExtension,
+> {
     let contract = Cw721Contract::default();
     let msg = Cw721InstantiateMsg {
         name: CONTRACT_NAME.to_string(),
         symbol: SYMBOL.to_string(),
+        collection_info_extension: None,
+        creator: None,
         minter: Some(String::from(MINTER_ADDR)),
         withdraw_address: None,
+        max_batch_size: None,
+        max_supply: None,
+        token_id_policy: None,
+        allow_remint: None,
+        base_uri: None,
+        enable_snapshots: None,
     };
     let info = mock_info("creator", &[]);
     let res = contract
@@ -51,13 +82,26 @@ fn setup_contract(
 #[test]
 fn proper_instantiation() {
     let mut deps = mock_dependencies();
-    let contract = Cw721Contract::<DefaultOptionMetadataExtension, Empty, Empty>::default();
+    let contract = Cw721Contract::<
+        DefaultOptionMetadataExtension,
+        Empty,
+        Empty,
+        DefaultOptionCollectionInfoExtension,
+    >::default();
 
     let msg = Cw721InstantiateMsg {
         name: CONTRACT_NAME.to_string(),
         symbol: SYMBOL.to_string(),
+        collection_info_extension: None,
+        creator: None,
         minter: Some(String::from(MINTER_ADDR)),
         withdraw_address: Some(String::from(CREATOR_ADDR)),
+        max_batch_size: None,
+        max_supply: None,
+        token_id_policy: None,
+        allow_remint: None,
+        base_uri: None,
+        enable_snapshots: None,
     };
     let info = mock_info("creator", &[]);
     let env = mock_env();
@@ -76,8 +120,18 @@ fn proper_instantiation() {
     assert_eq!(0, res.messages.len());
 
     // it worked, let's query the state
-    let minter_ownership = MINTER.get_ownership(deps.as_ref().storage).unwrap();
+    let minter_ownership = contract
+        .config
+        .minter
+        .get_ownership(deps.as_ref().storage)
+        .unwrap();
     assert_eq!(Some(Addr::unchecked(MINTER_ADDR)), minter_ownership.owner);
+    let creator_ownership = contract
+        .config
+        .creator
+        .get_ownership(deps.as_ref().storage)
+        .unwrap();
+    assert_eq!(Some(Addr::unchecked(CREATOR_ADDR)), creator_ownership.owner);
     let collection_info = contract
         .query_collection_info(deps.as_ref(), env.clone())
         .unwrap();
@@ -103,7 +157,7 @@ fn proper_instantiation() {
 
     // list the token_ids
     let tokens = contract
-        .query_all_tokens(deps.as_ref(), env, None, None)
+        .query_all_tokens(deps.as_ref(), env, None, None, None, None)
         .unwrap();
     assert_eq!(0, tokens.tokens.len());
 }
@@ -111,13 +165,37 @@ fn proper_instantiation() {
 #[test]
 fn proper_instantiation_with_collection_info() {
     let mut deps = mock_dependencies();
-    let contract = Cw721Contract::<DefaultOptionMetadataExtension, Empty, Empty>::default();
-
+    let contract = Cw721Contract::<
+        DefaultOptionMetadataExtension,
+        Empty,
+        Empty,
+        DefaultOptionCollectionInfoExtension,
+    >::default();
+
+    let collection_info_extension = Some(CollectionInfoExtension {
+        description: "description".to_string(),
+        image: "https://moonphases.org".to_string(),
+        external_link: Some("https://moonphases.org/external".to_string()),
+        explicit_content: Some(false),
+        start_trading_time: Some(Timestamp::from_seconds(42)),
+        royalty_info: Some(RoyaltyInfo {
+            payment_address: Addr::unchecked(CREATOR_ADDR),
+            share: Decimal::percent(5),
+        }),
+    });
     let msg = Cw721InstantiateMsg {
         name: CONTRACT_NAME.to_string(),
         symbol: SYMBOL.to_string(),
+        collection_info_extension: collection_info_extension.clone(),
+        creator: None,
         minter: Some(String::from(MINTER_ADDR)),
         withdraw_address: Some(String::from(CREATOR_ADDR)),
+        max_batch_size: None,
+        max_supply: None,
+        token_id_policy: None,
+        allow_remint: None,
+        base_uri: None,
+        enable_snapshots: None,
     };
     let collection_info = mock_info("creator", &[]);
     let env = mock_env();
@@ -136,7 +214,11 @@ fn proper_instantiation_with_collection_info() {
     assert_eq!(0, res.messages.len());
 
     // it worked, let's query the state
-    let minter_ownership = MINTER.get_ownership(deps.as_ref().storage).unwrap();
+    let minter_ownership = contract
+        .config
+        .minter
+        .get_ownership(deps.as_ref().storage)
+        .unwrap();
     assert_eq!(Some(Addr::unchecked(MINTER_ADDR)), minter_ownership.owner);
     let info = contract
         .query_collection_info(deps.as_ref(), env.clone())
@@ -148,6 +230,17 @@ fn proper_instantiation_with_collection_info() {
             symbol: SYMBOL.to_string(),
         }
     );
+    let collection_info_and_extension = contract
+        .query_collection_info_and_extension(deps.as_ref())
+        .unwrap();
+    assert_eq!(
+        collection_info_and_extension,
+        CollectionInfoAndExtensionResponse {
+            name: CONTRACT_NAME.to_string(),
+            symbol: SYMBOL.to_string(),
+            extension: collection_info_extension,
+        }
+    );
 
     let withdraw_address = contract
         .config
@@ -163,7 +256,7 @@ fn proper_instantiation_with_collection_info() {
 
     // list the token_ids
     let tokens = contract
-        .query_all_tokens(deps.as_ref(), env, None, None)
+        .query_all_tokens(deps.as_ref(), env, None, None, None, None)
         .unwrap();
     assert_eq!(0, tokens.tokens.len());
 }
@@ -248,7 +341,7 @@ fn minting() {
 
     // list the token_ids
     let tokens = contract
-        .query_all_tokens(deps.as_ref(), env, None, None)
+        .query_all_tokens(deps.as_ref(), env, None, None, None, None)
         .unwrap();
     assert_eq!(1, tokens.tokens.len());
     assert_eq!(vec![token_id], tokens.tokens);
@@ -347,599 +440,4154 @@ fn test_update_minter() {
 }
 
 #[test]
-fn burning() {
+fn test_update_creator() {
     let mut deps = mock_dependencies();
     let contract = setup_contract(deps.as_mut());
 
-    let token_id = "petrify".to_string();
-    let token_uri = "https://www.merriam-webster.com/dictionary/petrify".to_string();
-
-    let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id.clone(),
-        owner: MINTER_ADDR.to_string(),
-        token_uri: Some(token_uri),
-        extension: None,
-    };
-
-    let burn_msg = Cw721ExecuteMsg::Burn { token_id };
-
-    // mint some NFT
-    let allowed = mock_info(MINTER_ADDR, &[]);
-    let _ = contract
-        .execute(deps.as_mut(), mock_env(), allowed.clone(), mint_msg)
-        .unwrap();
-
-    // random not allowed to burn
-    let random = mock_info("random", &[]);
-    let env = mock_env();
+    // Minter can not update creator ownership.
+    let minter_info = mock_info(MINTER_ADDR, &[]);
     let err = contract
-        .execute(deps.as_mut(), env.clone(), random, burn_msg.clone())
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            minter_info,
+            Cw721ExecuteMsg::UpdateCreatorOwnership(Action::TransferOwnership {
+                new_owner: "random".to_string(),
+                expiry: None,
+            }),
+        )
         .unwrap_err();
-
     assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
 
-    let _ = contract
-        .execute(deps.as_mut(), env.clone(), allowed, burn_msg)
+    // Update the creator to "random".
+    let creator_info = mock_info(CREATOR_ADDR, &[]);
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            creator_info.clone(),
+            Cw721ExecuteMsg::UpdateCreatorOwnership(Action::TransferOwnership {
+                new_owner: "random".to_string(),
+                expiry: None,
+            }),
+        )
         .unwrap();
 
-    // ensure num tokens decreases
-    let count = contract
-        .query_num_tokens(deps.as_ref(), env.clone())
+    // Creator does not change until ownership transfer completes.
+    let ownership: Ownership<Addr> = from_json(
+        contract
+            .query(
+                deps.as_ref(),
+                mock_env(),
+                Cw721QueryMsg::GetCreatorOwnership {},
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        ownership,
+        Ownership::<Addr> {
+            owner: Some(Addr::unchecked(CREATOR_ADDR)),
+            pending_owner: Some(Addr::unchecked("random")),
+            pending_expiry: None,
+        }
+    );
+
+    // Accept the ownership transfer.
+    let random_info = mock_info("random", &[]);
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            random_info.clone(),
+            Cw721ExecuteMsg::UpdateCreatorOwnership(Action::AcceptOwnership),
+        )
         .unwrap();
-    assert_eq!(0, count.count);
 
-    // trying to get nft returns error
-    let _ = contract
-        .query_nft_info(deps.as_ref(), env.clone(), "petrify".to_string())
+    // Creator changes after ownership transfer is accepted, minter stays the same.
+    let creator_ownership: Ownership<Addr> = from_json(
+        contract
+            .query(
+                deps.as_ref(),
+                mock_env(),
+                Cw721QueryMsg::GetCreatorOwnership {},
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(creator_ownership.owner, Some(random_info.sender.clone()));
+    let minter_ownership: Ownership<Addr> = from_json(
+        contract
+            .query(
+                deps.as_ref(),
+                mock_env(),
+                Cw721QueryMsg::GetMinterOwnership {},
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(minter_ownership.owner, Some(Addr::unchecked(MINTER_ADDR)));
+
+    // Old creator can not set withdraw address.
+    let err = contract
+        .set_withdraw_address(deps.as_mut(), &creator_info.sender, "foo".to_string())
         .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
 
-    // list the token_ids
-    let tokens = contract
-        .query_all_tokens(deps.as_ref(), env, None, None)
+    // New creator can set withdraw address.
+    contract
+        .set_withdraw_address(deps.as_mut(), &random_info.sender, "foo".to_string())
         .unwrap();
-    assert!(tokens.tokens.is_empty());
 }
 
 #[test]
-fn transferring_nft() {
+fn test_update_collection_info() {
     let mut deps = mock_dependencies();
     let contract = setup_contract(deps.as_mut());
 
-    // Mint a token
-    let token_id = "melt".to_string();
-    let token_uri = "https://www.merriam-webster.com/dictionary/melt".to_string();
-
-    let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id.clone(),
-        owner: String::from("venus"),
-        token_uri: Some(token_uri),
-        extension: None,
-    };
-
-    let minter = mock_info(MINTER_ADDR, &[]);
-    contract
-        .execute(deps.as_mut(), mock_env(), minter, mint_msg)
+    // no extension set on instantiate
+    let collection_info_and_extension = contract
+        .query_collection_info_and_extension(deps.as_ref())
         .unwrap();
-
-    // random cannot transfer
-    let random = mock_info("random", &[]);
-    let transfer_msg = Cw721ExecuteMsg::TransferNft {
-        recipient: String::from("random"),
-        token_id: token_id.clone(),
+    assert_eq!(collection_info_and_extension.extension, None);
+
+    let extension = CollectionInfoExtension {
+        description: "new description".to_string(),
+        image: "ipfs://new-image".to_string(),
+        external_link: None,
+        explicit_content: None,
+        start_trading_time: None,
+        royalty_info: Some(RoyaltyInfo {
+            payment_address: Addr::unchecked(CREATOR_ADDR),
+            share: Decimal::percent(MAX_ROYALTY_SHARE_PCT),
+        }),
+    };
+    let update_msg = Cw721ExecuteMsg::UpdateCollectionInfo {
+        collection_info: CollectionInfoMsg {
+            name: "new name".to_string(),
+            symbol: "NEW".to_string(),
+            extension: Some(extension.clone()),
+        },
     };
 
+    // Only creator can update collection info
+    let minter_info = mock_info(MINTER_ADDR, &[]);
     let err = contract
-        .execute(deps.as_mut(), mock_env(), random, transfer_msg)
+        .execute(deps.as_mut(), mock_env(), minter_info, update_msg.clone())
         .unwrap_err();
     assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
 
-    // owner can
-    let random = mock_info("venus", &[]);
-    let transfer_msg = Cw721ExecuteMsg::TransferNft {
-        recipient: String::from("random"),
-        token_id: token_id.clone(),
+    // extension is validated
+    let creator_info = mock_info(CREATOR_ADDR, &[]);
+    let invalid_msg = Cw721ExecuteMsg::UpdateCollectionInfo {
+        collection_info: CollectionInfoMsg {
+            name: "new name".to_string(),
+            symbol: "NEW".to_string(),
+            extension: Some(CollectionInfoExtension {
+                image: "no url".to_string(),
+                ..extension.clone()
+            }),
+        },
+    };
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), creator_info.clone(), invalid_msg)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::InvalidUrl {
+            url: "no url".to_string()
+        }
+    );
+    let invalid_msg = Cw721ExecuteMsg::UpdateCollectionInfo {
+        collection_info: CollectionInfoMsg {
+            name: "new name".to_string(),
+            symbol: "NEW".to_string(),
+            extension: Some(CollectionInfoExtension {
+                royalty_info: Some(RoyaltyInfo {
+                    payment_address: Addr::unchecked(CREATOR_ADDR),
+                    share: Decimal::percent(MAX_ROYALTY_SHARE_PCT + 1),
+                }),
+                ..extension.clone()
+            }),
+        },
     };
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), creator_info.clone(), invalid_msg)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::InvalidRoyaltyShare {
+            max_share: Decimal::percent(MAX_ROYALTY_SHARE_PCT)
+        }
+    );
 
-    let res = contract
-        .execute(deps.as_mut(), mock_env(), random, transfer_msg)
+    // creator updates collection info
+    contract
+        .execute(deps.as_mut(), mock_env(), creator_info, update_msg)
         .unwrap();
-
+    let collection_info_and_extension: CollectionInfoAndExtensionResponse<
+        DefaultOptionCollectionInfoExtension,
+    > = from_json(
+        contract
+            .query(
+                deps.as_ref(),
+                mock_env(),
+                Cw721QueryMsg::GetCollectionInfo {},
+            )
+            .unwrap(),
+    )
+    .unwrap();
     assert_eq!(
-        res,
-        Response::new()
-            .add_attribute("action", "transfer_nft")
-            .add_attribute("sender", "venus")
-            .add_attribute("recipient", "random")
-            .add_attribute("token_id", token_id)
+        collection_info_and_extension,
+        CollectionInfoAndExtensionResponse {
+            name: "new name".to_string(),
+            symbol: "NEW".to_string(),
+            extension: Some(extension),
+        }
+    );
+    let collection_info = contract
+        .query_collection_info(deps.as_ref(), mock_env())
+        .unwrap();
+    assert_eq!(
+        collection_info,
+        CollectionInfo {
+            name: "new name".to_string(),
+            symbol: "NEW".to_string(),
+        }
     );
 }
 
 #[test]
-fn sending_nft() {
+fn test_update_nft_info() {
     let mut deps = mock_dependencies();
     let contract = setup_contract(deps.as_mut());
 
-    // Mint a token
-    let token_id = "melt".to_string();
-    let token_uri = "https://www.merriam-webster.com/dictionary/melt".to_string();
-
+    let token_id = "grow".to_string();
     let mint_msg = Cw721ExecuteMsg::Mint {
         token_id: token_id.clone(),
-        owner: String::from("venus"),
-        token_uri: Some(token_uri),
+        owner: "medusa".to_string(),
+        token_uri: Some("https://www.merriam-webster.com/dictionary/grow".to_string()),
         extension: None,
     };
-
-    let minter = mock_info(MINTER_ADDR, &[]);
     contract
-        .execute(deps.as_mut(), mock_env(), minter, mint_msg)
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER_ADDR, &[]),
+            mint_msg,
+        )
         .unwrap();
 
-    let msg = to_json_binary("You now have the melting power").unwrap();
-    let target = String::from("another_contract");
-    let send_msg = Cw721ExecuteMsg::SendNft {
-        contract: target.clone(),
+    let extension = Some(Metadata {
+        description: Some("grows".to_string()),
+        ..Metadata::default()
+    });
+    let update_msg = Cw721ExecuteMsg::UpdateNftInfo {
         token_id: token_id.clone(),
-        msg: msg.clone(),
+        token_uri: None,
+        extension: Some(extension.clone()),
     };
 
-    let random = mock_info("random", &[]);
-    let err = contract
-        .execute(deps.as_mut(), mock_env(), random, send_msg.clone())
-        .unwrap_err();
-    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+    // only creator can update nft info, neither minter nor owner
+    for sender in [MINTER_ADDR, "medusa"] {
+        let err = contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(sender, &[]),
+                update_msg.clone(),
+            )
+            .unwrap_err();
+        assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+    }
 
-    // but owner can
-    let random = mock_info("venus", &[]);
+    // creator updates extension, token uri is unchanged
+    let creator_info = mock_info(CREATOR_ADDR, &[]);
     let res = contract
-        .execute(deps.as_mut(), mock_env(), random, send_msg)
+        .execute(deps.as_mut(), mock_env(), creator_info.clone(), update_msg)
         .unwrap();
-
-    let payload = Cw721ReceiveMsg {
-        sender: String::from("venus"),
-        token_id: token_id.clone(),
-        msg,
-    };
-    let expected = payload.into_cosmos_msg(target.clone()).unwrap();
-    // ensure expected serializes as we think it should
-    match &expected {
-        CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) => {
-            assert_eq!(contract_addr, &target)
+    let extension_attribute = to_json_string(&extension).unwrap();
+    assert_eq!(
+        res.events,
+        vec![Cw721Event::UpdateNftInfo {
+            sender: CREATOR_ADDR.to_string(),
+            token_id: token_id.clone(),
+            token_uri: None,
+            extension: Some(extension_attribute),
         }
-        m => panic!("Unexpected message type: {m:?}"),
-    }
-    // and make sure this is the request sent by the contract
+        .into()]
+    );
+    let nft_info = contract
+        .query_nft_info(deps.as_ref(), mock_env(), token_id.clone())
+        .unwrap();
     assert_eq!(
-        res,
-        Response::new()
-            .add_message(expected)
-            .add_attribute("action", "send_nft")
-            .add_attribute("sender", "venus")
-            .add_attribute("recipient", "another_contract")
-            .add_attribute("token_id", token_id)
+        nft_info,
+        NftInfoResponse {
+            token_uri: Some("https://www.merriam-webster.com/dictionary/grow".to_string()),
+            extension: extension.clone(),
+        }
     );
-}
-
-#[test]
-fn approving_revoking() {
-    let mut deps = mock_dependencies();
-    let contract = setup_contract(deps.as_mut());
-
-    // Mint a token
-    let token_id = "grow".to_string();
-    let token_uri = "https://www.merriam-webster.com/dictionary/grow".to_string();
-
-    let mint_msg = Cw721ExecuteMsg::Mint {
-        token_id: token_id.clone(),
-        owner: String::from("demeter"),
-        token_uri: Some(token_uri),
-        extension: None,
-    };
 
-    let minter = mock_info(MINTER_ADDR, &[]);
+    // empty token uri removes it
     contract
-        .execute(deps.as_mut(), mock_env(), minter, mint_msg)
-        .unwrap();
-
-    // token owner shows in approval query
-    let res = contract
-        .query_approval(
-            deps.as_ref(),
+        .execute(
+            deps.as_mut(),
             mock_env(),
-            token_id.clone(),
-            String::from("demeter"),
-            false,
+            creator_info.clone(),
+            Cw721ExecuteMsg::UpdateNftInfo {
+                token_id: token_id.clone(),
+                token_uri: Some("".to_string()),
+                extension: None,
+            },
         )
         .unwrap();
+    let nft_info = contract
+        .query_nft_info(deps.as_ref(), mock_env(), token_id.clone())
+        .unwrap();
     assert_eq!(
-        res,
-        ApprovalResponse {
-            approval: Approval {
-                spender: Addr::unchecked("demeter"),
-                expires: Expiration::Never {}
-            }
+        nft_info,
+        NftInfoResponse {
+            token_uri: None,
+            extension,
         }
     );
 
-    // Give random transferring power
-    let approve_msg = Cw721ExecuteMsg::Approve {
-        spender: String::from("random"),
-        token_id: token_id.clone(),
+    // token uri is validated like on mint
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            creator_info.clone(),
+            Cw721ExecuteMsg::UpdateNftInfo {
+                token_id: token_id.clone(),
+                token_uri: Some("ftp://www.merriam-webster.com/dictionary/grow".to_string()),
+                extension: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::UnsupportedUrlScheme {
+            url: "ftp://www.merriam-webster.com/dictionary/grow".to_string(),
+        }
+    );
+
+    // update of unknown token fails
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            creator_info,
+            Cw721ExecuteMsg::UpdateNftInfo {
+                token_id: "unknown".to_string(),
+                token_uri: Some("https://unknown.com".to_string()),
+                extension: None,
+            },
+        )
+        .unwrap_err();
+}
+
+#[test]
+fn test_mint_validates_token_uri() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER_ADDR, &[]),
+            Cw721ExecuteMsg::Mint {
+                token_id: "grow".to_string(),
+                owner: "medusa".to_string(),
+                token_uri: Some("ftp://www.merriam-webster.com/dictionary/grow".to_string()),
+                extension: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::UnsupportedUrlScheme {
+            url: "ftp://www.merriam-webster.com/dictionary/grow".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_extension_msg_is_rejected() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    // creator only, and rejected instead of silently succeeding
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER_ADDR, &[]),
+            Cw721ExecuteMsg::Extension { msg: Empty {} },
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR_ADDR, &[]),
+            Cw721ExecuteMsg::Extension { msg: Empty {} },
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::UnsupportedExtensionMsg {});
+}
+
+#[test]
+fn test_freeze_nft_info() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let minter_info = mock_info(MINTER_ADDR, &[]);
+    for token_id in ["1", "2"] {
+        contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                minter_info.clone(),
+                Cw721ExecuteMsg::Mint {
+                    token_id: token_id.to_string(),
+                    owner: "medusa".to_string(),
+                    token_uri: None,
+                    extension: None,
+                },
+            )
+            .unwrap();
+    }
+    let update_msg = |token_id: &str| Cw721ExecuteMsg::UpdateNftInfo {
+        token_id: token_id.to_string(),
+        token_uri: Some("https://www.merriam-webster.com/dictionary/freeze".to_string()),
+        extension: None,
+    };
+
+    // only creator can freeze
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            minter_info,
+            Cw721ExecuteMsg::FreezeNftInfo {
+                token_id: Some("1".to_string()),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+
+    // freeze single token
+    let creator_info = mock_info(CREATOR_ADDR, &[]);
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            creator_info.clone(),
+            Cw721ExecuteMsg::FreezeNftInfo {
+                token_id: Some("1".to_string()),
+            },
+        )
+        .unwrap();
+    assert!(contract
+        .query_is_nft_info_frozen(deps.as_ref(), Some("1".to_string()))
+        .unwrap());
+    assert!(!contract
+        .query_is_nft_info_frozen(deps.as_ref(), Some("2".to_string()))
+        .unwrap());
+    assert!(!contract
+        .query_is_nft_info_frozen(deps.as_ref(), None)
+        .unwrap());
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            creator_info.clone(),
+            update_msg("1"),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::NftInfoFrozen {
+            token_id: "1".to_string()
+        }
+    );
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            creator_info.clone(),
+            update_msg("2"),
+        )
+        .unwrap();
+
+    // freeze whole collection
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            creator_info.clone(),
+            Cw721ExecuteMsg::FreezeNftInfo { token_id: None },
+        )
+        .unwrap();
+    let frozen: bool = from_json(
+        contract
+            .query(
+                deps.as_ref(),
+                mock_env(),
+                Cw721QueryMsg::IsNftInfoFrozen { token_id: None },
+            )
+            .unwrap(),
+    )
+    .unwrap();
+    assert!(frozen);
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), creator_info, update_msg("2"))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::NftInfoFrozen {
+            token_id: "2".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_base_uri() {
+    let mut deps = mock_dependencies();
+    let contract = Cw721Contract::<
+        DefaultOptionMetadataExtension,
+        Empty,
+        Empty,
+        DefaultOptionCollectionInfoExtension,
+    >::default();
+    let base_uri = BaseUri {
+        uri: "ipfs://cid/".to_string(),
+        suffix: Some(".json".to_string()),
+    };
+    let msg = Cw721InstantiateMsg {
+        name: CONTRACT_NAME.to_string(),
+        symbol: SYMBOL.to_string(),
+        collection_info_extension: None,
+        creator: None,
+        minter: Some(String::from(MINTER_ADDR)),
+        withdraw_address: None,
+        max_batch_size: None,
+        max_supply: None,
+        token_id_policy: None,
+        allow_remint: None,
+        base_uri: Some(base_uri.clone()),
+        enable_snapshots: None,
+    };
+    contract
+        .instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR_ADDR, &[]),
+            msg,
+            "contract_name",
+            "contract_version",
+        )
+        .unwrap();
+    assert_eq!(
+        contract.query_base_uri(deps.as_ref()).unwrap(),
+        Some(base_uri)
+    );
+
+    // token uri is derived from base uri, unless token has its own
+    let minter_info = mock_info(MINTER_ADDR, &[]);
+    for (token_id, token_uri) in [("1", None), ("2", Some("https://example.com/2"))] {
+        contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                minter_info.clone(),
+                Cw721ExecuteMsg::Mint {
+                    token_id: token_id.to_string(),
+                    owner: "medusa".to_string(),
+                    token_uri: token_uri.map(String::from),
+                    extension: None,
+                },
+            )
+            .unwrap();
+    }
+    let res = contract
+        .query_nft_info(deps.as_ref(), mock_env(), "1".to_string())
+        .unwrap();
+    assert_eq!(res.token_uri, Some("ipfs://cid/1.json".to_string()));
+    let res = contract
+        .query_all_nft_infos(deps.as_ref(), mock_env(), None, None, false)
+        .unwrap();
+    let token_uris: Vec<_> = res
+        .nft_infos
+        .into_iter()
+        .map(|nft_info| nft_info.info.token_uri)
+        .collect();
+    assert_eq!(
+        token_uris,
+        vec![
+            Some("ipfs://cid/1.json".to_string()),
+            Some("https://example.com/2".to_string()),
+        ]
+    );
+
+    // only creator can update base uri, and only with a valid url
+    let update_msg = |uri: &str| Cw721ExecuteMsg::UpdateBaseUri {
+        base_uri: Some(BaseUri {
+            uri: uri.to_string(),
+            suffix: None,
+        }),
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            minter_info,
+            update_msg("ipfs://new-cid/"),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+    let creator_info = mock_info(CREATOR_ADDR, &[]);
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            creator_info.clone(),
+            update_msg("ftp://new-cid/"),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::UnsupportedUrlScheme {
+            url: "ftp://new-cid/".to_string()
+        }
+    );
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            creator_info.clone(),
+            update_msg("ipfs://new-cid/"),
+        )
+        .unwrap();
+    let res = contract
+        .query_all_nft_info(deps.as_ref(), mock_env(), "1".to_string(), false)
+        .unwrap();
+    assert_eq!(res.info.token_uri, Some("ipfs://new-cid/1".to_string()));
+
+    // frozen tokens keep their uri on base uri updates
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            creator_info.clone(),
+            Cw721ExecuteMsg::FreezeNftInfo {
+                token_id: Some("1".to_string()),
+            },
+        )
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            creator_info.clone(),
+            update_msg("ipfs://newer-cid/"),
+        )
+        .unwrap();
+    let res = contract
+        .query_nft_info(deps.as_ref(), mock_env(), "1".to_string())
+        .unwrap();
+    assert_eq!(res.token_uri, Some("ipfs://new-cid/1".to_string()));
+
+    // base uri can not be updated once the collection is frozen
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            creator_info.clone(),
+            Cw721ExecuteMsg::FreezeNftInfo { token_id: None },
+        )
+        .unwrap();
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            creator_info,
+            Cw721ExecuteMsg::UpdateBaseUri { base_uri: None },
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::BaseUriFrozen {});
+}
+
+#[test]
+fn test_reveal() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let placeholder_extension = Some(Metadata {
+        name: Some("Unrevealed".to_string()),
+        ..Metadata::default()
+    });
+    let placeholder_msg = Cw721ExecuteMsg::SetPlaceholder {
+        token_uri: Some("ipfs://placeholder.json".to_string()),
+        extension: placeholder_extension.clone(),
+        reveal_at: Some(Expiration::AtHeight(1234567)),
+    };
+
+    // only creator can set placeholder
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER_ADDR, &[]),
+            placeholder_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+    let creator_info = mock_info(CREATOR_ADDR, &[]);
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            creator_info.clone(),
+            placeholder_msg,
+        )
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER_ADDR, &[]),
+            Cw721ExecuteMsg::Mint {
+                token_id: "1".to_string(),
+                owner: "medusa".to_string(),
+                token_uri: None,
+                extension: Some(Metadata {
+                    name: Some("Rare".to_string()),
+                    ..Metadata::default()
+                }),
+            },
+        )
+        .unwrap();
+
+    // placeholder is returned until revealed
+    let placeholder_info = NftInfoResponse {
+        token_uri: Some("ipfs://placeholder.json".to_string()),
+        extension: placeholder_extension,
+    };
+    let res = contract
+        .query_nft_info(deps.as_ref(), mock_env(), "1".to_string())
+        .unwrap();
+    assert_eq!(res, placeholder_info);
+    let res = contract
+        .query_all_nft_info(deps.as_ref(), mock_env(), "1".to_string(), false)
+        .unwrap();
+    assert_eq!(res.info, placeholder_info);
+    let res = contract
+        .query_all_nft_infos(deps.as_ref(), mock_env(), None, None, false)
+        .unwrap();
+    assert_eq!(res.nft_infos[0].info, placeholder_info);
+
+    // revealed automatically once reveal_at expires
+    let mut late_env = mock_env();
+    late_env.block.height = 1234568; //expired
+    let res = contract
+        .query_nft_info(deps.as_ref(), late_env, "1".to_string())
+        .unwrap();
+    assert_eq!(res.extension.unwrap().name, Some("Rare".to_string()));
+
+    // creator reveals and sets base uri
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            creator_info.clone(),
+            Cw721ExecuteMsg::Reveal {
+                base_uri: Some(BaseUri {
+                    uri: "ipfs://cid/".to_string(),
+                    suffix: None,
+                }),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        contract
+            .query_placeholder(deps.as_ref(), mock_env())
+            .unwrap(),
+        None
+    );
+    let res = contract
+        .query_nft_info(deps.as_ref(), mock_env(), "1".to_string())
+        .unwrap();
+    assert_eq!(res.token_uri, Some("ipfs://cid/1".to_string()));
+    assert_eq!(res.extension.unwrap().name, Some("Rare".to_string()));
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            creator_info,
+            Cw721ExecuteMsg::Reveal { base_uri: None },
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::AlreadyRevealed {});
+}
+
+#[test]
+fn burning() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let token_id = "petrify".to_string();
+    let token_uri = "https://www.merriam-webster.com/dictionary/petrify".to_string();
+
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: token_id.clone(),
+        owner: MINTER_ADDR.to_string(),
+        token_uri: Some(token_uri),
+        extension: None,
+    };
+
+    let burn_msg = Cw721ExecuteMsg::Burn { token_id };
+
+    // mint some NFT
+    let allowed = mock_info(MINTER_ADDR, &[]);
+    let _ = contract
+        .execute(deps.as_mut(), mock_env(), allowed.clone(), mint_msg)
+        .unwrap();
+
+    // random not allowed to burn
+    let random = mock_info("random", &[]);
+    let env = mock_env();
+    let err = contract
+        .execute(deps.as_mut(), env.clone(), random, burn_msg.clone())
+        .unwrap_err();
+
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+
+    let _ = contract
+        .execute(deps.as_mut(), env.clone(), allowed, burn_msg)
+        .unwrap();
+
+    // ensure num tokens decreases
+    let count = contract
+        .query_num_tokens(deps.as_ref(), env.clone())
+        .unwrap();
+    assert_eq!(0, count.count);
+
+    // trying to get nft returns error
+    let _ = contract
+        .query_nft_info(deps.as_ref(), env.clone(), "petrify".to_string())
+        .unwrap_err();
+
+    // list the token_ids
+    let tokens = contract
+        .query_all_tokens(deps.as_ref(), env, None, None, None, None)
+        .unwrap();
+    assert!(tokens.tokens.is_empty());
+}
+
+#[test]
+fn burned_tokens_can_not_be_reminted() {
+    for allow_remint in [None, Some(true)] {
+        let mut deps = mock_dependencies();
+        let contract = Cw721Contract::<
+            DefaultOptionMetadataExtension,
+            Empty,
+            Empty,
+            DefaultOptionCollectionInfoExtension,
+        >::default();
+        let msg = Cw721InstantiateMsg {
+            name: CONTRACT_NAME.to_string(),
+            symbol: SYMBOL.to_string(),
+            collection_info_extension: None,
+            creator: None,
+            minter: Some(String::from(MINTER_ADDR)),
+            withdraw_address: None,
+            max_batch_size: None,
+            max_supply: None,
+            token_id_policy: None,
+            allow_remint,
+            base_uri: None,
+            enable_snapshots: None,
+        };
+        contract
+            .instantiate(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(CREATOR_ADDR, &[]),
+                msg,
+                "contract_name",
+                "contract_version",
+            )
+            .unwrap();
+
+        let minter = mock_info(MINTER_ADDR, &[]);
+        let mint_msg = |token_id: &str| Cw721ExecuteMsg::Mint {
+            token_id: token_id.to_string(),
+            owner: String::from("medusa"),
+            token_uri: None,
+            extension: None,
+        };
+        for token_id in ["1", "2", "3"] {
+            contract
+                .execute(
+                    deps.as_mut(),
+                    mock_env(),
+                    minter.clone(),
+                    mint_msg(token_id),
+                )
+                .unwrap();
+        }
+        contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("medusa", &[]),
+                Cw721ExecuteMsg::BatchBurn {
+                    token_ids: vec!["1".to_string(), "3".to_string()],
+                },
+            )
+            .unwrap();
+
+        // burned ids are recorded
+        assert!(contract
+            .query_is_burned(deps.as_ref(), mock_env(), "1".to_string())
+            .unwrap());
+        assert!(!contract
+            .query_is_burned(deps.as_ref(), mock_env(), "2".to_string())
+            .unwrap());
+        let res = contract
+            .query_burned_tokens(deps.as_ref(), mock_env(), None, Some(1))
+            .unwrap();
+        assert_eq!(res.tokens, vec!["1".to_string()]);
+        assert_eq!(res.next, Some("1".to_string()));
+        let res = contract
+            .query_burned_tokens(deps.as_ref(), mock_env(), res.next, None)
+            .unwrap();
+        assert_eq!(res.tokens, vec!["3".to_string()]);
+        assert_eq!(res.next, None);
+        let res = contract
+            .query_total_minted(deps.as_ref(), mock_env())
+            .unwrap();
+        assert_eq!(
+            res,
+            TotalMintedResponse {
+                total_minted: 3,
+                total_burned: 2,
+            }
+        );
+
+        // next sequential id skips burned ids
+        let supply = contract.query_supply(deps.as_ref(), mock_env()).unwrap();
+        assert_eq!(supply.next_token_id, 4);
+
+        let res = contract.execute(deps.as_mut(), mock_env(), minter.clone(), mint_msg("1"));
+        match allow_remint {
+            None => {
+                assert_eq!(
+                    res.unwrap_err(),
+                    Cw721ContractError::TokenBurned {
+                        token_id: "1".to_string()
+                    }
+                );
+            }
+            Some(_) => {
+                // reminted token is not burned anymore
+                res.unwrap();
+                assert!(!contract
+                    .query_is_burned(deps.as_ref(), mock_env(), "1".to_string())
+                    .unwrap());
+                let res = contract
+                    .query_total_minted(deps.as_ref(), mock_env())
+                    .unwrap();
+                assert_eq!(
+                    res,
+                    TotalMintedResponse {
+                        total_minted: 4,
+                        total_burned: 2,
+                    }
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn recording_provenance() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let mint_msg = |token_id: &str| Cw721ExecuteMsg::Mint {
+        token_id: token_id.to_string(),
+        owner: String::from("medusa"),
+        token_uri: None,
+        extension: None,
+    };
+
+    // nothing is recorded by default
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER_ADDR, &[]),
+            mint_msg("1"),
+        )
+        .unwrap();
+    let res = contract
+        .query_token_history(deps.as_ref(), mock_env(), "1".to_string(), None, None)
+        .unwrap();
+    assert_eq!(res.entries, vec![]);
+
+    // only creator can set the cap
+    let cap_msg = Cw721ExecuteMsg::UpdateProvenanceCap {
+        max_entries: Some(3),
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER_ADDR, &[]),
+            cap_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR_ADDR, &[]),
+            cap_msg,
+        )
+        .unwrap();
+
+    // mint, transfer, send and burn are recorded
+    let env = mock_env();
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(MINTER_ADDR, &[]),
+            mint_msg("2"),
+        )
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("medusa", &[]),
+            Cw721ExecuteMsg::TransferNft {
+                recipient: String::from("venus"),
+                token_id: "2".to_string(),
+            },
+        )
+        .unwrap();
+    let entry =
+        |id: u64, from: Option<&str>, to: Option<&str>, action: ProvenanceAction| ProvenanceEntry {
+            id,
+            height: env.block.height,
+            time: env.block.time,
+            from: from.map(Addr::unchecked),
+            to: to.map(Addr::unchecked),
+            action,
+        };
+    let res = contract
+        .query_token_history(deps.as_ref(), env.clone(), "2".to_string(), None, None)
+        .unwrap();
+    assert_eq!(
+        res.entries,
+        vec![
+            entry(0, None, Some("medusa"), ProvenanceAction::Mint),
+            entry(1, Some("medusa"), Some("venus"), ProvenanceAction::Transfer),
+        ]
+    );
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("venus", &[]),
+            Cw721ExecuteMsg::SendNft {
+                contract: String::from("another_contract"),
+                token_id: "2".to_string(),
+                msg: to_json_binary("my msg").unwrap(),
+            },
+        )
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("another_contract", &[]),
+            Cw721ExecuteMsg::Burn {
+                token_id: "2".to_string(),
+            },
+        )
+        .unwrap();
+
+    // oldest entries exceeding the cap are removed
+    let res = contract
+        .query_token_history(deps.as_ref(), env.clone(), "2".to_string(), None, Some(2))
+        .unwrap();
+    assert_eq!(
+        res.entries,
+        vec![
+            entry(1, Some("medusa"), Some("venus"), ProvenanceAction::Transfer),
+            entry(
+                2,
+                Some("venus"),
+                Some("another_contract"),
+                ProvenanceAction::Send
+            ),
+        ]
+    );
+    assert_eq!(res.next, Some(2));
+    let res = contract
+        .query_token_history(deps.as_ref(), env.clone(), "2".to_string(), Some(2), None)
+        .unwrap();
+    assert_eq!(
+        res.entries,
+        vec![entry(
+            3,
+            Some("another_contract"),
+            None,
+            ProvenanceAction::Burn
+        )]
+    );
+    assert_eq!(res.next, None);
+
+    // removing the cap stops recording
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(CREATOR_ADDR, &[]),
+            Cw721ExecuteMsg::UpdateProvenanceCap { max_entries: None },
+        )
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("medusa", &[]),
+            Cw721ExecuteMsg::TransferNft {
+                recipient: String::from("venus"),
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+    let res = contract
+        .query_token_history(deps.as_ref(), env, "1".to_string(), None, None)
+        .unwrap();
+    assert_eq!(res.entries, vec![]);
+}
+
+#[test]
+fn ownership_snapshots() {
+    let mut deps = mock_dependencies();
+    let contract = Cw721Contract::<
+        DefaultOptionMetadataExtension,
+        Empty,
+        Empty,
+        DefaultOptionCollectionInfoExtension,
+    >::default();
+    let msg = Cw721InstantiateMsg {
+        name: CONTRACT_NAME.to_string(),
+        symbol: SYMBOL.to_string(),
+        collection_info_extension: None,
+        creator: None,
+        minter: Some(String::from(MINTER_ADDR)),
+        withdraw_address: None,
+        max_batch_size: None,
+        max_supply: None,
+        token_id_policy: None,
+        allow_remint: None,
+        base_uri: None,
+        enable_snapshots: Some(true),
+    };
+    let mut env = mock_env();
+    let start_height = env.block.height;
+    contract
+        .instantiate(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(CREATOR_ADDR, &[]),
+            msg,
+            "contract_name",
+            "contract_version",
+        )
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(MINTER_ADDR, &[]),
+            Cw721ExecuteMsg::Mint {
+                token_id: "1".to_string(),
+                owner: String::from("medusa"),
+                token_uri: None,
+                extension: None,
+            },
+        )
+        .unwrap();
+    env.block.height += 10;
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("medusa", &[]),
+            Cw721ExecuteMsg::TransferNft {
+                recipient: String::from("venus"),
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+    env.block.height += 10;
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("venus", &[]),
+            Cw721ExecuteMsg::Burn {
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+
+    // snapshots return the state at the beginning of the given block
+    let owner_at = |height: u64| {
+        contract
+            .query_owner_of_at_height(deps.as_ref(), env.clone(), "1".to_string(), height)
+            .unwrap()
+            .owner
+    };
+    let balance_at = |owner: &str, height: u64| {
+        contract
+            .query_balance_of_at_height(deps.as_ref(), env.clone(), owner.to_string(), height)
+            .unwrap()
+            .balance
+    };
+    assert_eq!(owner_at(start_height), None);
+    assert_eq!(owner_at(start_height + 1), Some("medusa".to_string()));
+    assert_eq!(owner_at(start_height + 10), Some("medusa".to_string()));
+    assert_eq!(owner_at(start_height + 11), Some("venus".to_string()));
+    assert_eq!(owner_at(start_height + 21), None);
+    assert_eq!(balance_at("medusa", start_height), 0);
+    assert_eq!(balance_at("medusa", start_height + 1), 1);
+    assert_eq!(balance_at("medusa", start_height + 11), 0);
+    assert_eq!(balance_at("venus", start_height + 11), 1);
+    assert_eq!(balance_at("venus", start_height + 21), 0);
+
+    // no snapshots before they have been enabled
+    let err = contract
+        .query_owner_of_at_height(
+            deps.as_ref(),
+            env.clone(),
+            "1".to_string(),
+            start_height - 1,
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err(format!("No snapshots before height {start_height}"))
+    );
+
+    // snapshots are disabled by default
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let err = contract
+        .query_balance_of_at_height(
+            deps.as_ref(),
+            mock_env(),
+            "medusa".to_string(),
+            start_height,
+        )
+        .unwrap_err();
+    assert_eq!(err, StdError::generic_err("Snapshots are not enabled"));
+}
+
+#[test]
+fn renting_nfts() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let mut env = mock_env();
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(MINTER_ADDR, &[]),
+            Cw721ExecuteMsg::Mint {
+                token_id: "1".to_string(),
+                owner: String::from("medusa"),
+                token_uri: None,
+                extension: None,
+            },
+        )
+        .unwrap();
+    let expires = Expiration::AtHeight(env.block.height + 10);
+    let set_user_msg = Cw721ExecuteMsg::SetUser {
+        token_id: "1".to_string(),
+        user: Some(String::from("gamer")),
+        expires: Some(expires),
+    };
+
+    // only those allowed to send the token can set a user
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("random", &[]),
+            set_user_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("medusa", &[]),
+            Cw721ExecuteMsg::Approve {
+                spender: String::from("marketplace"),
+                token_id: "1".to_string(),
+                expires: None,
+            },
+        )
+        .unwrap();
+    // locked tokens can still be rented
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("medusa", &[]),
+            Cw721ExecuteMsg::LockNft {
+                token_id: "1".to_string(),
+                until: Expiration::AtHeight(env.block.height + 100),
+            },
+        )
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("marketplace", &[]),
+            set_user_msg.clone(),
+        )
+        .unwrap();
+    let res = contract
+        .query_user_of(deps.as_ref(), env.clone(), "1".to_string())
+        .unwrap();
+    assert_eq!(
+        res.user,
+        Some(NftUser {
+            user: Addr::unchecked("gamer"),
+            expires,
+        })
+    );
+    let res = contract
+        .query_tokens_of_user(deps.as_ref(), env.clone(), "gamer".to_string(), None, None)
+        .unwrap();
+    assert_eq!(res.tokens, vec!["1".to_string()]);
+
+    // expired users are absent
+    env.block.height += 10;
+    let res = contract
+        .query_user_of(deps.as_ref(), env.clone(), "1".to_string())
+        .unwrap();
+    assert_eq!(res.user, None);
+    let res = contract
+        .query_tokens_of_user(deps.as_ref(), env.clone(), "gamer".to_string(), None, None)
+        .unwrap();
+    assert_eq!(res.tokens, Vec::<String>::new());
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("medusa", &[]),
+            set_user_msg,
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Expired {});
+
+    // user is removed on transfer
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("medusa", &[]),
+            Cw721ExecuteMsg::SetUser {
+                token_id: "1".to_string(),
+                user: Some(String::from("gamer")),
+                expires: None,
+            },
+        )
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("medusa", &[]),
+            Cw721ExecuteMsg::UnlockNft {
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("medusa", &[]),
+            Cw721ExecuteMsg::TransferNft {
+                recipient: String::from("venus"),
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+    let res = contract
+        .query_user_of(deps.as_ref(), env.clone(), "1".to_string())
+        .unwrap();
+    assert_eq!(res.user, None);
+    let res = contract
+        .query_tokens_of_user(deps.as_ref(), env, "gamer".to_string(), None, None)
+        .unwrap();
+    assert_eq!(res.tokens, Vec::<String>::new());
+}
+
+/// Humanizes canonical addresses derived from public keys as hex, since `MockApi` only
+/// humanizes addresses it canonicalized itself.
+struct PermitApi(MockApi);
+
+impl Api for PermitApi {
+    fn addr_validate(&self, human: &str) -> StdResult<Addr> {
+        self.0.addr_validate(human)
+    }
+
+    fn addr_canonicalize(&self, human: &str) -> StdResult<CanonicalAddr> {
+        self.0.addr_canonicalize(human)
+    }
+
+    fn addr_humanize(&self, canonical: &CanonicalAddr) -> StdResult<Addr> {
+        if canonical.len() != 20 {
+            return self.0.addr_humanize(canonical);
+        }
+        let hex: String = canonical
+            .as_slice()
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect();
+        Ok(Addr::unchecked(hex))
+    }
+
+    fn secp256k1_verify(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        public_key: &[u8],
+    ) -> Result<bool, VerificationError> {
+        self.0.secp256k1_verify(message_hash, signature, public_key)
+    }
+
+    fn secp256k1_recover_pubkey(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        recovery_param: u8,
+    ) -> Result<Vec<u8>, RecoverPubkeyError> {
+        self.0
+            .secp256k1_recover_pubkey(message_hash, signature, recovery_param)
+    }
+
+    fn ed25519_verify(
+        &self,
+        message: &[u8],
+        signature: &[u8],
+        public_key: &[u8],
+    ) -> Result<bool, VerificationError> {
+        self.0.ed25519_verify(message, signature, public_key)
+    }
+
+    fn ed25519_batch_verify(
+        &self,
+        messages: &[&[u8]],
+        signatures: &[&[u8]],
+        public_keys: &[&[u8]],
+    ) -> Result<bool, VerificationError> {
+        self.0
+            .ed25519_batch_verify(messages, signatures, public_keys)
+    }
+
+    fn debug(&self, message: &str) {
+        self.0.debug(message)
+    }
+}
+
+#[test]
+fn approving_with_permit() {
+    // signatures are created off-chain for the mock env's chain id and contract address
+    const OWNER_PUBKEY: &str = "ArtQ4tiaTtcGY9CAZZ/grUubw+BsF6InQzlmy1nO7gIN";
+    const OWNER_ADDR: &str = "a390bb4d6d4ab570767ef21f66c3edc1a4d69026";
+    const TOKEN_SIGNATURE: &str =
+        "wd+Qzb6zLZYO4RDxu/nCvO+vyBgb1brV+m18VBKIW3AbbBqFapNk25mL5pSv2evoH4OjrkMZ4CfFlt6ABHgPGA==";
+    const OPERATOR_SIGNATURE: &str =
+        "BZX6Hdvj1N3ctfRySiVWjzQIQqjJsreFTL5wqdurDW4wDbjZpgSSbtoH4pHdxKs8DFWI64YSuq77FcEZxf39RA==";
+
+    let mut deps = OwnedDeps {
+        storage: MockStorage::default(),
+        api: PermitApi(MockApi::default()),
+        querier: MockQuerier::<Empty>::new(&[]),
+        custom_query_type: PhantomData,
+    };
+    let contract = setup_contract(deps.as_mut());
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER_ADDR, &[]),
+            Cw721ExecuteMsg::Mint {
+                token_id: "1".to_string(),
+                owner: OWNER_ADDR.to_string(),
+                token_uri: None,
+                extension: None,
+            },
+        )
+        .unwrap();
+    let permit_msg = |token_id: Option<&str>, spender: &str, nonce: u64, signature: &str| {
+        Cw721ExecuteMsg::Permit {
+            owner_pubkey: Binary::from_base64(OWNER_PUBKEY).unwrap(),
+            token_id: token_id.map(str::to_string),
+            spender: spender.to_string(),
+            expires: None,
+            nonce,
+            signature: Binary::from_base64(signature).unwrap(),
+        }
+    };
+
+    // anyone can submit a permit signed by the owner
+    let relayer = mock_info("relayer", &[]);
+    let token_permit = permit_msg(Some("1"), "marketplace", 0, TOKEN_SIGNATURE);
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            relayer.clone(),
+            token_permit.clone(),
+        )
+        .unwrap();
+    let res = contract
+        .query_approval(
+            deps.as_ref(),
+            mock_env(),
+            "1".to_string(),
+            "marketplace".to_string(),
+            false,
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        ApprovalResponse {
+            approval: Approval {
+                spender: Addr::unchecked("marketplace"),
+                expires: Expiration::Never {}
+            }
+        }
+    );
+
+    // permits can not be replayed
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), relayer.clone(), token_permit)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::InvalidPermitNonce {
+            expected: 1,
+            nonce: 0
+        }
+    );
+
+    // signature must match the permit
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            relayer.clone(),
+            permit_msg(None, "thief", 1, OPERATOR_SIGNATURE),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::InvalidPermitSignature {});
+
+    // only compressed pubkeys are accepted
+    let mut uncompressed_permit = permit_msg(None, "operator", 1, OPERATOR_SIGNATURE);
+    if let Cw721ExecuteMsg::Permit { owner_pubkey, .. } = &mut uncompressed_permit {
+        *owner_pubkey = Binary::from(vec![4u8; 65]);
+    }
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            relayer.clone(),
+            uncompressed_permit,
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::InvalidPermitPubkey { length: 65 });
+
+    // without token id, spender is approved as operator
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            relayer,
+            permit_msg(None, "operator", 1, OPERATOR_SIGNATURE),
+        )
+        .unwrap();
+    contract
+        .query_operator(
+            deps.as_ref(),
+            mock_env(),
+            OWNER_ADDR.to_string(),
+            "operator".to_string(),
+            false,
+        )
+        .unwrap();
+    let nonce = contract
+        .query_permit_nonce(deps.as_ref(), mock_env(), OWNER_ADDR.to_string())
+        .unwrap();
+    assert_eq!(nonce, 2);
+}
+
+#[test]
+fn using_custom_storage_keys() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    // second collection shares the storage of the default one
+    let contract = setup_contract(deps.as_mut());
+    let second_contract = Cw721Contract::<
+        DefaultOptionMetadataExtension,
+        Empty,
+        Empty,
+        DefaultOptionCollectionInfoExtension,
+    > {
+        config: Cw721Config::new(cw721_storage_keys!("second_")),
+    };
+    second_contract
+        .instantiate(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(CREATOR_ADDR, &[]),
+            Cw721InstantiateMsg {
+                name: "Second".to_string(),
+                symbol: "SCD".to_string(),
+                collection_info_extension: None,
+                creator: None,
+                minter: Some("second_minter".to_string()),
+                withdraw_address: None,
+                max_batch_size: None,
+                max_supply: None,
+                token_id_policy: None,
+                allow_remint: None,
+                base_uri: None,
+                enable_snapshots: None,
+            },
+            "contract_name",
+            "contract_version",
+        )
+        .unwrap();
+    assert_eq!(
+        contract
+            .query_collection_info(deps.as_ref(), env.clone())
+            .unwrap()
+            .name,
+        CONTRACT_NAME
+    );
+    assert_eq!(
+        second_contract
+            .query_collection_info(deps.as_ref(), env.clone())
+            .unwrap()
+            .name,
+        "Second"
+    );
+
+    // minter of one collection can not mint in the other
+    let mint_msg = |owner: &str| Cw721ExecuteMsg::Mint {
+        token_id: "1".to_string(),
+        owner: owner.to_string(),
+        token_uri: None,
+        extension: None,
+    };
+    let err = second_contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(MINTER_ADDR, &[]),
+            mint_msg("alice"),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+
+    // same token id can be minted in both collections
+    contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(MINTER_ADDR, &[]),
+            mint_msg("alice"),
+        )
+        .unwrap();
+    second_contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("second_minter", &[]),
+            mint_msg("bob"),
+        )
+        .unwrap();
+
+    // transfers only affect their own collection
+    second_contract
+        .execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("bob", &[]),
+            Cw721ExecuteMsg::TransferNft {
+                recipient: "carl".to_string(),
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+    let owner = contract
+        .query_owner_of(deps.as_ref(), env.clone(), "1".to_string(), false)
+        .unwrap()
+        .owner;
+    assert_eq!(owner, "alice");
+    let owner = second_contract
+        .query_owner_of(deps.as_ref(), env.clone(), "1".to_string(), false)
+        .unwrap()
+        .owner;
+    assert_eq!(owner, "carl");
+    let count = contract
+        .query_num_tokens(deps.as_ref(), env.clone())
+        .unwrap()
+        .count;
+    assert_eq!(count, 1);
+    let tokens = second_contract
+        .query_tokens(
+            deps.as_ref(),
+            env,
+            "alice".to_string(),
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+    assert!(tokens.tokens.is_empty());
+}
+
+#[test]
+fn batch_minting_and_burning() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let mints: Vec<_> = ["1", "2", "3"]
+        .iter()
+        .map(|token_id| MintMsg {
+            token_id: token_id.to_string(),
+            owner: "medusa".to_string(),
+            token_uri: None,
+            extension: None,
+        })
+        .collect();
+
+    // only minter can batch mint
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("random", &[]),
+            Cw721ExecuteMsg::BatchMint {
+                mints: mints.clone(),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+
+    let minter_info = mock_info(MINTER_ADDR, &[]);
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            minter_info,
+            Cw721ExecuteMsg::BatchMint { mints },
+        )
+        .unwrap();
+    assert_eq!(
+        Cw721Event::from_events(&res.events),
+        ["1", "2", "3"]
+            .iter()
+            .map(|token_id| Cw721Event::Mint {
+                minter: MINTER_ADDR.to_string(),
+                owner: "medusa".to_string(),
+                token_id: token_id.to_string(),
+            })
+            .collect::<Vec<_>>()
+    );
+    let count = contract
+        .query_num_tokens(deps.as_ref(), mock_env())
+        .unwrap();
+    assert_eq!(3, count.count);
+
+    // already minted token ids are rejected
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER_ADDR, &[]),
+            Cw721ExecuteMsg::BatchMint {
+                mints: vec![MintMsg {
+                    token_id: "2".to_string(),
+                    owner: "medusa".to_string(),
+                    token_uri: None,
+                    extension: None,
+                }],
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Claimed {});
+
+    // random can not burn, no token is burned
+    let burn_msg = Cw721ExecuteMsg::BatchBurn {
+        token_ids: vec!["1".to_string(), "3".to_string()],
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("random", &[]),
+            burn_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("medusa", &[]),
+            burn_msg,
+        )
+        .unwrap();
+    let count = contract
+        .query_num_tokens(deps.as_ref(), mock_env())
+        .unwrap();
+    assert_eq!(1, count.count);
+    let tokens = contract
+        .query_all_tokens(deps.as_ref(), mock_env(), None, None, None, None)
+        .unwrap();
+    assert_eq!(tokens.tokens, vec!["2".to_string()]);
+}
+
+#[test]
+fn batch_transferring_approving_and_revoking() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let mints = ["1", "2", "3"]
+        .iter()
+        .map(|token_id| MintMsg {
+            token_id: token_id.to_string(),
+            owner: "medusa".to_string(),
+            token_uri: None,
+            extension: None,
+        })
+        .collect();
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER_ADDR, &[]),
+            Cw721ExecuteMsg::BatchMint { mints },
+        )
+        .unwrap();
+
+    // approve spender for two tokens, then revoke one
+    let owner_info = mock_info("medusa", &[]);
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            owner_info.clone(),
+            Cw721ExecuteMsg::BatchApprove {
+                spender: "spender".to_string(),
+                token_ids: vec!["1".to_string(), "2".to_string()],
+                expires: None,
+            },
+        )
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            owner_info,
+            Cw721ExecuteMsg::BatchRevoke {
+                spender: "spender".to_string(),
+                token_ids: vec!["2".to_string()],
+            },
+        )
+        .unwrap();
+
+    // spender can not transfer revoked token
+    let spender_info = mock_info("spender", &[]);
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            spender_info.clone(),
+            Cw721ExecuteMsg::BatchTransferNft {
+                recipient: "random".to_string(),
+                token_ids: vec!["2".to_string(), "1".to_string()],
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            spender_info,
+            Cw721ExecuteMsg::BatchTransferNft {
+                recipient: "random".to_string(),
+                token_ids: vec!["1".to_string()],
+            },
+        )
+        .unwrap();
+    let tokens = contract
+        .query_tokens(
+            deps.as_ref(),
+            mock_env(),
+            "random".to_string(),
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+    assert_eq!(tokens.tokens, vec!["1".to_string()]);
+}
+
+#[test]
+fn batch_size_limits() {
+    let mut deps = mock_dependencies();
+    let contract = Cw721Contract::<
+        DefaultOptionMetadataExtension,
+        Empty,
+        Empty,
+        DefaultOptionCollectionInfoExtension,
+    >::default();
+    let msg = Cw721InstantiateMsg {
+        name: CONTRACT_NAME.to_string(),
+        symbol: SYMBOL.to_string(),
+        collection_info_extension: None,
+        creator: None,
+        minter: Some(String::from(MINTER_ADDR)),
+        withdraw_address: None,
+        max_batch_size: Some(2),
+        max_supply: None,
+        token_id_policy: None,
+        allow_remint: None,
+        base_uri: None,
+        enable_snapshots: None,
+    };
+    contract
+        .instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR_ADDR, &[]),
+            msg,
+            "contract_name",
+            "contract_version",
+        )
+        .unwrap();
+
+    let minter_info = mock_info(MINTER_ADDR, &[]);
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            minter_info.clone(),
+            Cw721ExecuteMsg::BatchMint { mints: vec![] },
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::EmptyBatch {});
+
+    let mints: Vec<_> = ["1", "2", "3"]
+        .iter()
+        .map(|token_id| MintMsg {
+            token_id: token_id.to_string(),
+            owner: "medusa".to_string(),
+            token_uri: None,
+            extension: None,
+        })
+        .collect();
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            minter_info,
+            Cw721ExecuteMsg::BatchMint { mints },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::BatchSizeExceeded { max_batch_size: 2 }
+    );
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("medusa", &[]),
+            Cw721ExecuteMsg::BatchBurn {
+                token_ids: vec!["1".to_string(), "2".to_string(), "3".to_string()],
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::BatchSizeExceeded { max_batch_size: 2 }
+    );
+}
+
+#[test]
+fn minting_next_up_to_max_supply() {
+    let mut deps = mock_dependencies();
+    let contract = Cw721Contract::<
+        DefaultOptionMetadataExtension,
+        Empty,
+        Empty,
+        DefaultOptionCollectionInfoExtension,
+    >::default();
+    let msg = Cw721InstantiateMsg {
+        name: CONTRACT_NAME.to_string(),
+        symbol: SYMBOL.to_string(),
+        collection_info_extension: None,
+        creator: None,
+        minter: Some(String::from(MINTER_ADDR)),
+        withdraw_address: None,
+        max_batch_size: None,
+        max_supply: Some(4),
+        token_id_policy: None,
+        allow_remint: None,
+        base_uri: None,
+        enable_snapshots: None,
+    };
+    contract
+        .instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR_ADDR, &[]),
+            msg,
+            "contract_name",
+            "contract_version",
+        )
+        .unwrap();
+
+    let supply = contract.query_supply(deps.as_ref(), mock_env()).unwrap();
+    assert_eq!(
+        supply,
+        SupplyResponse {
+            num_tokens: 0,
+            max_supply: Some(4),
+            next_token_id: 1,
+        }
+    );
+
+    let minter_info = mock_info(MINTER_ADDR, &[]);
+    let mint_next_msg = Cw721ExecuteMsg::MintNext {
+        owner: String::from("medusa"),
+        token_uri: None,
+        extension: None,
+    };
+
+    // random cannot mint next
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("random", &[]),
+            mint_next_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+
+    // minter mints sequential token ids
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            minter_info.clone(),
+            mint_next_msg.clone(),
+        )
+        .unwrap();
+    let res = contract
+        .query_owner_of(deps.as_ref(), mock_env(), "1".to_string(), false)
+        .unwrap();
+    assert_eq!(res.owner, "medusa");
+
+    // ids already claimed by a regular mint are skipped
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: "2".to_string(),
+        owner: String::from("venus"),
+        token_uri: None,
+        extension: None,
+    };
+    contract
+        .execute(deps.as_mut(), mock_env(), minter_info.clone(), mint_msg)
+        .unwrap();
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            minter_info.clone(),
+            mint_next_msg.clone(),
+        )
+        .unwrap();
+    assert_eq!(
+        Cw721Event::from_events(&res.events),
+        vec![Cw721Event::Mint {
+            minter: MINTER_ADDR.to_string(),
+            owner: "medusa".to_string(),
+            token_id: "3".to_string(),
+        }]
+    );
+    let supply = contract.query_supply(deps.as_ref(), mock_env()).unwrap();
+    assert_eq!(
+        supply,
+        SupplyResponse {
+            num_tokens: 3,
+            max_supply: Some(4),
+            next_token_id: 4,
+        }
+    );
+
+    // batch mint must not exceed max supply
+    let mints: Vec<_> = ["a", "b"]
+        .iter()
+        .map(|token_id| MintMsg {
+            token_id: token_id.to_string(),
+            owner: "medusa".to_string(),
+            token_uri: None,
+            extension: None,
+        })
+        .collect();
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            minter_info.clone(),
+            Cw721ExecuteMsg::BatchMint { mints },
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::MaxSupplyReached { max_supply: 4 });
+
+    // last token can be minted, afterwards max supply is reached
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            minter_info.clone(),
+            mint_next_msg.clone(),
+        )
+        .unwrap();
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            minter_info.clone(),
+            mint_next_msg,
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::MaxSupplyReached { max_supply: 4 });
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: "a".to_string(),
+        owner: String::from("venus"),
+        token_uri: None,
+        extension: None,
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            minter_info.clone(),
+            mint_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::MaxSupplyReached { max_supply: 4 });
+
+    // burned tokens still count towards max supply
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("medusa", &[]),
+            Cw721ExecuteMsg::Burn {
+                token_id: "1".to_string(),
+            },
+        )
+        .unwrap();
+    let supply = contract.query_supply(deps.as_ref(), mock_env()).unwrap();
+    assert_eq!(supply.num_tokens, 3);
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), minter_info, mint_msg)
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::MaxSupplyReached { max_supply: 4 });
+}
+
+#[test]
+fn test_next_token_id_is_bounded() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let minter_info = mock_info(MINTER_ADDR, &[]);
+    let mint_msg = |token_id: u64| Cw721ExecuteMsg::Mint {
+        token_id: token_id.to_string(),
+        owner: String::from("medusa"),
+        token_uri: None,
+        extension: None,
+    };
+
+    // explicit numeric mints advance the cursor, so nothing needs to be skipped
+    for token_id in 1..=MAX_TOKEN_ID_SKIPS + 1 {
+        contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                minter_info.clone(),
+                mint_msg(token_id),
+            )
+            .unwrap();
+    }
+    let supply = contract.query_supply(deps.as_ref(), mock_env()).unwrap();
+    assert_eq!(supply.next_token_id, MAX_TOKEN_ID_SKIPS + 2);
+
+    // ids minted before the cursor was tracked are skipped, but not beyond the bound
+    contract.config.next_token_id.remove(deps.as_mut().storage);
+    let mint_next_msg = Cw721ExecuteMsg::MintNext {
+        owner: String::from("medusa"),
+        token_uri: None,
+        extension: None,
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            minter_info.clone(),
+            mint_next_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::Std(StdError::generic_err(format!(
+            "No free token id within {MAX_TOKEN_ID_SKIPS} ids after 1"
+        )))
+    );
+
+    // a higher explicit mint moves the cursor past them
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            minter_info.clone(),
+            mint_msg(200),
+        )
+        .unwrap();
+    let res = contract
+        .execute(deps.as_mut(), mock_env(), minter_info, mint_next_msg)
+        .unwrap();
+    assert_eq!(
+        Cw721Event::from_events(&res.events),
+        vec![Cw721Event::Mint {
+            minter: MINTER_ADDR.to_string(),
+            owner: "medusa".to_string(),
+            token_id: "201".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn validating_token_ids() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let minter_info = mock_info(MINTER_ADDR, &[]);
+    let mint = |deps: &mut cosmwasm_std::OwnedDeps<_, _, _>, token_id: String| {
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id,
+            owner: String::from("medusa"),
+            token_uri: None,
+            extension: None,
+        };
+        contract.execute(deps.as_mut(), mock_env(), minter_info.clone(), mint_msg)
+    };
+
+    // default policy rejects empty, too long and control characters
+    let err = mint(&mut deps, "".to_string()).unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::InvalidTokenId {
+            reason: "must not be empty".to_string()
+        }
+    );
+    let err = mint(
+        &mut deps,
+        "a".repeat(DEFAULT_MAX_TOKEN_ID_LENGTH as usize + 1),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::InvalidTokenId {
+            reason: format!("exceeds max length of {DEFAULT_MAX_TOKEN_ID_LENGTH}")
+        }
+    );
+    let err = mint(&mut deps, "grow\n".to_string()).unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::InvalidTokenId {
+            reason: "contains characters not allowed by charset".to_string()
+        }
+    );
+    mint(&mut deps, "a".repeat(DEFAULT_MAX_TOKEN_ID_LENGTH as usize)).unwrap();
+    mint(&mut deps, "Medusa's Gaze #1".to_string()).unwrap();
+
+    // numeric-only policy
+    let mut deps = mock_dependencies();
+    let msg = Cw721InstantiateMsg {
+        name: CONTRACT_NAME.to_string(),
+        symbol: SYMBOL.to_string(),
+        collection_info_extension: None,
+        creator: None,
+        minter: Some(String::from(MINTER_ADDR)),
+        withdraw_address: None,
+        max_batch_size: None,
+        max_supply: None,
+        token_id_policy: Some(TokenIdPolicy {
+            max_length: 4,
+            charset: TokenIdCharset::Numeric,
+        }),
+        allow_remint: None,
+        base_uri: None,
+        enable_snapshots: None,
+    };
+    contract
+        .instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR_ADDR, &[]),
+            msg,
+            "contract_name",
+            "contract_version",
+        )
+        .unwrap();
+    let mints: Vec<_> = ["1", "two"]
+        .iter()
+        .map(|token_id| MintMsg {
+            token_id: token_id.to_string(),
+            owner: "medusa".to_string(),
+            token_uri: None,
+            extension: None,
+        })
+        .collect();
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            minter_info.clone(),
+            Cw721ExecuteMsg::BatchMint { mints },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::InvalidTokenId {
+            reason: "contains characters not allowed by charset".to_string()
+        }
+    );
+    let err = mint(&mut deps, "12345".to_string()).unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::InvalidTokenId {
+            reason: "exceeds max length of 4".to_string()
+        }
+    );
+    mint(&mut deps, "1234".to_string()).unwrap();
+}
+
+#[test]
+fn transferring_nft() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    // Mint a token
+    let token_id = "melt".to_string();
+    let token_uri = "https://www.merriam-webster.com/dictionary/melt".to_string();
+
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: token_id.clone(),
+        owner: String::from("venus"),
+        token_uri: Some(token_uri),
+        extension: None,
+    };
+
+    let minter = mock_info(MINTER_ADDR, &[]);
+    contract
+        .execute(deps.as_mut(), mock_env(), minter, mint_msg)
+        .unwrap();
+
+    // random cannot transfer
+    let random = mock_info("random", &[]);
+    let transfer_msg = Cw721ExecuteMsg::TransferNft {
+        recipient: String::from("random"),
+        token_id: token_id.clone(),
+    };
+
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), random, transfer_msg)
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+
+    // owner can
+    let random = mock_info("venus", &[]);
+    let transfer_msg = Cw721ExecuteMsg::TransferNft {
+        recipient: String::from("random"),
+        token_id: token_id.clone(),
+    };
+
+    let res = contract
+        .execute(deps.as_mut(), mock_env(), random, transfer_msg)
+        .unwrap();
+
+    assert_eq!(
+        res,
+        Response::new()
+            .add_attribute("action", "transfer_nft")
+            .add_event(
+                Event::new("transfer_nft")
+                    .add_attribute("sender", "venus")
+                    .add_attribute("recipient", "random")
+                    .add_attribute("token_id", token_id)
+            )
+    );
+}
+
+#[test]
+fn sending_nft() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    // Mint a token
+    let token_id = "melt".to_string();
+    let token_uri = "https://www.merriam-webster.com/dictionary/melt".to_string();
+
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: token_id.clone(),
+        owner: String::from("venus"),
+        token_uri: Some(token_uri),
+        extension: None,
+    };
+
+    let minter = mock_info(MINTER_ADDR, &[]);
+    contract
+        .execute(deps.as_mut(), mock_env(), minter, mint_msg)
+        .unwrap();
+
+    let msg = to_json_binary("You now have the melting power").unwrap();
+    let target = String::from("another_contract");
+    let send_msg = Cw721ExecuteMsg::SendNft {
+        contract: target.clone(),
+        token_id: token_id.clone(),
+        msg: msg.clone(),
+    };
+
+    let random = mock_info("random", &[]);
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), random, send_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+
+    // but owner can
+    let random = mock_info("venus", &[]);
+    let res = contract
+        .execute(deps.as_mut(), mock_env(), random, send_msg)
+        .unwrap();
+
+    let payload = Cw721ReceiveMsg {
+        sender: String::from("venus"),
+        token_id: token_id.clone(),
+        msg,
+    };
+    let expected = payload.into_cosmos_msg(target.clone()).unwrap();
+    // ensure expected serializes as we think it should
+    match &expected {
+        CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) => {
+            assert_eq!(contract_addr, &target)
+        }
+        m => panic!("Unexpected message type: {m:?}"),
+    }
+    // and make sure this is the request sent by the contract
+    assert_eq!(
+        res,
+        Response::new()
+            .add_message(expected)
+            .add_attribute("action", "send_nft")
+            .add_event(
+                Event::new("send_nft")
+                    .add_attribute("sender", "venus")
+                    .add_attribute("contract", "another_contract")
+                    .add_attribute("token_id", token_id)
+            )
+    );
+}
+
+#[test]
+fn approving_revoking() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    // Mint a token
+    let token_id = "grow".to_string();
+    let token_uri = "https://www.merriam-webster.com/dictionary/grow".to_string();
+
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: token_id.clone(),
+        owner: String::from("demeter"),
+        token_uri: Some(token_uri),
+        extension: None,
+    };
+
+    let minter = mock_info(MINTER_ADDR, &[]);
+    contract
+        .execute(deps.as_mut(), mock_env(), minter, mint_msg)
+        .unwrap();
+
+    // token owner shows in approval query
+    let res = contract
+        .query_approval(
+            deps.as_ref(),
+            mock_env(),
+            token_id.clone(),
+            String::from("demeter"),
+            false,
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        ApprovalResponse {
+            approval: Approval {
+                spender: Addr::unchecked("demeter"),
+                expires: Expiration::Never {}
+            }
+        }
+    );
+
+    // Give random transferring power
+    let approve_msg = Cw721ExecuteMsg::Approve {
+        spender: String::from("random"),
+        token_id: token_id.clone(),
+        expires: None,
+    };
+    let owner = mock_info("demeter", &[]);
+    let res = contract
+        .execute(deps.as_mut(), mock_env(), owner, approve_msg)
+        .unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_attribute("action", "approve")
+            .add_event(
+                Event::new("approve")
+                    .add_attribute("sender", "demeter")
+                    .add_attribute("spender", "random")
+                    .add_attribute("token_id", token_id.clone())
+            )
+    );
+
+    // test approval query
+    let res = contract
+        .query_approval(
+            deps.as_ref(),
+            mock_env(),
+            token_id.clone(),
+            String::from("random"),
+            true,
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        ApprovalResponse {
+            approval: Approval {
+                spender: Addr::unchecked("random"),
+                expires: Expiration::Never {}
+            }
+        }
+    );
+
+    // random can now transfer
+    let random = mock_info("random", &[]);
+    let transfer_msg = Cw721ExecuteMsg::TransferNft {
+        recipient: String::from("person"),
+        token_id: token_id.clone(),
+    };
+    contract
+        .execute(deps.as_mut(), mock_env(), random, transfer_msg)
+        .unwrap();
+
+    // Approvals are removed / cleared
+    let query_msg = Cw721QueryMsg::OwnerOf {
+        token_id: token_id.clone(),
+        include_expired: None,
+    };
+    let res: OwnerOfResponse = from_json(
+        contract
+            .query(deps.as_ref(), mock_env(), query_msg.clone())
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        OwnerOfResponse {
+            owner: String::from("person"),
+            approvals: vec![],
+        }
+    );
+
+    // Approve, revoke, and check for empty, to test revoke
+    let approve_msg = Cw721ExecuteMsg::Approve {
+        spender: String::from("random"),
+        token_id: token_id.clone(),
+        expires: None,
+    };
+    let owner = mock_info("person", &[]);
+    contract
+        .execute(deps.as_mut(), mock_env(), owner.clone(), approve_msg)
+        .unwrap();
+
+    let revoke_msg = Cw721ExecuteMsg::Revoke {
+        spender: String::from("random"),
+        token_id,
+    };
+    contract
+        .execute(deps.as_mut(), mock_env(), owner, revoke_msg)
+        .unwrap();
+
+    // Approvals are now removed / cleared
+    let res: OwnerOfResponse = from_json(
+        contract
+            .query(deps.as_ref(), mock_env(), query_msg)
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        OwnerOfResponse {
+            owner: String::from("person"),
+            approvals: vec![],
+        }
+    );
+}
+
+#[test]
+fn approving_all_revoking_all() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    // Mint a couple tokens (from the same owner)
+    let token_id1 = "grow1".to_string();
+    let token_uri1 = "https://www.merriam-webster.com/dictionary/grow1".to_string();
+
+    let token_id2 = "grow2".to_string();
+    let token_uri2 = "https://www.merriam-webster.com/dictionary/grow2".to_string();
+
+    let mint_msg1 = Cw721ExecuteMsg::Mint {
+        token_id: token_id1.clone(),
+        owner: String::from("demeter"),
+        token_uri: Some(token_uri1),
+        extension: None,
+    };
+
+    let minter = mock_info(MINTER_ADDR, &[]);
+    contract
+        .execute(deps.as_mut(), mock_env(), minter.clone(), mint_msg1)
+        .unwrap();
+
+    let mint_msg2 = Cw721ExecuteMsg::Mint {
+        token_id: token_id2.clone(),
+        owner: String::from("demeter"),
+        token_uri: Some(token_uri2),
+        extension: None,
+    };
+
+    let env = mock_env();
+    contract
+        .execute(deps.as_mut(), env.clone(), minter, mint_msg2)
+        .unwrap();
+
+    // paginate the token_ids
+    let tokens = contract
+        .query_all_tokens(deps.as_ref(), env.clone(), None, None, Some(1), None)
+        .unwrap();
+    assert_eq!(1, tokens.tokens.len());
+    assert_eq!(vec![token_id1.clone()], tokens.tokens);
+    let tokens = contract
+        .query_all_tokens(
+            deps.as_ref(),
+            env,
+            Some(token_id1.clone()),
+            None,
+            Some(3),
+            None,
+        )
+        .unwrap();
+    assert_eq!(1, tokens.tokens.len());
+    assert_eq!(vec![token_id2.clone()], tokens.tokens);
+
+    // demeter gives random full (operator) power over her tokens
+    let approve_all_msg = Cw721ExecuteMsg::ApproveAll {
+        operator: String::from("random"),
+        expires: None,
+    };
+    let owner = mock_info("demeter", &[]);
+    let res = contract
+        .execute(deps.as_mut(), mock_env(), owner, approve_all_msg)
+        .unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_attribute("action", "approve_all")
+            .add_event(
+                Event::new("approve_all")
+                    .add_attribute("sender", "demeter")
+                    .add_attribute("operator", "random")
+            )
+    );
+
+    // random can now transfer
+    let random = mock_info("random", &[]);
+    let transfer_msg = Cw721ExecuteMsg::TransferNft {
+        recipient: String::from("person"),
+        token_id: token_id1,
+    };
+    contract
+        .execute(deps.as_mut(), mock_env(), random.clone(), transfer_msg)
+        .unwrap();
+
+    // random can now send
+    let inner_msg = WasmMsg::Execute {
+        contract_addr: "another_contract".into(),
+        msg: to_json_binary("You now also have the growing power").unwrap(),
+        funds: vec![],
+    };
+    let msg: CosmosMsg = CosmosMsg::Wasm(inner_msg);
+
+    let send_msg = Cw721ExecuteMsg::SendNft {
+        contract: String::from("another_contract"),
+        token_id: token_id2,
+        msg: to_json_binary(&msg).unwrap(),
+    };
+    contract
+        .execute(deps.as_mut(), mock_env(), random, send_msg)
+        .unwrap();
+
+    // Approve_all, revoke_all, and check for empty, to test revoke_all
+    let approve_all_msg = Cw721ExecuteMsg::ApproveAll {
+        operator: String::from("operator"),
+        expires: None,
+    };
+    // person is now the owner of the tokens
+    let owner = mock_info("person", &[]);
+    contract
+        .execute(deps.as_mut(), mock_env(), owner, approve_all_msg)
+        .unwrap();
+
+    // query for operator should return approval
+    let res = contract
+        .query_operator(
+            deps.as_ref(),
+            mock_env(),
+            String::from("person"),
+            String::from("operator"),
+            true,
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        OperatorResponse {
+            approval: Approval {
+                spender: Addr::unchecked("operator"),
+                expires: Expiration::Never {}
+            }
+        }
+    );
+
+    // query for other should throw error
+    let res = contract.query_operator(
+        deps.as_ref(),
+        mock_env(),
+        String::from("person"),
+        String::from("other"),
+        true,
+    );
+    match res {
+        Err(StdError::NotFound { kind }) => assert_eq!(kind, "Approval not found"),
+        _ => panic!("Unexpected error"),
+    }
+
+    let res = contract
+        .query_operators(
+            deps.as_ref(),
+            mock_env(),
+            String::from("person"),
+            true,
+            None,
+            None,
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        OperatorsResponse {
+            operators: vec![Approval {
+                spender: Addr::unchecked("operator"),
+                expires: Expiration::Never {}
+            }]
+        }
+    );
+
+    // second approval
+    let buddy_expires = Expiration::AtHeight(1234567);
+    let approve_all_msg = Cw721ExecuteMsg::ApproveAll {
+        operator: String::from("buddy"),
+        expires: Some(buddy_expires),
+    };
+    let owner = mock_info("person", &[]);
+    contract
+        .execute(deps.as_mut(), mock_env(), owner.clone(), approve_all_msg)
+        .unwrap();
+
+    // and paginate queries
+    let res = contract
+        .query_operators(
+            deps.as_ref(),
+            mock_env(),
+            String::from("person"),
+            true,
+            None,
+            Some(1),
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        OperatorsResponse {
+            operators: vec![Approval {
+                spender: Addr::unchecked("buddy"),
+                expires: buddy_expires,
+            }]
+        }
+    );
+    let res = contract
+        .query_operators(
+            deps.as_ref(),
+            mock_env(),
+            String::from("person"),
+            true,
+            Some(String::from("buddy")),
+            Some(2),
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        OperatorsResponse {
+            operators: vec![Approval {
+                spender: Addr::unchecked("operator"),
+                expires: Expiration::Never {}
+            }]
+        }
+    );
+
+    let revoke_all_msg = Cw721ExecuteMsg::RevokeAll {
+        operator: String::from("operator"),
+    };
+    contract
+        .execute(deps.as_mut(), mock_env(), owner, revoke_all_msg)
+        .unwrap();
+
+    // query for operator should return error
+    let res = contract.query_operator(
+        deps.as_ref(),
+        mock_env(),
+        String::from("person"),
+        String::from("operator"),
+        true,
+    );
+    match res {
+        Err(StdError::NotFound { kind }) => assert_eq!(kind, "Approval not found"),
+        _ => panic!("Unexpected error"),
+    }
+
+    // Approvals are removed / cleared without affecting others
+    let res = contract
+        .query_operators(
+            deps.as_ref(),
+            mock_env(),
+            String::from("person"),
+            false,
+            None,
+            None,
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        OperatorsResponse {
+            operators: vec![Approval {
+                spender: Addr::unchecked("buddy"),
+                expires: buddy_expires,
+            }]
+        }
+    );
+
+    // ensure the filter works (nothing should be here
+    let mut late_env = mock_env();
+    late_env.block.height = 1234568; //expired
+    let res = contract
+        .query_operators(
+            deps.as_ref(),
+            late_env.clone(),
+            String::from("person"),
+            false,
+            None,
+            None,
+        )
+        .unwrap();
+    assert_eq!(0, res.operators.len());
+
+    // query operator should also return error
+    let res = contract.query_operator(
+        deps.as_ref(),
+        late_env,
+        String::from("person"),
+        String::from("buddy"),
+        false,
+    );
+
+    match res {
+        Err(StdError::NotFound { kind }) => assert_eq!(kind, "Approval not found"),
+        _ => panic!("Unexpected error"),
+    }
+}
+
+#[test]
+fn querying_operator_grants() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    // demeter and person both give operator full power over their tokens
+    let person_expires = Expiration::AtHeight(1234567);
+    for (owner, expires) in [("demeter", None), ("person", Some(person_expires))] {
+        let approve_all_msg = Cw721ExecuteMsg::ApproveAll {
+            operator: String::from("operator"),
+            expires,
+        };
+        contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(owner, &[]),
+                approve_all_msg,
+            )
+            .unwrap();
+    }
+
+    // paginate grants of operator
+    let res = contract
+        .query_operator_grants(
+            deps.as_ref(),
+            mock_env(),
+            String::from("operator"),
+            true,
+            None,
+            Some(1),
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        OperatorGrantsResponse {
+            grants: vec![OperatorGrant {
+                granter: Addr::unchecked("demeter"),
+                expires: Expiration::Never {},
+            }],
+            next: Some(String::from("demeter")),
+        }
+    );
+    let res = contract
+        .query_operator_grants(
+            deps.as_ref(),
+            mock_env(),
+            String::from("operator"),
+            true,
+            Some(String::from("demeter")),
+            Some(2),
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        OperatorGrantsResponse {
+            grants: vec![OperatorGrant {
+                granter: Addr::unchecked("person"),
+                expires: person_expires,
+            }],
+            next: None,
+        }
+    );
+
+    // expired grants are filtered out
+    let mut late_env = mock_env();
+    late_env.block.height = 1234568; //expired
+    let res = contract
+        .query_operator_grants(
+            deps.as_ref(),
+            late_env,
+            String::from("operator"),
+            false,
+            None,
+            None,
+        )
+        .unwrap();
+    assert_eq!(
+        res.grants,
+        vec![OperatorGrant {
+            granter: Addr::unchecked("demeter"),
+            expires: Expiration::Never {},
+        }]
+    );
+
+    // revoke_all removes grant without affecting others
+    let revoke_all_msg = Cw721ExecuteMsg::RevokeAll {
+        operator: String::from("operator"),
+    };
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("demeter", &[]),
+            revoke_all_msg,
+        )
+        .unwrap();
+    let res = contract
+        .query_operator_grants(
+            deps.as_ref(),
+            mock_env(),
+            String::from("operator"),
+            true,
+            None,
+            None,
+        )
+        .unwrap();
+    assert_eq!(
+        res.grants,
+        vec![OperatorGrant {
+            granter: Addr::unchecked("person"),
+            expires: person_expires,
+        }]
+    );
+}
+
+#[test]
+fn querying_approved_tokens() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let minter = mock_info(MINTER_ADDR, &[]);
+    for token_id in ["a", "b", "c", "d"] {
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.to_string(),
+            owner: String::from("demeter"),
+            token_uri: None,
+            extension: None,
+        };
+        contract
+            .execute(deps.as_mut(), mock_env(), minter.clone(), mint_msg)
+            .unwrap();
+    }
+
+    // demeter approves hermes for all tokens, token b expires
+    let b_expires = Expiration::AtHeight(1234567);
+    for (token_id, expires) in [
+        ("a", None),
+        ("b", Some(b_expires)),
+        ("c", None),
+        ("d", None),
+    ] {
+        let approve_msg = Cw721ExecuteMsg::Approve {
+            spender: String::from("hermes"),
+            token_id: token_id.to_string(),
+            expires,
+        };
+        contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("demeter", &[]),
+                approve_msg,
+            )
+            .unwrap();
+    }
+    let approved_token = |token_id: &str, expires: Expiration| ApprovedToken {
+        token_id: token_id.to_string(),
+        expires,
+    };
+
+    // paginate
+    let res = contract
+        .query_approved_tokens(
+            deps.as_ref(),
+            mock_env(),
+            String::from("hermes"),
+            true,
+            None,
+            Some(2),
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        ApprovedTokensResponse {
+            tokens: vec![
+                approved_token("a", Expiration::Never {}),
+                approved_token("b", b_expires),
+            ],
+            next: Some("b".to_string()),
+        }
+    );
+    let res = contract
+        .query_approved_tokens(
+            deps.as_ref(),
+            mock_env(),
+            String::from("hermes"),
+            true,
+            Some("b".to_string()),
+            None,
+        )
+        .unwrap();
+    assert_eq!(
+        res.tokens,
+        vec![
+            approved_token("c", Expiration::Never {}),
+            approved_token("d", Expiration::Never {}),
+        ]
+    );
+    assert_eq!(res.next, None);
+
+    // expired approvals are filtered out
+    let mut late_env = mock_env();
+    late_env.block.height = 1234568; //expired
+    let res = contract
+        .query_approved_tokens(
+            deps.as_ref(),
+            late_env,
+            String::from("hermes"),
+            false,
+            None,
+            None,
+        )
+        .unwrap();
+    assert_eq!(
+        res.tokens,
+        vec![
+            approved_token("a", Expiration::Never {}),
+            approved_token("c", Expiration::Never {}),
+            approved_token("d", Expiration::Never {}),
+        ]
+    );
+
+    // revoke, transfer and burn remove the approval
+    let revoke_msg = Cw721ExecuteMsg::Revoke {
+        spender: String::from("hermes"),
+        token_id: "a".to_string(),
+    };
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("demeter", &[]),
+            revoke_msg,
+        )
+        .unwrap();
+    let transfer_msg = Cw721ExecuteMsg::TransferNft {
+        recipient: String::from("person"),
+        token_id: "b".to_string(),
+    };
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("demeter", &[]),
+            transfer_msg,
+        )
+        .unwrap();
+    let burn_msg = Cw721ExecuteMsg::Burn {
+        token_id: "c".to_string(),
+    };
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("demeter", &[]),
+            burn_msg,
+        )
+        .unwrap();
+    let res = contract
+        .query_approved_tokens(
+            deps.as_ref(),
+            mock_env(),
+            String::from("hermes"),
+            true,
+            None,
+            None,
+        )
+        .unwrap();
+    assert_eq!(res.tokens, vec![approved_token("d", Expiration::Never {})]);
+}
+
+#[test]
+fn locking_nfts() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let minter = mock_info(MINTER_ADDR, &[]);
+    for token_id in ["a", "b"] {
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.to_string(),
+            owner: String::from("demeter"),
+            token_uri: None,
+            extension: None,
+        };
+        contract
+            .execute(deps.as_mut(), mock_env(), minter.clone(), mint_msg)
+            .unwrap();
+    }
+    let approve_msg = Cw721ExecuteMsg::Approve {
+        spender: String::from("hermes"),
+        token_id: "a".to_string(),
         expires: None,
     };
-    let owner = mock_info("demeter", &[]);
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("demeter", &[]),
+            approve_msg,
+        )
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("demeter", &[]),
+            Cw721ExecuteMsg::ApproveAll {
+                operator: String::from("apollo"),
+                expires: None,
+            },
+        )
+        .unwrap();
+
+    // only owner and operators can lock the token, and not with an expired lock
+    let until = Expiration::AtHeight(1234567);
+    let lock_msg = Cw721ExecuteMsg::LockNft {
+        token_id: "a".to_string(),
+        until,
+    };
+    for sender in ["random", "hermes"] {
+        let err = contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(sender, &[]),
+                lock_msg.clone(),
+            )
+            .unwrap_err();
+        assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+    }
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("apollo", &[]),
+            Cw721ExecuteMsg::LockNft {
+                token_id: "a".to_string(),
+                until: Expiration::AtHeight(1),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Expired {});
+
+    // operator locks the token
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("apollo", &[]),
+            lock_msg.clone(),
+        )
+        .unwrap();
     let res = contract
-        .execute(deps.as_mut(), mock_env(), owner, approve_msg)
+        .query_nft_lock(deps.as_ref(), mock_env(), "a".to_string(), false)
         .unwrap();
     assert_eq!(
-        res,
-        Response::new()
-            .add_attribute("action", "approve")
-            .add_attribute("sender", "demeter")
-            .add_attribute("spender", "random")
-            .add_attribute("token_id", token_id.clone())
+        res.lock,
+        Some(NftLock {
+            locker: Addr::unchecked("apollo"),
+            until,
+        })
     );
 
-    // test approval query
+    // locked token can neither be locked again, transferred nor burned, not even by the owner
+    let locked = Cw721ContractError::NftLocked {
+        token_id: "a".to_string(),
+        until,
+    };
+    let transfer_msg = Cw721ExecuteMsg::TransferNft {
+        recipient: String::from("person"),
+        token_id: "a".to_string(),
+    };
+    let burn_msg = Cw721ExecuteMsg::Burn {
+        token_id: "a".to_string(),
+    };
+    for msg in [lock_msg.clone(), transfer_msg.clone(), burn_msg] {
+        let err = contract
+            .execute(deps.as_mut(), mock_env(), mock_info("demeter", &[]), msg)
+            .unwrap_err();
+        assert_eq!(err, locked);
+    }
+
+    // only the locker can unlock, not even the owner
+    let unlock_msg = Cw721ExecuteMsg::UnlockNft {
+        token_id: "a".to_string(),
+    };
+    for sender in ["hermes", "demeter"] {
+        let err = contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(sender, &[]),
+                unlock_msg.clone(),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err,
+            Cw721ContractError::NotLocker {
+                token_id: "a".to_string(),
+                locker: "apollo".to_string(),
+            }
+        );
+    }
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("apollo", &[]),
+            unlock_msg.clone(),
+        )
+        .unwrap();
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("apollo", &[]),
+            unlock_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::NftNotLocked {
+            token_id: "a".to_string(),
+        }
+    );
     let res = contract
-        .query_approval(
+        .query_nft_lock(deps.as_ref(), mock_env(), "a".to_string(), true)
+        .unwrap();
+    assert_eq!(res.lock, None);
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("apollo", &[]),
+            lock_msg.clone(),
+        )
+        .unwrap();
+
+    // owner locks token b forever
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("demeter", &[]),
+            Cw721ExecuteMsg::LockNft {
+                token_id: "b".to_string(),
+                until: Expiration::Never {},
+            },
+        )
+        .unwrap();
+    let res = contract
+        .query_nft_locks(
             deps.as_ref(),
             mock_env(),
-            token_id.clone(),
-            String::from("random"),
-            true,
+            String::from("demeter"),
+            false,
+            None,
+            None,
         )
         .unwrap();
     assert_eq!(
-        res,
-        ApprovalResponse {
-            approval: Approval {
-                spender: Addr::unchecked("random"),
-                expires: Expiration::Never {}
-            }
-        }
+        res.locks,
+        vec![
+            LockedToken {
+                token_id: "a".to_string(),
+                locker: Addr::unchecked("apollo"),
+                until,
+            },
+            LockedToken {
+                token_id: "b".to_string(),
+                locker: Addr::unchecked("demeter"),
+                until: Expiration::Never {},
+            },
+        ]
     );
+    assert_eq!(res.next, None);
+    let res = contract
+        .query_nft_locks(
+            deps.as_ref(),
+            mock_env(),
+            String::from("demeter"),
+            false,
+            None,
+            Some(1),
+        )
+        .unwrap();
+    assert_eq!(res.locks.len(), 1);
+    assert_eq!(res.next, Some("a".to_string()));
 
-    // random can now transfer
-    let random = mock_info("random", &[]);
-    let transfer_msg = Cw721ExecuteMsg::TransferNft {
-        recipient: String::from("person"),
-        token_id: token_id.clone(),
+    // expired locks are filtered out and do not prevent transfers
+    let mut late_env = mock_env();
+    late_env.block.height = 1234568; //expired
+    let res = contract
+        .query_nft_locks(
+            deps.as_ref(),
+            late_env.clone(),
+            String::from("demeter"),
+            false,
+            None,
+            None,
+        )
+        .unwrap();
+    assert_eq!(res.locks.len(), 1);
+    assert_eq!(res.locks[0].token_id, "b");
+    contract
+        .execute(
+            deps.as_mut(),
+            late_env,
+            mock_info("demeter", &[]),
+            transfer_msg,
+        )
+        .unwrap();
+    let res = contract
+        .query_nft_lock(deps.as_ref(), mock_env(), "a".to_string(), true)
+        .unwrap();
+    assert_eq!(res.lock, None);
+
+    // owner can remove an expired lock set by an operator
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("person", &[]),
+            Cw721ExecuteMsg::ApproveAll {
+                operator: String::from("apollo"),
+                expires: None,
+            },
+        )
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("apollo", &[]),
+            lock_msg,
+        )
+        .unwrap();
+    let mut late_env = mock_env();
+    late_env.block.height = 1234568; //expired
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("person", &[]),
+            unlock_msg.clone(),
+        )
+        .unwrap_err();
+    contract
+        .execute(
+            deps.as_mut(),
+            late_env,
+            mock_info("person", &[]),
+            unlock_msg,
+        )
+        .unwrap();
+    let res = contract
+        .query_nft_lock(deps.as_ref(), mock_env(), "a".to_string(), true)
+        .unwrap();
+    assert_eq!(res.lock, None);
+
+    // unlocked token can be burned again
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("demeter", &[]),
+            Cw721ExecuteMsg::UnlockNft {
+                token_id: "b".to_string(),
+            },
+        )
+        .unwrap();
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("demeter", &[]),
+            Cw721ExecuteMsg::Burn {
+                token_id: "b".to_string(),
+            },
+        )
+        .unwrap();
+    let res = contract
+        .query_nft_locks(
+            deps.as_ref(),
+            mock_env(),
+            String::from("demeter"),
+            true,
+            None,
+            None,
+        )
+        .unwrap();
+    assert_eq!(res.locks, vec![]);
+}
+
+#[test]
+fn pruning_expired_approvals_and_operators() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let minter = mock_info(MINTER_ADDR, &[]);
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: "a".to_string(),
+        owner: String::from("demeter"),
+        token_uri: None,
+        extension: None,
     };
     contract
-        .execute(deps.as_mut(), mock_env(), random, transfer_msg)
+        .execute(deps.as_mut(), mock_env(), minter, mint_msg)
         .unwrap();
 
-    // Approvals are removed / cleared
-    let query_msg = Cw721QueryMsg::OwnerOf {
-        token_id: token_id.clone(),
-        include_expired: None,
+    // demeter approves and grants hermes, apollo and athena, only athena does not expire
+    let expires = Expiration::AtHeight(1234567);
+    for (spender, expires) in [
+        ("hermes", Some(expires)),
+        ("apollo", Some(expires)),
+        ("athena", None),
+    ] {
+        let approve_msg = Cw721ExecuteMsg::Approve {
+            spender: spender.to_string(),
+            token_id: "a".to_string(),
+            expires,
+        };
+        contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("demeter", &[]),
+                approve_msg,
+            )
+            .unwrap();
+        let approve_all_msg = Cw721ExecuteMsg::ApproveAll {
+            operator: spender.to_string(),
+            expires,
+        };
+        contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("demeter", &[]),
+                approve_all_msg,
+            )
+            .unwrap();
+    }
+
+    // nothing to prune yet, unknown (e.g. burned) tokens are skipped
+    let prune_msg = |limit: Option<u32>| Cw721ExecuteMsg::PruneExpired {
+        token_ids: vec!["burned".to_string(), "a".to_string()],
+        owners: vec![String::from("demeter")],
+        limit,
     };
-    let res: OwnerOfResponse = from_json(
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("random", &[]),
+            prune_msg(None),
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_attribute("action", "prune_expired")
+            .add_event(
+                Cw721Event::PruneExpired {
+                    sender: "random".to_string(),
+                    pruned_approvals: 0,
+                    pruned_operators: 0,
+                }
+                .into()
+            )
+    );
+
+    // anyone can prune expired entries, bounded by limit
+    let mut late_env = mock_env();
+    late_env.block.height = 1234568; //expired
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            late_env.clone(),
+            mock_info("random", &[]),
+            prune_msg(Some(3)),
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_attribute("action", "prune_expired")
+            .add_event(
+                Cw721Event::PruneExpired {
+                    sender: "random".to_string(),
+                    pruned_approvals: 2,
+                    pruned_operators: 1,
+                }
+                .into()
+            )
+    );
+    let res = contract
+        .execute(
+            deps.as_mut(),
+            late_env.clone(),
+            mock_info("random", &[]),
+            prune_msg(None),
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_attribute("action", "prune_expired")
+            .add_event(
+                Cw721Event::PruneExpired {
+                    sender: "random".to_string(),
+                    pruned_approvals: 0,
+                    pruned_operators: 1,
+                }
+                .into()
+            )
+    );
+
+    // only non-expired entries are left, even when including expired ones
+    let res = contract
+        .query_approvals(deps.as_ref(), late_env.clone(), "a".to_string(), true)
+        .unwrap();
+    assert_eq!(
+        res.approvals,
+        vec![Approval {
+            spender: Addr::unchecked("athena"),
+            expires: Expiration::Never {},
+        }]
+    );
+    let res = contract
+        .query_approved_tokens(
+            deps.as_ref(),
+            late_env.clone(),
+            String::from("hermes"),
+            true,
+            None,
+            None,
+        )
+        .unwrap();
+    assert_eq!(res.tokens, vec![]);
+    let res = contract
+        .query_operators(
+            deps.as_ref(),
+            late_env.clone(),
+            String::from("demeter"),
+            true,
+            None,
+            None,
+        )
+        .unwrap();
+    assert_eq!(res.operators.len(), 1);
+    assert_eq!(res.operators[0].spender, "athena");
+    let res = contract
+        .query_operator_grants(
+            deps.as_ref(),
+            late_env,
+            String::from("apollo"),
+            true,
+            None,
+            None,
+        )
+        .unwrap();
+    assert_eq!(res.grants, vec![]);
+}
+
+#[test]
+fn tracking_balances() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let balance_of = |deps: &cosmwasm_std::OwnedDeps<_, _, _>, owner: &str| {
+        contract
+            .query_balance_of(deps.as_ref(), mock_env(), owner.to_string())
+            .unwrap()
+            .balance
+    };
+    assert_eq!(balance_of(&deps, "demeter"), 0);
+
+    // mint increases balance
+    let minter = mock_info(MINTER_ADDR, &[]);
+    for token_id in ["1", "2", "3"] {
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.to_string(),
+            owner: String::from("demeter"),
+            token_uri: None,
+            extension: None,
+        };
         contract
-            .query(deps.as_ref(), mock_env(), query_msg.clone())
-            .unwrap(),
-    )
-    .unwrap();
-    assert_eq!(
-        res,
-        OwnerOfResponse {
-            owner: String::from("person"),
-            approvals: vec![],
-        }
-    );
+            .execute(deps.as_mut(), mock_env(), minter.clone(), mint_msg)
+            .unwrap();
+    }
+    assert_eq!(balance_of(&deps, "demeter"), 3);
 
-    // Approve, revoke, and check for empty, to test revoke
-    let approve_msg = Cw721ExecuteMsg::Approve {
-        spender: String::from("random"),
-        token_id: token_id.clone(),
-        expires: None,
+    // transfer moves balance to recipient
+    let transfer_msg = Cw721ExecuteMsg::TransferNft {
+        recipient: String::from("person"),
+        token_id: "1".to_string(),
     };
-    let owner = mock_info("person", &[]);
     contract
-        .execute(deps.as_mut(), mock_env(), owner.clone(), approve_msg)
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("demeter", &[]),
+            transfer_msg,
+        )
         .unwrap();
+    assert_eq!(balance_of(&deps, "demeter"), 2);
+    assert_eq!(balance_of(&deps, "person"), 1);
 
-    let revoke_msg = Cw721ExecuteMsg::Revoke {
-        spender: String::from("random"),
-        token_id,
+    // burn decreases balance
+    let burn_msg = Cw721ExecuteMsg::Burn {
+        token_id: "1".to_string(),
     };
     contract
-        .execute(deps.as_mut(), mock_env(), owner, revoke_msg)
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("person", &[]),
+            burn_msg,
+        )
         .unwrap();
+    assert_eq!(balance_of(&deps, "person"), 0);
 
-    // Approvals are now removed / cleared
-    let res: OwnerOfResponse = from_json(
-        contract
-            .query(deps.as_ref(), mock_env(), query_msg)
-            .unwrap(),
-    )
-    .unwrap();
-    assert_eq!(
-        res,
-        OwnerOfResponse {
-            owner: String::from("person"),
-            approvals: vec![],
-        }
-    );
+    // batch operations are tracked as well
+    let batch_transfer_msg = Cw721ExecuteMsg::BatchTransferNft {
+        recipient: String::from("person"),
+        token_ids: vec!["2".to_string(), "3".to_string()],
+    };
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("demeter", &[]),
+            batch_transfer_msg,
+        )
+        .unwrap();
+    assert_eq!(balance_of(&deps, "demeter"), 0);
+    assert_eq!(balance_of(&deps, "person"), 2);
 }
 
 #[test]
-fn approving_all_revoking_all() {
+fn pausing_transfers_and_minting() {
     let mut deps = mock_dependencies();
     let contract = setup_contract(deps.as_mut());
 
-    // Mint a couple tokens (from the same owner)
-    let token_id1 = "grow1".to_string();
-    let token_uri1 = "https://www.merriam-webster.com/dictionary/grow1".to_string();
-
-    let token_id2 = "grow2".to_string();
-    let token_uri2 = "https://www.merriam-webster.com/dictionary/grow2".to_string();
-
-    let mint_msg1 = Cw721ExecuteMsg::Mint {
-        token_id: token_id1.clone(),
+    let mint_msg = |token_id: &str| Cw721ExecuteMsg::Mint {
+        token_id: token_id.to_string(),
         owner: String::from("demeter"),
-        token_uri: Some(token_uri1),
+        token_uri: None,
         extension: None,
     };
-
     let minter = mock_info(MINTER_ADDR, &[]);
     contract
-        .execute(deps.as_mut(), mock_env(), minter.clone(), mint_msg1)
+        .execute(deps.as_mut(), mock_env(), minter.clone(), mint_msg("1"))
         .unwrap();
-
-    let mint_msg2 = Cw721ExecuteMsg::Mint {
-        token_id: token_id2.clone(),
-        owner: String::from("demeter"),
-        token_uri: Some(token_uri2),
-        extension: None,
-    };
-
-    let env = mock_env();
+    let owner = mock_info("demeter", &[]);
     contract
-        .execute(deps.as_mut(), env.clone(), minter, mint_msg2)
-        .unwrap();
-
-    // paginate the token_ids
-    let tokens = contract
-        .query_all_tokens(deps.as_ref(), env.clone(), None, Some(1))
-        .unwrap();
-    assert_eq!(1, tokens.tokens.len());
-    assert_eq!(vec![token_id1.clone()], tokens.tokens);
-    let tokens = contract
-        .query_all_tokens(deps.as_ref(), env, Some(token_id1.clone()), Some(3))
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            owner.clone(),
+            Cw721ExecuteMsg::Approve {
+                spender: String::from("random"),
+                token_id: "1".to_string(),
+                expires: None,
+            },
+        )
         .unwrap();
-    assert_eq!(1, tokens.tokens.len());
-    assert_eq!(vec![token_id2.clone()], tokens.tokens);
 
-    // demeter gives random full (operator) power over her tokens
-    let approve_all_msg = Cw721ExecuteMsg::ApproveAll {
-        operator: String::from("random"),
+    // only creator can pause
+    let pause_msg = Cw721ExecuteMsg::Pause {
+        target: None,
         expires: None,
     };
-    let owner = mock_info("demeter", &[]);
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MINTER_ADDR, &[]),
+            pause_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+
+    // pausing defaults to transfers
+    let creator = mock_info(CREATOR_ADDR, &[]);
     let res = contract
-        .execute(deps.as_mut(), mock_env(), owner, approve_all_msg)
+        .execute(deps.as_mut(), mock_env(), creator.clone(), pause_msg)
         .unwrap();
     assert_eq!(
-        res,
-        Response::new()
-            .add_attribute("action", "approve_all")
-            .add_attribute("sender", "demeter")
-            .add_attribute("operator", "random")
+        res.events,
+        vec![Cw721Event::Pause {
+            sender: CREATOR_ADDR.to_string(),
+            target: "transfers".to_string(),
+            expires: "expiration: never".to_string(),
+        }
+        .into()]
+    );
+    let status = contract
+        .query_pause_status(deps.as_ref(), mock_env())
+        .unwrap();
+    assert_eq!(
+        status,
+        PauseStatusResponse {
+            transfers: Some(Expiration::Never {}),
+            minting: None,
+        }
     );
 
-    // random can now transfer
-    let random = mock_info("random", &[]);
+    // transfers, burns and approvals are rejected
+    let paused_err = Cw721ContractError::Paused {
+        target: "transfers".to_string(),
+    };
     let transfer_msg = Cw721ExecuteMsg::TransferNft {
         recipient: String::from("person"),
-        token_id: token_id1,
-    };
-    contract
-        .execute(deps.as_mut(), mock_env(), random.clone(), transfer_msg)
-        .unwrap();
-
-    // random can now send
-    let inner_msg = WasmMsg::Execute {
-        contract_addr: "another_contract".into(),
-        msg: to_json_binary("You now also have the growing power").unwrap(),
-        funds: vec![],
+        token_id: "1".to_string(),
     };
-    let msg: CosmosMsg = CosmosMsg::Wasm(inner_msg);
-
-    let send_msg = Cw721ExecuteMsg::SendNft {
-        contract: String::from("another_contract"),
-        token_id: token_id2,
-        msg: to_json_binary(&msg).unwrap(),
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            owner.clone(),
+            transfer_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, paused_err);
+    let burn_msg = Cw721ExecuteMsg::Burn {
+        token_id: "1".to_string(),
     };
-    contract
-        .execute(deps.as_mut(), mock_env(), random, send_msg)
-        .unwrap();
-
-    // Approve_all, revoke_all, and check for empty, to test revoke_all
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), owner.clone(), burn_msg)
+        .unwrap_err();
+    assert_eq!(err, paused_err);
     let approve_all_msg = Cw721ExecuteMsg::ApproveAll {
-        operator: String::from("operator"),
+        operator: String::from("random"),
         expires: None,
     };
-    // person is now the owner of the tokens
-    let owner = mock_info("person", &[]);
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), owner.clone(), approve_all_msg)
+        .unwrap_err();
+    assert_eq!(err, paused_err);
+
+    // revoking is still possible
     contract
-        .execute(deps.as_mut(), mock_env(), owner, approve_all_msg)
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            owner.clone(),
+            Cw721ExecuteMsg::Revoke {
+                spender: String::from("random"),
+                token_id: "1".to_string(),
+            },
+        )
         .unwrap();
 
-    // query for operator should return approval
-    let res = contract
-        .query_operator(
-            deps.as_ref(),
+    // minting is not affected, but can be paused separately until a given height
+    contract
+        .execute(deps.as_mut(), mock_env(), minter.clone(), mint_msg("2"))
+        .unwrap();
+    let minting_expires = Expiration::AtHeight(mock_env().block.height + 10);
+    contract
+        .execute(
+            deps.as_mut(),
             mock_env(),
-            String::from("person"),
-            String::from("operator"),
-            true,
+            creator.clone(),
+            Cw721ExecuteMsg::Pause {
+                target: Some(PauseTarget::Minting),
+                expires: Some(minting_expires),
+            },
         )
         .unwrap();
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), minter.clone(), mint_msg("3"))
+        .unwrap_err();
     assert_eq!(
-        res,
-        OperatorResponse {
-            approval: Approval {
-                spender: Addr::unchecked("operator"),
-                expires: Expiration::Never {}
-            }
+        err,
+        Cw721ContractError::Paused {
+            target: "minting".to_string(),
         }
     );
+    let mut late_env = mock_env();
+    late_env.block.height += 10;
+    let status = contract
+        .query_pause_status(deps.as_ref(), late_env.clone())
+        .unwrap();
+    assert_eq!(status.minting, None);
+    contract
+        .execute(deps.as_mut(), late_env, minter, mint_msg("3"))
+        .unwrap();
 
-    // query for other should throw error
-    let res = contract.query_operator(
-        deps.as_ref(),
-        mock_env(),
-        String::from("person"),
-        String::from("other"),
-        true,
-    );
-    match res {
-        Err(StdError::NotFound { kind }) => assert_eq!(kind, "Approval not found"),
-        _ => panic!("Unexpected error"),
-    }
-
+    // unpause transfers
     let res = contract
-        .query_operators(
-            deps.as_ref(),
+        .execute(
+            deps.as_mut(),
             mock_env(),
-            String::from("person"),
-            true,
-            None,
-            None,
+            creator,
+            Cw721ExecuteMsg::Unpause { target: None },
         )
         .unwrap();
     assert_eq!(
-        res,
-        OperatorsResponse {
-            operators: vec![Approval {
-                spender: Addr::unchecked("operator"),
-                expires: Expiration::Never {}
-            }]
+        res.events,
+        vec![Cw721Event::Unpause {
+            sender: CREATOR_ADDR.to_string(),
+            target: "transfers".to_string(),
         }
+        .into()]
     );
-
-    // second approval
-    let buddy_expires = Expiration::AtHeight(1234567);
-    let approve_all_msg = Cw721ExecuteMsg::ApproveAll {
-        operator: String::from("buddy"),
-        expires: Some(buddy_expires),
-    };
-    let owner = mock_info("person", &[]);
     contract
-        .execute(deps.as_mut(), mock_env(), owner.clone(), approve_all_msg)
+        .execute(deps.as_mut(), mock_env(), owner, transfer_msg)
         .unwrap();
+}
 
-    // and paginate queries
-    let res = contract
-        .query_operators(
-            deps.as_ref(),
+#[test]
+fn notifying_hooks() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    // only creator can add hooks
+    let err = contract
+        .execute(
+            deps.as_mut(),
             mock_env(),
-            String::from("person"),
-            true,
-            None,
-            Some(1),
+            mock_info(MINTER_ADDR, &[]),
+            Cw721ExecuteMsg::AddHook {
+                addr: String::from("hook_a"),
+                mode: HookMode::Blocking,
+            },
         )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
+
+    let creator = mock_info(CREATOR_ADDR, &[]);
+    for (addr, mode) in [
+        ("hook_a", HookMode::Blocking),
+        ("hook_b", HookMode::FireAndForget),
+    ] {
+        contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                creator.clone(),
+                Cw721ExecuteMsg::AddHook {
+                    addr: addr.to_string(),
+                    mode,
+                },
+            )
+            .unwrap();
+    }
+    let hooks = contract
+        .query_hooks(deps.as_ref(), mock_env(), None, None)
         .unwrap();
     assert_eq!(
-        res,
-        OperatorsResponse {
-            operators: vec![Approval {
-                spender: Addr::unchecked("buddy"),
-                expires: buddy_expires,
-            }]
-        }
+        hooks.hooks,
+        vec![
+            Hook {
+                addr: Addr::unchecked("hook_a"),
+                mode: HookMode::Blocking,
+            },
+            Hook {
+                addr: Addr::unchecked("hook_b"),
+                mode: HookMode::FireAndForget,
+            },
+        ]
     );
+    assert_eq!(hooks.next, None);
+    let hooks = contract
+        .query_hooks(deps.as_ref(), mock_env(), None, Some(1))
+        .unwrap();
+    assert_eq!(hooks.hooks.len(), 1);
+    assert_eq!(hooks.next, Some(String::from("hook_a")));
+
+    // mint notifies all hooks
+    let mint_msg = Cw721ExecuteMsg::Mint {
+        token_id: "1".to_string(),
+        owner: String::from("demeter"),
+        token_uri: None,
+        extension: None,
+    };
     let res = contract
-        .query_operators(
-            deps.as_ref(),
+        .execute(
+            deps.as_mut(),
             mock_env(),
-            String::from("person"),
-            true,
-            Some(String::from("buddy")),
-            Some(2),
+            mock_info(MINTER_ADDR, &[]),
+            mint_msg,
         )
         .unwrap();
+    let hook_msg = Cw721HookMsg::Mint {
+        owner: String::from("demeter"),
+        token_id: "1".to_string(),
+    };
     assert_eq!(
-        res,
-        OperatorsResponse {
-            operators: vec![Approval {
-                spender: Addr::unchecked("operator"),
-                expires: Expiration::Never {}
-            }]
-        }
+        res.messages,
+        vec![
+            hook_msg
+                .clone()
+                .into_sub_msg("hook_a", &HookMode::Blocking)
+                .unwrap(),
+            hook_msg
+                .into_sub_msg("hook_b", &HookMode::FireAndForget)
+                .unwrap(),
+        ]
     );
+    assert_eq!(res.messages[1].reply_on, ReplyOn::Error);
 
-    let revoke_all_msg = Cw721ExecuteMsg::RevokeAll {
-        operator: String::from("operator"),
-    };
+    // removed hooks are not notified anymore
     contract
-        .execute(deps.as_mut(), mock_env(), owner, revoke_all_msg)
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            creator.clone(),
+            Cw721ExecuteMsg::RemoveHook {
+                addr: String::from("hook_a"),
+            },
+        )
         .unwrap();
-
-    // query for operator should return error
-    let res = contract.query_operator(
-        deps.as_ref(),
-        mock_env(),
-        String::from("person"),
-        String::from("operator"),
-        true,
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            creator,
+            Cw721ExecuteMsg::RemoveHook {
+                addr: String::from("hook_a"),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::HookNotRegistered {
+            addr: String::from("hook_a"),
+        }
     );
-    match res {
-        Err(StdError::NotFound { kind }) => assert_eq!(kind, "Approval not found"),
-        _ => panic!("Unexpected error"),
-    }
 
-    // Approvals are removed / cleared without affecting others
+    let transfer_msg = Cw721ExecuteMsg::TransferNft {
+        recipient: String::from("person"),
+        token_id: "1".to_string(),
+    };
     let res = contract
-        .query_operators(
-            deps.as_ref(),
+        .execute(
+            deps.as_mut(),
             mock_env(),
-            String::from("person"),
-            false,
-            None,
-            None,
+            mock_info("demeter", &[]),
+            transfer_msg,
         )
         .unwrap();
+    let hook_msg = Cw721HookMsg::Transfer {
+        sender: String::from("demeter"),
+        previous_owner: String::from("demeter"),
+        recipient: String::from("person"),
+        token_id: "1".to_string(),
+    };
     assert_eq!(
-        res,
-        OperatorsResponse {
-            operators: vec![Approval {
-                spender: Addr::unchecked("buddy"),
-                expires: buddy_expires,
-            }]
-        }
+        res.messages,
+        vec![hook_msg
+            .into_sub_msg("hook_b", &HookMode::FireAndForget)
+            .unwrap()]
     );
 
-    // ensure the filter works (nothing should be here
-    let mut late_env = mock_env();
-    late_env.block.height = 1234568; //expired
-    let res = contract
-        .query_operators(
-            deps.as_ref(),
-            late_env.clone(),
-            String::from("person"),
-            false,
-            None,
-            None,
+    // number of hooks is capped, but modes of registered hooks can still be updated
+    for i in 1..MAX_HOOKS {
+        contract
+            .execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(CREATOR_ADDR, &[]),
+                Cw721ExecuteMsg::AddHook {
+                    addr: format!("hook_{i}"),
+                    mode: HookMode::FireAndForget,
+                },
+            )
+            .unwrap();
+    }
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR_ADDR, &[]),
+            Cw721ExecuteMsg::AddHook {
+                addr: String::from("hook_a"),
+                mode: HookMode::Blocking,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::MaxHooksReached { max: MAX_HOOKS });
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CREATOR_ADDR, &[]),
+            Cw721ExecuteMsg::AddHook {
+                addr: String::from("hook_b"),
+                mode: HookMode::Blocking,
+            },
         )
         .unwrap();
-    assert_eq!(0, res.operators.len());
 
-    // query operator should also return error
-    let res = contract.query_operator(
-        deps.as_ref(),
-        late_env,
-        String::from("person"),
-        String::from("buddy"),
-        false,
+    // errors of fire-and-forget hooks are ignored
+    let reply = Reply {
+        id: HOOK_REPLY_ID,
+        result: SubMsgResult::Err("hook error".to_string()),
+    };
+    let res = contract.reply(deps.as_mut(), mock_env(), reply).unwrap();
+    assert_eq!(
+        Cw721Event::from_events(&res.events),
+        vec![Cw721Event::HookFailed {
+            error: "hook error".to_string()
+        }]
     );
-
-    match res {
-        Err(StdError::NotFound { kind }) => assert_eq!(kind, "Approval not found"),
-        _ => panic!("Unexpected error"),
-    }
+    assert_eq!(res.attributes, vec![("action", "hook_failed")]);
+    let reply = Reply {
+        id: 1,
+        result: SubMsgResult::Err("other error".to_string()),
+    };
+    let err = contract
+        .reply(deps.as_mut(), mock_env(), reply)
+        .unwrap_err();
+    assert_eq!(err, Cw721ContractError::UnknownReplyId { id: 1 });
 }
 
 #[test]
@@ -947,17 +4595,17 @@ fn test_set_withdraw_address() {
     let mut deps = mock_dependencies();
     let contract = setup_contract(deps.as_mut());
 
-    // other than minter cant set
+    // other than creator cant set
     let err = contract
         .set_withdraw_address(deps.as_mut(), &Addr::unchecked("other"), "foo".to_string())
         .unwrap_err();
     assert_eq!(err, Cw721ContractError::Ownership(OwnershipError::NotOwner));
 
-    // minter can set
+    // creator can set
     contract
         .set_withdraw_address(
             deps.as_mut(),
-            &Addr::unchecked(MINTER_ADDR),
+            &Addr::unchecked(CREATOR_ADDR),
             "foo".to_string(),
         )
         .unwrap();
@@ -983,7 +4631,7 @@ fn test_remove_withdraw_address() {
 
     // no withdraw address set yet
     let err = contract
-        .remove_withdraw_address(deps.as_mut().storage, &Addr::unchecked(MINTER_ADDR))
+        .remove_withdraw_address(deps.as_mut().storage, &Addr::unchecked(CREATOR_ADDR))
         .unwrap_err();
     assert_eq!(err, Cw721ContractError::NoWithdrawAddress {});
 
@@ -991,12 +4639,12 @@ fn test_remove_withdraw_address() {
     contract
         .set_withdraw_address(
             deps.as_mut(),
-            &Addr::unchecked(MINTER_ADDR),
+            &Addr::unchecked(CREATOR_ADDR),
             "foo".to_string(),
         )
         .unwrap();
     contract
-        .remove_withdraw_address(deps.as_mut().storage, &Addr::unchecked(MINTER_ADDR))
+        .remove_withdraw_address(deps.as_mut().storage, &Addr::unchecked(CREATOR_ADDR))
         .unwrap();
     assert!(!contract
         .config
@@ -1007,7 +4655,7 @@ fn test_remove_withdraw_address() {
     contract
         .set_withdraw_address(
             deps.as_mut(),
-            &Addr::unchecked(MINTER_ADDR),
+            &Addr::unchecked(CREATOR_ADDR),
             "foo".to_string(),
         )
         .unwrap();
@@ -1034,7 +4682,7 @@ fn test_withdraw_funds() {
     contract
         .set_withdraw_address(
             deps.as_mut(),
-            &Addr::unchecked(MINTER_ADDR),
+            &Addr::unchecked(CREATOR_ADDR),
             "foo".to_string(),
         )
         .unwrap();
@@ -1090,16 +4738,23 @@ fn query_tokens_by_owner() {
     // get all tokens in order:
     let expected = vec![token_id1.clone(), token_id2.clone(), token_id3.clone()];
     let tokens = contract
-        .query_all_tokens(deps.as_ref(), env.clone(), None, None)
+        .query_all_tokens(deps.as_ref(), env.clone(), None, None, None, None)
         .unwrap();
     assert_eq!(&expected, &tokens.tokens);
     // paginate
     let tokens = contract
-        .query_all_tokens(deps.as_ref(), env.clone(), None, Some(2))
+        .query_all_tokens(deps.as_ref(), env.clone(), None, None, Some(2), None)
         .unwrap();
     assert_eq!(&expected[..2], &tokens.tokens[..]);
     let tokens = contract
-        .query_all_tokens(deps.as_ref(), env.clone(), Some(expected[1].clone()), None)
+        .query_all_tokens(
+            deps.as_ref(),
+            env.clone(),
+            Some(expected[1].clone()),
+            None,
+            None,
+            None,
+        )
         .unwrap();
     assert_eq!(&expected[2..], &tokens.tokens[..]);
 
@@ -1108,17 +4763,33 @@ fn query_tokens_by_owner() {
     let by_demeter = vec![token_id1, token_id3];
     // all tokens by owner
     let tokens = contract
-        .query_tokens(deps.as_ref(), env.clone(), demeter.clone(), None, None)
+        .query_tokens(
+            deps.as_ref(),
+            env.clone(),
+            demeter.clone(),
+            None,
+            None,
+            None,
+            None,
+        )
         .unwrap();
     assert_eq!(&by_demeter, &tokens.tokens);
     let tokens = contract
-        .query_tokens(deps.as_ref(), env.clone(), ceres, None, None)
+        .query_tokens(deps.as_ref(), env.clone(), ceres, None, None, None, None)
         .unwrap();
     assert_eq!(&by_ceres, &tokens.tokens);
 
     // paginate for demeter
     let tokens = contract
-        .query_tokens(deps.as_ref(), env.clone(), demeter.clone(), None, Some(1))
+        .query_tokens(
+            deps.as_ref(),
+            env.clone(),
+            demeter.clone(),
+            None,
+            None,
+            Some(1),
+            None,
+        )
         .unwrap();
     assert_eq!(&by_demeter[..1], &tokens.tokens[..]);
     let tokens = contract
@@ -1127,8 +4798,235 @@ fn query_tokens_by_owner() {
             env,
             demeter,
             Some(by_demeter[0].clone()),
+            None,
             Some(3),
+            None,
         )
         .unwrap();
     assert_eq!(&by_demeter[1..], &tokens.tokens[..]);
 }
+
+#[test]
+fn query_tokens_ordered_and_ranged() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let minter = mock_info(MINTER_ADDR, &[]);
+    for (token_id, owner) in [
+        ("a", "demeter"),
+        ("b", "demeter"),
+        ("c", "ceres"),
+        ("d", "demeter"),
+        ("e", "demeter"),
+    ] {
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.to_string(),
+            owner: owner.to_string(),
+            token_uri: None,
+            extension: None,
+        };
+        contract
+            .execute(deps.as_mut(), mock_env(), minter.clone(), mint_msg)
+            .unwrap();
+    }
+    let env = mock_env();
+
+    // newest first, next is set as long as there are more pages
+    let tokens = contract
+        .query_all_tokens(
+            deps.as_ref(),
+            env.clone(),
+            None,
+            None,
+            Some(2),
+            Some(OrderBy::Descending),
+        )
+        .unwrap();
+    assert_eq!(
+        tokens,
+        TokensResponse {
+            tokens: vec!["e".to_string(), "d".to_string()],
+            next: Some("d".to_string()),
+        }
+    );
+    let tokens = contract
+        .query_all_tokens(
+            deps.as_ref(),
+            env.clone(),
+            tokens.next,
+            None,
+            Some(3),
+            Some(OrderBy::Descending),
+        )
+        .unwrap();
+    assert_eq!(
+        tokens,
+        TokensResponse {
+            tokens: vec!["c".to_string(), "b".to_string(), "a".to_string()],
+            next: None,
+        }
+    );
+
+    // bounded ranges
+    let tokens = contract
+        .query_all_tokens(
+            deps.as_ref(),
+            env.clone(),
+            Some("a".to_string()),
+            Some("d".to_string()),
+            None,
+            None,
+        )
+        .unwrap();
+    assert_eq!(tokens.tokens, vec!["b".to_string(), "c".to_string()]);
+    assert_eq!(tokens.next, None);
+    let tokens = contract
+        .query_all_tokens(
+            deps.as_ref(),
+            env.clone(),
+            Some("e".to_string()),
+            Some("b".to_string()),
+            None,
+            Some(OrderBy::Descending),
+        )
+        .unwrap();
+    assert_eq!(tokens.tokens, vec!["d".to_string(), "c".to_string()]);
+
+    // by owner
+    let tokens = contract
+        .query_tokens(
+            deps.as_ref(),
+            env.clone(),
+            "demeter".to_string(),
+            Some("e".to_string()),
+            None,
+            Some(2),
+            Some(OrderBy::Descending),
+        )
+        .unwrap();
+    assert_eq!(
+        tokens,
+        TokensResponse {
+            tokens: vec!["d".to_string(), "b".to_string()],
+            next: Some("b".to_string()),
+        }
+    );
+    let tokens = contract
+        .query_tokens(
+            deps.as_ref(),
+            env,
+            "demeter".to_string(),
+            Some("e".to_string()),
+            None,
+            Some(3),
+            Some(OrderBy::Descending),
+        )
+        .unwrap();
+    assert_eq!(tokens.tokens, vec!["d", "b", "a"]);
+    assert_eq!(tokens.next, None);
+}
+
+#[test]
+fn query_nft_infos() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let minter = mock_info(MINTER_ADDR, &[]);
+    for (token_id, owner) in [("a", "demeter"), ("b", "ceres"), ("c", "demeter")] {
+        let mint_msg = Cw721ExecuteMsg::Mint {
+            token_id: token_id.to_string(),
+            owner: owner.to_string(),
+            token_uri: Some(format!("https://www.merriam-webster.com/{token_id}")),
+            extension: None,
+        };
+        contract
+            .execute(deps.as_mut(), mock_env(), minter.clone(), mint_msg)
+            .unwrap();
+    }
+    let approve_msg = Cw721ExecuteMsg::Approve {
+        spender: String::from("hermes"),
+        token_id: "a".to_string(),
+        expires: None,
+    };
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("demeter", &[]),
+            approve_msg,
+        )
+        .unwrap();
+    let env = mock_env();
+
+    let nft_info = |token_id: &str, owner: &str, approvals: Vec<Approval>| NftInfoWithId {
+        token_id: token_id.to_string(),
+        access: OwnerOfResponse {
+            owner: owner.to_string(),
+            approvals,
+        },
+        info: NftInfoResponse {
+            token_uri: Some(format!("https://www.merriam-webster.com/{token_id}")),
+            extension: None,
+        },
+    };
+    let hermes_approval = Approval {
+        spender: Addr::unchecked("hermes"),
+        expires: Expiration::Never {},
+    };
+
+    // all nft infos, paginated
+    let res = contract
+        .query_all_nft_infos(deps.as_ref(), env.clone(), None, Some(2), false)
+        .unwrap();
+    assert_eq!(
+        res,
+        NftInfosResponse {
+            nft_infos: vec![
+                nft_info("a", "demeter", vec![hermes_approval.clone()]),
+                nft_info("b", "ceres", vec![]),
+            ],
+            next: Some("b".to_string()),
+        }
+    );
+    let res = contract
+        .query_all_nft_infos(deps.as_ref(), env.clone(), res.next, Some(2), false)
+        .unwrap();
+    assert_eq!(
+        res,
+        NftInfosResponse {
+            nft_infos: vec![nft_info("c", "demeter", vec![])],
+            next: None,
+        }
+    );
+
+    // by owner
+    let res = contract
+        .query_nft_infos_by_owner(
+            deps.as_ref(),
+            env.clone(),
+            "demeter".to_string(),
+            None,
+            None,
+            false,
+        )
+        .unwrap();
+    assert_eq!(
+        res,
+        NftInfosResponse {
+            nft_infos: vec![
+                nft_info("a", "demeter", vec![hermes_approval]),
+                nft_info("c", "demeter", vec![]),
+            ],
+            next: None,
+        }
+    );
+    let res = contract
+        .query_nft_infos_by_owner(
+            deps.as_ref(),
+            env,
+            "demeter".to_string(),
+            Some("a".to_string()),
+            None,
+            false,
+        )
+        .unwrap();
+    assert_eq!(res.nft_infos, vec![nft_info("c", "demeter", vec![])]);
+}
@@ -1,12 +1,18 @@
 use crate::{
+    error::Cw721ContractError,
+    event::Cw721Event,
     execute::Cw721Execute,
     msg::{Cw721ExecuteMsg, Cw721InstantiateMsg},
     query::{Cw721Query, MAX_LIMIT},
-    state::{CollectionInfo, DefaultOptionMetadataExtension, Metadata, MINTER},
+    state::{
+        CollectionInfo, Cw721StorageKeys, DefaultOptionCollectionInfoExtension,
+        DefaultOptionMetadataExtension, Metadata, Trait, ValidateMetadata,
+        MAX_METADATA_TEXT_LENGTH, MAX_METADATA_TRAITS,
+    },
 };
 use cosmwasm_std::{
     testing::{mock_dependencies, mock_env, mock_info},
-    Addr, Empty,
+    Addr, Empty, Event, OverflowError, OverflowOperation, Response, StdError,
 };
 use cw2::ContractVersion;
 use cw_storage_plus::Item;
@@ -19,7 +25,13 @@ use super::*;
 fn proper_cw2_initialization() {
     let mut deps = mock_dependencies();
 
-    Cw721Contract::<DefaultOptionMetadataExtension, Empty, Empty>::default()
+    let contract = Cw721Contract::<
+        DefaultOptionMetadataExtension,
+        Empty,
+        Empty,
+        DefaultOptionCollectionInfoExtension,
+    >::default();
+    contract
         .instantiate(
             deps.as_mut(),
             mock_env(),
@@ -27,15 +39,25 @@ fn proper_cw2_initialization() {
             Cw721InstantiateMsg {
                 name: "collection_name".into(),
                 symbol: "collection_symbol".into(),
+                collection_info_extension: None,
+                creator: None,
                 minter: Some("minter".into()),
                 withdraw_address: None,
+                max_batch_size: None,
+                max_supply: None,
+                token_id_policy: None,
+                allow_remint: None,
+                base_uri: None,
+                enable_snapshots: None,
             },
             "contract_name",
             "contract_version",
         )
         .unwrap();
 
-    let minter = MINTER
+    let minter = contract
+        .config
+        .minter
         .get_ownership(deps.as_ref().storage)
         .unwrap()
         .owner
@@ -57,7 +79,13 @@ fn proper_owner_initialization() {
     let mut deps = mock_dependencies();
 
     let info_owner = mock_info("owner", &[]);
-    Cw721Contract::<DefaultOptionMetadataExtension, Empty, Empty>::default()
+    let contract = Cw721Contract::<
+        DefaultOptionMetadataExtension,
+        Empty,
+        Empty,
+        DefaultOptionCollectionInfoExtension,
+    >::default();
+    contract
         .instantiate(
             deps.as_mut(),
             mock_env(),
@@ -65,29 +93,64 @@ fn proper_owner_initialization() {
             Cw721InstantiateMsg {
                 name: "collection_name".into(),
                 symbol: "collection_symbol".into(),
+                collection_info_extension: None,
+                creator: None,
                 minter: None,
                 withdraw_address: None,
+                max_batch_size: None,
+                max_supply: None,
+                token_id_policy: None,
+                allow_remint: None,
+                base_uri: None,
+                enable_snapshots: None,
             },
             "contract_name",
             "contract_version",
         )
         .unwrap();
 
-    let minter = MINTER.item.load(deps.as_ref().storage).unwrap().owner;
-    assert_eq!(minter, Some(info_owner.sender));
+    let minter = contract
+        .config
+        .minter
+        .item
+        .load(deps.as_ref().storage)
+        .unwrap()
+        .owner;
+    assert_eq!(minter, Some(info_owner.sender.clone()));
+    let creator = contract
+        .config
+        .creator
+        .item
+        .load(deps.as_ref().storage)
+        .unwrap()
+        .owner;
+    assert_eq!(creator, Some(info_owner.sender));
 }
 
 #[test]
 fn use_metadata_extension() {
     let mut deps = mock_dependencies();
-    let contract = Cw721Contract::<DefaultOptionMetadataExtension, Empty, Empty>::default();
+    let contract = Cw721Contract::<
+        DefaultOptionMetadataExtension,
+        Empty,
+        Empty,
+        DefaultOptionCollectionInfoExtension,
+    >::default();
 
     let info = mock_info(CREATOR_ADDR, &[]);
     let init_msg = Cw721InstantiateMsg {
         name: "collection_name".into(),
         symbol: "collection_symbol".into(),
+        collection_info_extension: None,
+        creator: None,
         minter: None,
         withdraw_address: None,
+        max_batch_size: None,
+        max_supply: None,
+        token_id_policy: None,
+        allow_remint: None,
+        base_uri: None,
+        enable_snapshots: None,
     };
     let env = mock_env();
     contract
@@ -125,6 +188,164 @@ fn use_metadata_extension() {
     assert_eq!(res.extension, extension);
 }
 
+#[test]
+fn test_metadata_validation() {
+    let valid = Metadata {
+        image: Some("ipfs://QmW4y7vLBxdDLSQ2H5Cf7a1TH4dQkVqnBfFbDh4mfgGx2g".to_string()),
+        external_url: Some("https://starships.example.com".to_string()),
+        background_color: Some("1a2B3c".to_string()),
+        attributes: Some(vec![
+            Trait {
+                display_type: None,
+                trait_type: "speed".to_string(),
+                value: "warp 9".to_string(),
+            },
+            Trait {
+                display_type: Some("number".to_string()),
+                trait_type: "crew".to_string(),
+                value: "430".to_string(),
+            },
+        ]),
+        ..Metadata::default()
+    };
+    valid.validate_metadata().unwrap();
+    Some(valid.clone()).validate_metadata().unwrap();
+    None::<Metadata>.validate_metadata().unwrap();
+
+    // url schemes
+    let metadata = Metadata {
+        image: Some("ftp://starships.example.com/enterprise.png".to_string()),
+        ..valid.clone()
+    };
+    assert_eq!(
+        metadata.validate_metadata().unwrap_err(),
+        Cw721ContractError::UnsupportedUrlScheme {
+            url: "ftp://starships.example.com/enterprise.png".to_string()
+        }
+    );
+    let metadata = Metadata {
+        animation_url: Some("starships.example.com".to_string()),
+        ..valid.clone()
+    };
+    assert_eq!(
+        metadata.validate_metadata().unwrap_err(),
+        Cw721ContractError::InvalidUrl {
+            url: "starships.example.com".to_string()
+        }
+    );
+
+    // background color
+    for color in ["#1a2b3c", "1a2b3", "1a2b3g"] {
+        let metadata = Metadata {
+            background_color: Some(color.to_string()),
+            ..valid.clone()
+        };
+        assert_eq!(
+            metadata.validate_metadata().unwrap_err(),
+            Cw721ContractError::InvalidBackgroundColor {
+                color: color.to_string()
+            }
+        );
+    }
+
+    // traits
+    let speed = Trait {
+        display_type: None,
+        trait_type: "speed".to_string(),
+        value: "warp 9".to_string(),
+    };
+    let metadata = Metadata {
+        attributes: Some(vec![speed.clone(), speed.clone()]),
+        ..valid.clone()
+    };
+    assert_eq!(
+        metadata.validate_metadata().unwrap_err(),
+        Cw721ContractError::DuplicateTraitType {
+            trait_type: "speed".to_string()
+        }
+    );
+    let metadata = Metadata {
+        attributes: Some(
+            (0..=MAX_METADATA_TRAITS)
+                .map(|i| Trait {
+                    trait_type: i.to_string(),
+                    ..speed.clone()
+                })
+                .collect(),
+        ),
+        ..valid.clone()
+    };
+    assert_eq!(
+        metadata.validate_metadata().unwrap_err(),
+        Cw721ContractError::TooManyTraits {
+            max_traits: MAX_METADATA_TRAITS
+        }
+    );
+
+    // size limits
+    let metadata = Metadata {
+        description: Some("a".repeat(MAX_METADATA_TEXT_LENGTH as usize + 1)),
+        ..valid
+    };
+    assert_eq!(
+        metadata.validate_metadata().unwrap_err(),
+        Cw721ContractError::MetadataFieldTooLong {
+            field: "description".to_string(),
+            max_length: MAX_METADATA_TEXT_LENGTH
+        }
+    );
+
+    // mint rejects invalid metadata
+    let mut deps = mock_dependencies();
+    let contract = Cw721Contract::<
+        DefaultOptionMetadataExtension,
+        Empty,
+        Empty,
+        DefaultOptionCollectionInfoExtension,
+    >::default();
+    let info = mock_info(CREATOR_ADDR, &[]);
+    let init_msg = Cw721InstantiateMsg {
+        name: "collection_name".into(),
+        symbol: "collection_symbol".into(),
+        collection_info_extension: None,
+        creator: None,
+        minter: None,
+        withdraw_address: None,
+        max_batch_size: None,
+        max_supply: None,
+        token_id_policy: None,
+        allow_remint: None,
+        base_uri: None,
+        enable_snapshots: None,
+    };
+    contract
+        .instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            init_msg,
+            "contract_name",
+            "contract_version",
+        )
+        .unwrap();
+    let exec_msg = Cw721ExecuteMsg::Mint {
+        token_id: "Enterprise".to_string(),
+        owner: "john".to_string(),
+        token_uri: None,
+        extension: Some(metadata),
+    };
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), info, exec_msg)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Cw721ContractError::MetadataFieldTooLong {
+            field: "description".to_string(),
+            max_length: MAX_METADATA_TEXT_LENGTH
+        }
+    );
+}
+
 #[test]
 fn test_migrate() {
     let mut deps = mock_dependencies();
@@ -154,16 +375,47 @@ fn test_migrate() {
         });
         v16::entry::execute(deps.as_mut(), env.clone(), info, msg).unwrap();
     }
+    // owner grants operator before migration
+    v16::entry::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &[]),
+        v16::ExecuteMsg::ApproveAll {
+            operator: "operator".into(),
+            expires: None,
+        },
+    )
+    .unwrap();
 
     // assert new data before migration:
     // - ownership and collection info throws NotFound Error
-    MINTER.item.load(deps.as_ref().storage).unwrap_err(); // cw_ownable in v16 is used for minter
-    let contract = Cw721Contract::<DefaultOptionMetadataExtension, Empty, Empty>::default();
+    let contract = Cw721Contract::<
+        DefaultOptionMetadataExtension,
+        Empty,
+        Empty,
+        DefaultOptionCollectionInfoExtension,
+    >::default();
+    // cw_ownable in v16 is used for minter
+    contract
+        .config
+        .minter
+        .item
+        .load(deps.as_ref().storage)
+        .unwrap_err();
     contract
         .query_collection_info(deps.as_ref(), env.clone())
         .unwrap_err();
     // - query in new minter and creator ownership store throws NotFound Error (in v16 it was stored outside cw_ownable, in dedicated "minter" store)
-    MINTER.get_ownership(deps.as_ref().storage).unwrap_err();
+    contract
+        .config
+        .minter
+        .get_ownership(deps.as_ref().storage)
+        .unwrap_err();
+    contract
+        .config
+        .creator
+        .get_ownership(deps.as_ref().storage)
+        .unwrap_err();
     // assert legacy data before migration:
     // - version
     let version = cw2::get_contract_version(deps.as_ref().storage)
@@ -177,7 +429,14 @@ fn test_migrate() {
     // - legacy collection info is set
     let legacy_collection_info_store: Item<cw721_016::ContractInfoResponse> = Item::new("nft_info");
     let all_tokens = contract
-        .query_all_tokens(deps.as_ref(), env.clone(), None, Some(MAX_LIMIT))
+        .query_all_tokens(
+            deps.as_ref(),
+            env.clone(),
+            None,
+            None,
+            Some(MAX_LIMIT),
+            None,
+        )
         .unwrap();
     assert_eq!(all_tokens.tokens.len(), 200);
     for token_id in 0..200 {
@@ -187,18 +446,24 @@ fn test_migrate() {
         assert_eq!(token.owner.as_str(), "owner");
     }
 
-    Cw721Contract::<DefaultOptionMetadataExtension, Empty, Empty>::default()
-        .migrate(
-            deps.as_mut(),
-            env.clone(),
-            crate::msg::Cw721MigrateMsg::WithUpdate {
-                minter: None,
-                creator: None,
-            },
-            "contract_name",
-            "contract_version",
-        )
-        .unwrap();
+    Cw721Contract::<
+        DefaultOptionMetadataExtension,
+        Empty,
+        Empty,
+        DefaultOptionCollectionInfoExtension,
+    >::default()
+    .migrate(
+        deps.as_mut(),
+        env.clone(),
+        crate::msg::Cw721MigrateMsg::WithUpdate {
+            minter: None,
+            creator: None,
+            enable_snapshots: None,
+        },
+        "contract_name",
+        "contract_version",
+    )
+    .unwrap();
 
     // version
     let version = cw2::get_contract_version(deps.as_ref().storage)
@@ -208,13 +473,25 @@ fn test_migrate() {
     assert_ne!(version, "0.16.0");
 
     // assert minter ownership
-    let minter_ownership = MINTER
+    let minter_ownership = contract
+        .config
+        .minter
         .get_ownership(deps.as_ref().storage)
         .unwrap()
         .owner
         .map(|a| a.into_string());
     assert_eq!(minter_ownership, Some("legacy_minter".to_string()));
 
+    // assert creator ownership, in v16 creator and minter were the same
+    let creator_ownership = contract
+        .config
+        .creator
+        .get_ownership(deps.as_ref().storage)
+        .unwrap()
+        .owner
+        .map(|a| a.into_string());
+    assert_eq!(creator_ownership, Some("legacy_minter".to_string()));
+
     // assert collection info
     let collection_info = contract
         .query_collection_info(deps.as_ref(), env.clone())
@@ -225,9 +502,36 @@ fn test_migrate() {
     };
     assert_eq!(collection_info, legacy_contract_info);
 
+    // assert operator grants are backfilled
+    let grants = contract
+        .query_operator_grants(
+            deps.as_ref(),
+            env.clone(),
+            "operator".to_string(),
+            false,
+            None,
+            None,
+        )
+        .unwrap();
+    assert_eq!(grants.grants.len(), 1);
+    assert_eq!(grants.grants[0].granter, "owner");
+
+    // assert balances are backfilled
+    let balance = contract
+        .query_balance_of(deps.as_ref(), env.clone(), "owner".to_string())
+        .unwrap();
+    assert_eq!(balance.balance, 200);
+
     // assert tokens
     let all_tokens = contract
-        .query_all_tokens(deps.as_ref(), env.clone(), None, Some(MAX_LIMIT))
+        .query_all_tokens(
+            deps.as_ref(),
+            env.clone(),
+            None,
+            None,
+            Some(MAX_LIMIT),
+            None,
+        )
         .unwrap();
     assert_eq!(all_tokens.tokens.len(), 200);
 
@@ -243,7 +547,14 @@ fn test_migrate() {
     assert_eq!(legacy_collection_info.symbol, "legacy_symbol");
     // - tokens are unchanged/still exist
     let all_tokens = contract
-        .query_all_tokens(deps.as_ref(), env.clone(), None, Some(MAX_LIMIT))
+        .query_all_tokens(
+            deps.as_ref(),
+            env.clone(),
+            None,
+            None,
+            Some(MAX_LIMIT),
+            None,
+        )
         .unwrap();
     assert_eq!(all_tokens.tokens.len(), 200);
     for token_id in 0..200 {
@@ -252,4 +563,287 @@ fn test_migrate() {
             .unwrap();
         assert_eq!(token.owner.as_str(), "owner");
     }
+
+    // backfills are only done on the first migration, stores are maintained afterwards
+    contract
+        .config
+        .balances
+        .save(deps.as_mut().storage, &Addr::unchecked("owner"), &199)
+        .unwrap();
+    contract
+        .config
+        .operators
+        .save(
+            deps.as_mut().storage,
+            (&Addr::unchecked("owner"), &Addr::unchecked("unmirrored")),
+            &crate::Expiration::Never {},
+        )
+        .unwrap();
+    let res = contract
+        .migrate(
+            deps.as_mut(),
+            env.clone(),
+            crate::msg::Cw721MigrateMsg::WithUpdate {
+                minter: None,
+                creator: None,
+                enable_snapshots: Some(true),
+            },
+            "contract_name",
+            "contract_version",
+        )
+        .unwrap();
+    let migrate = Cw721Event::try_from(&res.events[0]).unwrap();
+    match migrate {
+        Cw721Event::Migrate {
+            migrated_operator_grants,
+            migrated_balances,
+            migrated_approved_tokens,
+            enabled_snapshots,
+            ..
+        } => {
+            assert_eq!(migrated_operator_grants, None);
+            assert_eq!(migrated_balances, None);
+            assert_eq!(migrated_approved_tokens, None);
+            assert_eq!(enabled_snapshots, Some(env.block.height + 1));
+        }
+        event => panic!("Unexpected event: {event:?}"),
+    }
+    let grants = contract
+        .query_operator_grants(
+            deps.as_ref(),
+            env.clone(),
+            "unmirrored".to_string(),
+            false,
+            None,
+            None,
+        )
+        .unwrap();
+    assert_eq!(grants.grants.len(), 0);
+    let balance = contract
+        .query_balance_of(deps.as_ref(), env.clone(), "owner".to_string())
+        .unwrap();
+    assert_eq!(balance.balance, 199);
+
+    // snapshots can be enabled on migration, current owners and balances are snapshotted
+    let owner = contract
+        .query_owner_of_at_height(
+            deps.as_ref(),
+            env.clone(),
+            "0".to_string(),
+            env.block.height + 1,
+        )
+        .unwrap();
+    assert_eq!(owner.owner, Some("owner".to_string()));
+    let balance = contract
+        .query_balance_of_at_height(
+            deps.as_ref(),
+            env.clone(),
+            "owner".to_string(),
+            env.block.height + 1,
+        )
+        .unwrap();
+    assert_eq!(balance.balance, 199);
+    // backfilled snapshots are not visible at the migration height itself
+    contract
+        .query_owner_of_at_height(
+            deps.as_ref(),
+            env.clone(),
+            "0".to_string(),
+            env.block.height,
+        )
+        .unwrap_err();
+    contract
+        .query_balance_of_at_height(
+            deps.as_ref(),
+            env.clone(),
+            "owner".to_string(),
+            env.block.height,
+        )
+        .unwrap_err();
+    contract
+        .query_owner_of_at_height(
+            deps.as_ref(),
+            env.clone(),
+            "0".to_string(),
+            env.block.height - 1,
+        )
+        .unwrap_err();
+}
+
+#[test]
+fn test_default_storage_keys() {
+    let keys = Cw721StorageKeys::default();
+    assert_eq!(keys, crate::cw721_storage_keys!(""));
+    // creator is stored using cw-ownable's key, as in previous versions
+    assert_eq!(keys.creator, cw_ownable::OWNERSHIP_KEY);
+    assert_eq!(keys.nft_info, "tokens");
+    assert_eq!(keys.nft_info_owner, "tokens__owner");
+}
+
+#[test]
+fn test_decrement_balance_underflow() {
+    let mut deps = mock_dependencies();
+    let contract = Cw721Contract::<
+        DefaultOptionMetadataExtension,
+        Empty,
+        Empty,
+        DefaultOptionCollectionInfoExtension,
+    >::default();
+    let owner = Addr::unchecked("owner");
+
+    contract
+        .config
+        .increment_balance(deps.as_mut().storage, &owner)
+        .unwrap();
+    let balance = contract
+        .config
+        .decrement_balance(deps.as_mut().storage, &owner)
+        .unwrap();
+    assert_eq!(balance, 0);
+
+    // balance out of sync returns an error instead of panicking
+    let err = contract
+        .config
+        .decrement_balance(deps.as_mut().storage, &owner)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::overflow(OverflowError::new(OverflowOperation::Sub, 0, 1))
+    );
+}
+
+#[test]
+fn test_decrement_tokens_underflow() {
+    let mut deps = mock_dependencies();
+    let contract = Cw721Contract::<
+        DefaultOptionMetadataExtension,
+        Empty,
+        Empty,
+        DefaultOptionCollectionInfoExtension,
+    >::default();
+
+    contract
+        .config
+        .increment_tokens_by(deps.as_mut().storage, 2)
+        .unwrap();
+
+    // token count out of sync returns an error instead of panicking
+    let err = contract
+        .config
+        .decrement_tokens_by(deps.as_mut().storage, 3)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::overflow(OverflowError::new(OverflowOperation::Sub, 2, 3))
+    );
+    let count = contract
+        .config
+        .decrement_tokens_by(deps.as_mut().storage, 2)
+        .unwrap();
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn test_cw721_event_round_trip() {
+    let mint = Cw721Event::Mint {
+        minter: "minter".to_string(),
+        owner: "owner".to_string(),
+        token_id: "1".to_string(),
+    };
+    let transfer = Cw721Event::Transfer {
+        sender: "owner".to_string(),
+        recipient: "recipient".to_string(),
+        token_id: "1".to_string(),
+    };
+    let mint_event: Event = mint.clone().into();
+    assert_eq!(
+        mint_event,
+        Event::new("mint")
+            .add_attribute("minter", "minter")
+            .add_attribute("owner", "owner")
+            .add_attribute("token_id", "1")
+    );
+
+    // events emitted on chain are prefixed and have additional attributes
+    let transfer_event: Event = transfer.clone().into();
+    let chain_events = vec![
+        Event::new("wasm").add_attribute("_contract_address", "cw721"),
+        Event::new("wasm-mint")
+            .add_attribute("_contract_address", "cw721")
+            .add_attributes(mint_event.attributes),
+        Event::new(format!("wasm-{}", transfer_event.ty))
+            .add_attribute("_contract_address", "cw721")
+            .add_attributes(transfer_event.attributes),
+        // same type emitted by another contract in the same transaction is skipped
+        Event::new("wasm-mint")
+            .add_attribute("_contract_address", "cw20")
+            .add_attribute("amount", "100"),
+    ];
+    assert_eq!(
+        Cw721Event::from_events(&chain_events),
+        vec![mint.clone(), transfer]
+    );
+
+    // optional fields are only emitted when set
+    let freeze = Cw721Event::FreezeNftInfo {
+        sender: "creator".to_string(),
+        token_id: None,
+    };
+    let set_user = Cw721Event::SetUser {
+        sender: "owner".to_string(),
+        token_id: "1".to_string(),
+        user: Some("user".to_string()),
+        expires: "expiration: never".to_string(),
+    };
+    let freeze_event: Event = freeze.clone().into();
+    assert_eq!(
+        freeze_event,
+        Event::new("freeze_nft_info").add_attribute("sender", "creator")
+    );
+    let set_user_event: Event = set_user.clone().into();
+    assert_eq!(
+        Cw721Event::from_events(&[freeze_event, set_user_event]),
+        vec![freeze, set_user]
+    );
+    let migrate = Cw721Event::Migrate {
+        from_version: "0.18.0".to_string(),
+        to_version: "0.19.0".to_string(),
+        creator: None,
+        minter: None,
+        creator_and_minter: None,
+        migrated_collection_name: None,
+        migrated_collection_symbol: None,
+        migrated_operator_grants: Some(0),
+        migrated_balances: None,
+        migrated_approved_tokens: None,
+        migrated_total_minted: None,
+        enabled_snapshots: Some(12346),
+    };
+    let migrate_event: Event = migrate.clone().into();
+    assert_eq!(
+        migrate_event,
+        Event::new("migrate")
+            .add_attribute("from_version", "0.18.0")
+            .add_attribute("to_version", "0.19.0")
+            .add_attribute("migrated_operator_grants", "0")
+            .add_attribute("enabled_snapshots", "12346")
+    );
+    assert_eq!(Cw721Event::try_from(&migrate_event).unwrap(), migrate);
+
+    // action attribute is added once per type for consumers of the default wasm event
+    let response = [mint.clone(), mint]
+        .into_iter()
+        .fold(Response::<Empty>::new(), |response, event| {
+            event.add_to_response(response)
+        });
+    assert_eq!(response.attributes, vec![("action", "mint")]);
+    assert_eq!(response.events.len(), 2);
+
+    // missing attributes are rejected
+    let err =
+        Cw721Event::try_from(&Event::new("burn").add_attribute("sender", "owner")).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("Missing attribute token_id in event burn")
+    );
 }